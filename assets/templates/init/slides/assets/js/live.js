@@ -0,0 +1,119 @@
+/**
+ * Live.js - Polls and audience questions while served by `slides serve`
+ * Polls from {{ poll(...) }} take one vote per device and show live results;
+ * question forms from {{ questions() }} feed the queue in presenter mode.
+ * Opened as files, polls stay static and forms are hidden.
+ */
+
+(() => {
+  const served = location.protocol.startsWith('http');
+  const polls = document.querySelectorAll('[data-poll]');
+  const forms = document.querySelectorAll('form[data-questions]');
+
+  if (!served) {
+    forms.forEach(form => form.hidden = true);
+    return;
+  }
+
+  // Anonymous id that lets a device change its vote
+  let voter = localStorage.getItem('slidesVoter');
+  if (!voter) {
+    voter = Math.random().toString(36).slice(2, 14);
+    localStorage.setItem('slidesVoter', voter);
+  }
+
+  function showResults(results) {
+    document.querySelectorAll(`[data-poll="${CSS.escape(results.poll)}"]`).forEach(poll => {
+      poll.classList.add('has-results');
+      poll.querySelectorAll('[data-option]').forEach(button => {
+        const count = results.votes[button.dataset.option] || 0;
+        const share = results.total ? Math.round(count / results.total * 100) : 0;
+        button.style.setProperty('--share', `${share}%`);
+        button.querySelector('.poll-count').textContent = `${count} · ${share}%`;
+      });
+      poll.querySelector('.poll-total').textContent = `${results.total}`;
+    });
+  }
+
+  polls.forEach(poll => {
+    const id = poll.dataset.poll;
+    const chosen = localStorage.getItem(`slidesVote:${id}`);
+
+    poll.querySelectorAll('[data-option]').forEach(button => {
+      button.classList.toggle('chosen', button.dataset.option === chosen);
+      button.addEventListener('click', () => {
+        fetch(`/_slides/polls/${encodeURIComponent(id)}`, {
+          method: 'POST',
+          body: JSON.stringify({ voter, option: button.dataset.option }),
+        })
+          .then(response => response.ok ? response.json() : Promise.reject(response))
+          .then(results => {
+            localStorage.setItem(`slidesVote:${id}`, button.dataset.option);
+            poll.querySelectorAll('[data-option]').forEach(other => other.classList.toggle('chosen', other === button));
+            showResults(results);
+          })
+          .catch(() => {});
+      });
+    });
+
+    fetch(`/_slides/polls/${encodeURIComponent(id)}`)
+      .then(response => response.ok ? response.json() : null)
+      .then(results => results && showResults(results))
+      .catch(() => {});
+  });
+
+  forms.forEach(form => form.addEventListener('submit', e => {
+    e.preventDefault();
+    const data = new FormData(form);
+    fetch('/_slides/questions', {
+      method: 'POST',
+      body: JSON.stringify({ text: data.get('text'), name: data.get('name') }),
+    })
+      .then(response => {
+        if (!response.ok) return;
+        form.reset();
+        form.classList.add('sent');
+        setTimeout(() => form.classList.remove('sent'), 2000);
+      })
+      .catch(() => {});
+  }));
+
+  // Question queue in presenter mode
+  const queue = document.getElementById('question-queue');
+
+  function loadQuestions() {
+    fetch('/_slides/questions')
+      .then(response => response.ok ? response.json() : Promise.reject(response))
+      .then(questions => {
+        const list = queue.querySelector('ol');
+        list.replaceChildren(...questions.filter(q => !q.answered).map(question => {
+          const item = document.createElement('li');
+          const text = document.createElement('span');
+          text.textContent = question.name ? `${question.text} — ${question.name}` : question.text;
+          const done = document.createElement('button');
+          done.type = 'button';
+          done.textContent = queue.dataset.answered;
+          done.addEventListener('click', () => {
+            fetch(`/_slides/questions/${question.id}/answered`, { method: 'POST' })
+              .then(loadQuestions)
+              .catch(() => {});
+          });
+          item.append(text, done);
+          return item;
+        }));
+        queue.querySelector('.question-count').textContent = list.children.length;
+        queue.hidden = false;
+      })
+      .catch(() => {});
+  }
+
+  if (queue) loadQuestions();
+
+  if ((polls.length || queue) && 'EventSource' in window) {
+    new EventSource('/_slides/live').onmessage = e => {
+      const event = JSON.parse(e.data);
+      if (event.type === 'poll') showResults(event.results);
+      if (event.type === 'questions' && queue) loadQuestions();
+    };
+  }
+})();
@@ -1,10 +1,17 @@
 /**
  * Presenter Mode - Speaker view with notes, timer, and keyboard navigation
  * Each slide now has its own presenter page (presenter/slide-N.html)
+ * Slide timings are recorded and can be downloaded as timings.json (t)
+ * Navigation steps through the fragments of a slide before moving on
+ * Audience pages follow the presenter via sync.js (and vice versa)
+ * With planned timings (decks.yaml `timing:`) the footer shows whether the
+ * talk is ahead or behind schedule and the time left for the current slide
  */
 
 const current = parseInt(document.body.dataset.current);
 const total = parseInt(document.body.dataset.total);
+const fragments = parseInt(document.body.dataset.fragments) || 0;
+let fragment = 0;
 
 // Persist timer across page navigations using sessionStorage
 let startTime = sessionStorage.getItem('presenterStartTime');
@@ -15,6 +22,98 @@ if (!startTime) {
     startTime = parseInt(startTime);
 }
 
+// Record the seconds spent on each slide, e.g. for video exports (--timings)
+const timings = JSON.parse(sessionStorage.getItem('presenterTimings') || '{}');
+const enteredAt = Date.now();
+window.addEventListener('pagehide', () => {
+    timings[current] = (timings[current] || 0) + (Date.now() - enteredAt) / 1000;
+    sessionStorage.setItem('presenterTimings', JSON.stringify(timings));
+});
+
+function downloadTimings() {
+    const recorded = { ...timings };
+    recorded[current] = (recorded[current] || 0) + (Date.now() - enteredAt) / 1000;
+    for (const slide in recorded) recorded[slide] = Math.round(recorded[slide] * 10) / 10;
+
+    const link = document.createElement('a');
+    link.href = URL.createObjectURL(new Blob([JSON.stringify(recorded, null, 2)], { type: 'application/json' }));
+    link.download = 'timings.json';
+    link.click();
+    URL.revokeObjectURL(link.href);
+}
+
+document.getElementById('timings').addEventListener('click', downloadTimings);
+
+// Fragment state of the slide, mirrored to the slide view (#fragment-N)
+function fragmentFromHash() {
+    const match = location.hash.match(/^#fragment-(\d+|last)$/);
+    if (!match) return 0;
+    return match[1] === 'last' ? fragments : parseInt(match[1]);
+}
+
+function showFragment(n) {
+    fragment = Math.max(0, Math.min(fragments, n));
+    if (!fragments) return;
+
+    history.replaceState(null, '', `#fragment-${fragment}`);
+    const view = document.getElementById('slide-view');
+    view.src = `${view.dataset.url}#fragment-${fragment}`;
+    document.getElementById('fragment-state').textContent = `${fragment} / ${fragments}`;
+
+    // While steps remain, the preview shows the fully revealed slide
+    const preview = document.getElementById('next-preview');
+    if (preview) {
+        preview.src = fragment < fragments ? preview.dataset.revealedSrc : preview.dataset.nextSrc;
+    }
+}
+
+showFragment(fragmentFromHash());
+
+function next() {
+    if (fragment < fragments) step(fragment + 1);
+    else go(current + 1);
+}
+
+function prev() {
+    if (fragment > 0) step(fragment - 1);
+    else go(current - 1, 'last');
+}
+
+// Reveal fragments and let the audience follow
+function step(n) {
+    showFragment(n);
+    publish(current, fragment);
+}
+
+function go(n, fragmentStep = 0) {
+    const target = Math.max(1, Math.min(total, n));
+    if (target === current) return;
+    publish(target, fragmentStep);
+    location.href = `slide-${target}.html#fragment-${fragmentStep}`;
+}
+
+function publish(slide, fragmentStep) {
+    if (window.SlidesSync) SlidesSync.publish({ slide, fragment: fragmentStep });
+}
+
+// Follow navigation on audience pages, e.g. a clicker used on the projector
+if (window.SlidesSync) {
+    SlidesSync.subscribe(state => {
+        const target = parseInt(state.slide);
+        if (!(target >= 1 && target <= total)) return;
+        if (target !== current) {
+            location.href = `slide-${target}.html#fragment-${state.fragment ?? 0}`;
+        } else {
+            showFragment(state.fragment === 'last' ? fragments : parseInt(state.fragment) || 0);
+        }
+    });
+}
+
+document.querySelectorAll('[data-go]').forEach(link => link.addEventListener('click', e => {
+    e.preventDefault();
+    link.dataset.go === 'next' ? next() : prev();
+}));
+
 // Timer update
 setInterval(() => {
     const s = Math.floor((Date.now() - startTime) / 1000);
@@ -22,6 +121,44 @@ setInterval(() => {
         `${String(Math.floor(s / 3600)).padStart(2, '0')}:${String(Math.floor((s % 3600) / 60)).padStart(2, '0')}:${String(s % 60).padStart(2, '0')}`;
 }, 1000);
 
+// Schedule: compare elapsed time to the slide's planned window
+const schedule = document.getElementById('schedule');
+
+function formatDuration(seconds) {
+    const s = Math.round(Math.abs(seconds));
+    return `${Math.floor(s / 60)}:${String(s % 60).padStart(2, '0')}`;
+}
+
+function updateSchedule() {
+    if (!schedule) return;
+    const elapsed = (Date.now() - startTime) / 1000;
+    const start = parseFloat(schedule.dataset.plannedStart);
+    const end = parseFloat(schedule.dataset.plannedEnd);
+    const state = document.getElementById('schedule-state');
+
+    if (elapsed < start) {
+        state.textContent = `${formatDuration(start - elapsed)} ${schedule.dataset.ahead}`;
+        state.className = 'ahead';
+    } else if (elapsed > end) {
+        state.textContent = `${formatDuration(elapsed - end)} ${schedule.dataset.behind}`;
+        state.className = 'behind';
+    } else {
+        state.textContent = schedule.dataset.onTime;
+        state.className = 'on-time';
+    }
+
+    const planned = parseFloat(schedule.dataset.planned);
+    const spent = (timings[current] || 0) + (Date.now() - enteredAt) / 1000;
+    const budget = document.getElementById('slide-budget');
+    budget.textContent = planned
+        ? `${planned - spent < 0 ? '-' : ''}${formatDuration(planned - spent)} ${schedule.dataset.slideLeft}`
+        : '';
+    budget.classList.toggle('behind', planned > 0 && spent > planned);
+}
+
+setInterval(updateSchedule, 1000);
+updateSchedule();
+
 // Clock update
 setInterval(() => {
     document.getElementById('clock').textContent = new Date().toLocaleTimeString();
@@ -37,36 +174,36 @@ document.addEventListener('keydown', e => {
         case ' ':
         case 'j':
         case 'l':
-            if (current < total) {
-                e.preventDefault();
-                location.href = `slide-${current + 1}.html`;
-            }
+            e.preventDefault();
+            next();
             break;
         case 'ArrowLeft':
         case 'PageUp':
         case 'k':
         case 'h':
-            if (current > 1) {
-                e.preventDefault();
-                location.href = `slide-${current - 1}.html`;
-            }
+            e.preventDefault();
+            prev();
             break;
         case 'Home':
             e.preventDefault();
-            location.href = 'slide-1.html';
+            go(1);
             break;
         case 'End':
         case 'G':
             e.preventDefault();
-            location.href = `slide-${total}.html`;
+            go(total);
             break;
         case 'Escape':
             e.preventDefault();
-            location.href = `../slide-${current}.html`;
+            location.href = `../slide-${current}.html#fragment-${fragment}`;
             break;
         case 'o':
             e.preventDefault();
             location.href = '../overview.html';
             break;
+        case 't':
+            e.preventDefault();
+            downloadTimings();
+            break;
     }
 });
@@ -0,0 +1,136 @@
+/**
+ * Remote Control - Drives the presentation from a phone (slides serve --remote)
+ * Each slide has its own remote page (remote/slide-N.html) with notes and timer
+ * Navigation is published via sync.js, so all connected pages follow
+ */
+
+const current = parseInt(document.body.dataset.current);
+const total = parseInt(document.body.dataset.total);
+const fragments = parseInt(document.body.dataset.fragments) || 0;
+let fragment = 0;
+
+// Persist timer across page navigations using sessionStorage
+let startTime = sessionStorage.getItem('remoteStartTime');
+if (!startTime) {
+    startTime = Date.now();
+    sessionStorage.setItem('remoteStartTime', startTime);
+} else {
+    startTime = parseInt(startTime);
+}
+
+const timings = JSON.parse(sessionStorage.getItem('remoteTimings') || '{}');
+const enteredAt = Date.now();
+window.addEventListener('pagehide', () => {
+    timings[current] = (timings[current] || 0) + (Date.now() - enteredAt) / 1000;
+    sessionStorage.setItem('remoteTimings', JSON.stringify(timings));
+});
+
+function fragmentFromHash() {
+    const match = location.hash.match(/^#fragment-(\d+|last)$/);
+    if (!match) return 0;
+    return match[1] === 'last' ? fragments : parseInt(match[1]);
+}
+
+function showFragment(n) {
+    fragment = Math.max(0, Math.min(fragments, n));
+    if (!fragments) return;
+
+    history.replaceState(null, '', `#fragment-${fragment}`);
+    document.getElementById('fragment-state').textContent = `${fragment} / ${fragments}`;
+}
+
+showFragment(fragmentFromHash());
+
+function next() {
+    if (fragment < fragments) step(fragment + 1);
+    else go(current + 1);
+}
+
+function prev() {
+    if (fragment > 0) step(fragment - 1);
+    else go(current - 1, 'last');
+}
+
+function step(n) {
+    showFragment(n);
+    publish(current, fragment);
+}
+
+function go(n, fragmentStep = 0) {
+    const target = Math.max(1, Math.min(total, n));
+    if (target === current) return;
+    publish(target, fragmentStep);
+    location.href = `slide-${target}.html#fragment-${fragmentStep}`;
+}
+
+function publish(slide, fragmentStep) {
+    if (window.SlidesSync) SlidesSync.publish({ slide, fragment: fragmentStep });
+}
+
+// Follow navigation from the laptop, e.g. keys pressed in presenter mode
+if (window.SlidesSync) {
+    SlidesSync.subscribe(state => {
+        const target = parseInt(state.slide);
+        if (!(target >= 1 && target <= total)) return;
+        if (target !== current) {
+            location.href = `slide-${target}.html#fragment-${state.fragment ?? 0}`;
+        } else {
+            showFragment(state.fragment === 'last' ? fragments : parseInt(state.fragment) || 0);
+        }
+    });
+}
+
+document.getElementById('next').addEventListener('click', next);
+document.getElementById('prev').addEventListener('click', prev);
+
+// Timer and schedule, as in presenter mode
+const schedule = document.getElementById('schedule');
+
+function formatDuration(seconds) {
+    const s = Math.round(Math.abs(seconds));
+    return `${Math.floor(s / 60)}:${String(s % 60).padStart(2, '0')}`;
+}
+
+function update() {
+    const s = Math.floor((Date.now() - startTime) / 1000);
+    document.getElementById('timer').textContent =
+        `${String(Math.floor(s / 3600)).padStart(2, '0')}:${String(Math.floor((s % 3600) / 60)).padStart(2, '0')}:${String(s % 60).padStart(2, '0')}`;
+
+    if (!schedule) return;
+    const elapsed = (Date.now() - startTime) / 1000;
+    const start = parseFloat(schedule.dataset.plannedStart);
+    const end = parseFloat(schedule.dataset.plannedEnd);
+    const state = document.getElementById('schedule-state');
+
+    if (elapsed < start) {
+        state.textContent = `${formatDuration(start - elapsed)} ${schedule.dataset.ahead}`;
+        state.className = 'ahead';
+    } else if (elapsed > end) {
+        state.textContent = `${formatDuration(elapsed - end)} ${schedule.dataset.behind}`;
+        state.className = 'behind';
+    } else {
+        state.textContent = schedule.dataset.onTime;
+        state.className = 'on-time';
+    }
+
+    const planned = parseFloat(schedule.dataset.planned);
+    const spent = (timings[current] || 0) + (Date.now() - enteredAt) / 1000;
+    document.getElementById('slide-budget').textContent = planned
+        ? `${planned - spent < 0 ? '-' : ''}${formatDuration(planned - spent)} ${schedule.dataset.slideLeft}`
+        : '';
+}
+
+setInterval(update, 1000);
+update();
+
+// Volume keys are not available to pages; hardware clickers send these
+document.addEventListener('keydown', e => {
+    if (e.ctrlKey || e.altKey || e.metaKey) return;
+    if (['ArrowRight', 'PageDown', ' '].includes(e.key)) {
+        e.preventDefault();
+        next();
+    } else if (['ArrowLeft', 'PageUp'].includes(e.key)) {
+        e.preventDefault();
+        prev();
+    }
+});
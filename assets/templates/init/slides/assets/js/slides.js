@@ -1,23 +1,31 @@
 /**
  * Slides.js - Presentation navigation
- * Keyboard (arrows, vim), touch swipe
+ * Keyboard (arrows, vim), touch swipe, fragments, narration auto-advance,
+ * presenter/audience sync (sync.js)
  */
 
 const current = parseInt(document.body.dataset.slide) || 1;
 const total = parseInt(document.body.dataset.total) || 1;
+const steps = collectFragments();
+let fragment = 0;
 let lastGPress = 0;
 
 document.addEventListener('keydown', handleKeydown);
 setupTouch();
+setupFragments();
+setupNarration();
+setupSync();
 
 function handleKeydown(e) {
   if (e.ctrlKey || e.altKey || e.metaKey) return;
+  // Typing a question must not change slides
+  if (e.target.closest && e.target.closest('input, textarea, select, [contenteditable]')) return;
 
   switch (e.key) {
     case 'ArrowRight': case 'PageDown': case ' ': case 'j': case 'l':
-      e.preventDefault(); go(current + 1); break;
+      e.preventDefault(); next(); break;
     case 'ArrowLeft': case 'PageUp': case 'k': case 'h':
-      e.preventDefault(); go(current - 1); break;
+      e.preventDefault(); prev(); break;
     case 'Home':
       e.preventDefault(); go(1); break;
     case 'End': case 'G':
@@ -41,22 +49,116 @@ function setupTouch() {
   document.addEventListener('touchstart', e => startX = e.changedTouches[0].screenX);
   document.addEventListener('touchend', e => {
     const diff = startX - e.changedTouches[0].screenX;
-    if (Math.abs(diff) > 50) go(diff > 0 ? current + 1 : current - 1);
+    if (Math.abs(diff) > 50) diff > 0 ? next() : prev();
   });
 }
 
-function go(n) {
+// Elements with data-fragment grouped by step: same numbers share a step,
+// unnumbered ones take their position (like the renderer's fragment count)
+function collectFragments() {
+  const byStep = new Map();
+  document.querySelectorAll('[data-fragment]').forEach((element, index) => {
+    const number = parseInt(element.dataset.fragment);
+    const step = number > 0 ? number : index + 1;
+    if (!byStep.has(step)) byStep.set(step, []);
+    byStep.get(step).push(element);
+  });
+  return [...byStep.keys()].sort((a, b) => a - b).map(step => byStep.get(step));
+}
+
+// #fragment-N reveals N steps, #fragment-last all of them
+function fragmentFromHash() {
+  const match = location.hash.match(/^#fragment-(\d+|last)$/);
+  if (!match) return 0;
+  return match[1] === 'last' ? steps.length : parseInt(match[1]);
+}
+
+function setupFragments() {
+  if (!steps.length) return;
+  document.documentElement.classList.add('has-fragments');
+  showFragment(fragmentFromHash());
+  window.addEventListener('hashchange', () => showFragment(fragmentFromHash()));
+}
+
+function showFragment(n) {
+  fragment = Math.max(0, Math.min(steps.length, n));
+  steps.forEach((elements, index) => elements.forEach(element => {
+    element.classList.toggle('visible', index < fragment);
+    element.classList.toggle('current-fragment', index === fragment - 1);
+  }));
+  if (steps.length) history.replaceState(null, '', `#fragment-${fragment}`);
+}
+
+function next() {
+  if (fragment < steps.length) step(fragment + 1);
+  else go(current + 1);
+}
+
+// Going back from the first step shows the previous slide fully revealed
+function prev() {
+  if (fragment > 0) step(fragment - 1);
+  else go(current - 1, 'last');
+}
+
+// Reveal fragments and let synced pages follow
+function step(n) {
+  showFragment(n);
+  publish(current, fragment);
+}
+
+function go(n, fragmentStep) {
   const target = Math.max(1, Math.min(total, n));
   if (target === current) return;
-  location.href = `slide-${target}.html`;
+  publish(target, fragmentStep ?? 0);
+  location.href = `slide-${target}.html${fragmentStep === undefined ? '' : `#fragment-${fragmentStep}`}`;
+}
+
+function publish(slide, fragmentStep) {
+  if (window.SlidesSync) SlidesSync.publish({ slide, fragment: fragmentStep });
+}
+
+document.querySelectorAll('[data-go]').forEach(link => link.addEventListener('click', e => {
+  e.preventDefault();
+  link.dataset.go === 'next' ? next() : prev();
+}));
+
+// Follow navigation in the presenter view and on other devices
+function setupSync() {
+  if (!window.SlidesSync) return;
+  SlidesSync.subscribe(state => {
+    const target = parseInt(state.slide);
+    if (!(target >= 1 && target <= total)) return;
+    if (target !== current) {
+      location.href = `slide-${target}.html#fragment-${state.fragment ?? 0}`;
+    } else {
+      showFragment(state.fragment === 'last' ? steps.length : parseInt(state.fragment) || 0);
+    }
+  });
+}
+
+// Play the slide's narration clip; go on when it ends if auto-advance is on
+function setupNarration() {
+  const audio = document.getElementById('narration');
+  if (!audio) return;
+  if ('autoAdvance' in audio.dataset) {
+    audio.addEventListener('ended', () => go(current + 1));
+  }
+  // Browsers may block autoplay until the first interaction
+  audio.play().catch(() => {
+    document.addEventListener('keydown', () => audio.play(), { once: true });
+  });
 }
 
 function openPresenter() {
   window.open(
-    `presenter/slide-${current}.html`,
+    `presenter/slide-${current}.html#fragment-${fragment}`,
     'presenter',
     `width=1200,height=800,left=${screenX + outerWidth},top=${screenY}`
   );
 }
 
-window.Slides = { go, current, total };
+window.Slides = {
+  go, next, prev, showFragment, current, total,
+  fragments: steps.length,
+  get fragment() { return fragment; },
+};
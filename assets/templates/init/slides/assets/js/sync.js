@@ -0,0 +1,40 @@
+/**
+ * Sync.js - Keeps presenter and audience pages on the same slide and fragment
+ * BroadcastChannel between windows of this browser, and the events of
+ * `slides serve` between devices. States look like { slide: 3, fragment: 1 }.
+ */
+
+window.SlidesSync = (() => {
+  const id = Math.random().toString(36).slice(2);
+  const listeners = [];
+  // Slide previews embedded in the presenter view must not follow on their own
+  const enabled = window.self === window.top;
+  const served = location.protocol.startsWith('http');
+  // Audience devices only follow; the server accepts navigation from the
+  // presenter's machine and paired remotes, whose pages set data-publish
+  const loopback = ['localhost', '127.0.0.1', '[::1]'].includes(location.hostname);
+  const publisher = loopback || document.currentScript?.dataset.publish !== undefined;
+  const channel = enabled && 'BroadcastChannel' in window ? new BroadcastChannel('slides-sync') : null;
+
+  function receive(state) {
+    if (!state || state.source === id) return;
+    listeners.forEach(listener => listener(state));
+  }
+
+  if (channel) channel.onmessage = e => receive(e.data);
+  if (enabled && served && 'EventSource' in window) {
+    new EventSource('/_slides/events').onmessage = e => receive(JSON.parse(e.data));
+  }
+
+  function publish(state) {
+    if (!enabled) return;
+    const message = { ...state, source: id };
+    if (channel) channel.postMessage(message);
+    if (served && publisher) {
+      fetch('/_slides/state', { method: 'POST', body: JSON.stringify(message), keepalive: true })
+        .catch(() => {});
+    }
+  }
+
+  return { publish, subscribe: listener => listeners.push(listener) };
+})();
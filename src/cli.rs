@@ -1,6 +1,10 @@
 //! CLI definitions and command structures
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use slides_rs::infrastructure::pdf::{
+    EncryptionAlgorithm, PageSize, Scaling, Watermark, WatermarkPosition,
+};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "slides")]
@@ -9,6 +13,20 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Progress and result format; json prints one JSON object per line
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Human)]
+    pub message_format: OutputFormat,
+
+    /// Only print errors, and the result object with --message-format json
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
 }
 
 #[derive(Subcommand)]
@@ -25,6 +43,18 @@ pub enum Commands {
         /// Language for translations (e.g. "de", "en")
         #[arg(short, long, default_value = "en")]
         lang: String,
+
+        /// Build HTML only, without thumbnails
+        #[arg(long)]
+        no_thumbnails: bool,
+
+        /// Also render thumbnails at 2x resolution
+        #[arg(long)]
+        retina: bool,
+
+        /// Browser binary for thumbnails (default: $SLIDES_CHROME or an installed Chrome)
+        #[arg(long)]
+        chrome_path: Option<PathBuf>,
     },
 
     /// Watch for changes and rebuild automatically
@@ -36,6 +66,75 @@ pub enum Commands {
         /// Language for translations (e.g. "de", "en")
         #[arg(short, long, default_value = "en")]
         lang: String,
+
+        /// Build HTML only, without thumbnails
+        #[arg(long)]
+        no_thumbnails: bool,
+
+        /// Also render thumbnails at 2x resolution
+        #[arg(long)]
+        retina: bool,
+
+        /// Browser binary for thumbnails (default: $SLIDES_CHROME or an installed Chrome)
+        #[arg(long)]
+        chrome_path: Option<PathBuf>,
+    },
+
+    /// Serve the presentation locally, keeping presenter and audience pages in sync
+    Serve {
+        /// Deck name from decks.yaml
+        #[arg(short, long, default_value = "default")]
+        deck: String,
+
+        /// Language for translations (e.g. "de", "en")
+        #[arg(short, long, default_value = "en")]
+        lang: String,
+
+        /// Address to listen on; use 0.0.0.0 to reach it from other devices
+        /// [default: 127.0.0.1, or 0.0.0.0 with --remote]
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8000)]
+        port: u16,
+
+        /// Rebuild on changes while serving
+        #[arg(long)]
+        watch: bool,
+
+        /// Serve a remote control for phones on the local network, paired with a pin
+        #[arg(long)]
+        remote: bool,
+
+        /// Build HTML only, without thumbnails
+        #[arg(long)]
+        no_thumbnails: bool,
+
+        /// Browser binary for thumbnails (default: $SLIDES_CHROME or an installed Chrome)
+        #[arg(long)]
+        chrome_path: Option<PathBuf>,
+    },
+
+    /// Check decks.yaml, templates and rendered pages for problems
+    Lint {
+        /// Deck name from decks.yaml (default: all decks)
+        #[arg(short, long)]
+        deck: Option<String>,
+
+        /// Language for translations (e.g. "de", "en")
+        #[arg(short, long, default_value = "en")]
+        lang: String,
+
+        /// Also fail on warnings
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Check the rendered presentation
+    Check {
+        #[command(subcommand)]
+        check: Check,
     },
 
     /// Create a new slides project
@@ -64,10 +163,22 @@ pub enum Commands {
         #[arg(short, long, default_value = "en")]
         lang: String,
 
-        /// Password for PDF protection. Without value, a secure password is generated.
-        #[arg(short, long, num_args(0..=1), default_missing_value = "auto")]
+        /// Password required to open the PDF. Without value, a secure password is generated.
+        #[arg(short, long, visible_alias = "user-password", num_args(0..=1), default_missing_value = "auto")]
         password: Option<String>,
 
+        /// Password required to change permissions. Generated if not given.
+        #[arg(long, num_args(0..=1), default_missing_value = "auto")]
+        owner_password: Option<String>,
+
+        /// Encryption algorithm: aes256, aes128 or rc4
+        #[arg(long, default_value = "aes256")]
+        encryption: EncryptionAlgorithm,
+
+        /// Write the PDF passwords as JSON to this file ("-" for stdout)
+        #[arg(long)]
+        passwords_out: Option<String>,
+
         /// Disable printing
         #[arg(long)]
         no_print: bool,
@@ -76,8 +187,177 @@ pub enum Commands {
         #[arg(long)]
         no_copy: bool,
 
-        /// Output file
-        #[arg(short, long, default_value = "output/presentation.pdf")]
-        output: String,
+        /// Browser binary for thumbnails (default: $SLIDES_CHROME or an installed Chrome)
+        #[arg(long)]
+        chrome_path: Option<PathBuf>,
+
+        /// Use thumbnails rendered at 2x resolution for a sharper PDF
+        #[arg(long)]
+        retina: bool,
+
+        /// One PDF page per fragment state instead of one per slide
+        #[arg(long)]
+        fragments: bool,
+
+        /// Output file [default: output/presentation.<format>]
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+
+        /// Export format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Pdf)]
+        format: ExportFormat,
+
+        /// Seconds each slide is shown in videos, unless set in decks.yaml
+        #[arg(long)]
+        duration: Option<f32>,
+
+        /// Slide timings recorded in presenter mode (timings.json), used for video durations
+        #[arg(long)]
+        timings: Option<PathBuf>,
+
+        /// Page size: 16:9, 4:3, a4 or WIDTHxHEIGHT in millimeters
+        #[arg(long, default_value = "16:9")]
+        page_size: PageSize,
+
+        /// Place slides to fit the page (letterboxed) or to fill it (cropped)
+        #[arg(long, default_value = "fit")]
+        scaling: Scaling,
+
+        /// Image compression
+        #[arg(long, value_enum, default_value_t = Compression::Flate)]
+        compression: Compression,
+
+        /// JPEG quality (1-100), used with --compression jpeg
+        #[arg(long, default_value_t = 85, value_parser = clap::value_parser!(u8).range(1..=100))]
+        quality: u8,
+
+        #[command(flatten)]
+        watermark: Box<WatermarkArgs>,
+
+        /// CSV file of recipients (columns: name, optional password and file);
+        /// exports one watermarked PDF with its own password per recipient
+        #[arg(long)]
+        recipients: Option<String>,
     },
 }
+
+impl Commands {
+    /// Name of the subcommand, as in the JSON result
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Init => "init",
+            Commands::Build { .. } => "build",
+            Commands::Watch { .. } => "watch",
+            Commands::Serve { .. } => "serve",
+            Commands::Lint { .. } => "lint",
+            Commands::Check { check } => check.name(),
+            Commands::New { .. } => "new",
+            Commands::ImportImages { .. } => "import-images",
+            Commands::Export { .. } => "export",
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Check {
+    /// Check slide pages for accessibility problems, slide by slide
+    A11y {
+        /// Deck name from decks.yaml
+        #[arg(short, long, default_value = "default")]
+        deck: String,
+
+        /// Language for translations (e.g. "de", "en")
+        #[arg(short, long, default_value = "en")]
+        lang: String,
+
+        /// Skip measuring text contrast in a browser
+        #[arg(long)]
+        no_contrast: bool,
+
+        /// Browser binary for contrast (default: $SLIDES_CHROME or an installed Chrome)
+        #[arg(long)]
+        chrome_path: Option<PathBuf>,
+
+        /// Also fail on warnings
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+impl Check {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::A11y { .. } => "check a11y",
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct WatermarkArgs {
+    /// Watermark text stamped on every page; may use {name} and other recipient columns
+    #[arg(long, conflicts_with = "watermark_image")]
+    pub watermark: Option<String>,
+
+    /// Watermark image (PNG or JPEG) stamped on every page
+    #[arg(long)]
+    pub watermark_image: Option<String>,
+
+    /// Watermark position: center, top, bottom, top-left, top-right, bottom-left or bottom-right
+    #[arg(long, default_value = "center")]
+    pub watermark_position: WatermarkPosition,
+
+    /// Watermark opacity (0.0 - 1.0)
+    #[arg(long, default_value_t = Watermark::DEFAULT_OPACITY)]
+    pub watermark_opacity: f32,
+
+    /// Watermark rotation in degrees (counter-clockwise)
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub watermark_rotation: f32,
+
+    /// Watermark font size in points, or image width in percent of the page
+    #[arg(long)]
+    pub watermark_size: Option<f32>,
+}
+
+impl WatermarkArgs {
+    pub fn watermark(self) -> Option<Watermark> {
+        let default = self
+            .watermark
+            .map(Watermark::text)
+            .or(self.watermark_image.map(Watermark::image))?;
+
+        Some(Watermark {
+            position: self.watermark_position,
+            opacity: self.watermark_opacity,
+            rotation: self.watermark_rotation,
+            size: self.watermark_size.unwrap_or(default.size),
+            ..default
+        })
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Pdf,
+    Mp4,
+    Gif,
+    /// MP4 with the slides' narration clips
+    Video,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Mp4 | ExportFormat::Video => "mp4",
+            ExportFormat::Gif => "gif",
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Compression {
+    None,
+    Flate,
+    Jpeg,
+}
@@ -0,0 +1,442 @@
+//! Console output - prints progress events and reports of the library, for
+//! people or as JSON lines for scripts
+
+use crate::cli::OutputFormat;
+use env_logger::Env;
+use log::Level;
+use serde_json::{json, Value};
+use slides_rs::infrastructure::video::VideoFormat;
+use slides_rs::model::{A11yReport, BuildReport, ExportReport, LintReport};
+use slides_rs::progress::ProgressEvent;
+use slides_rs::services::lint::{Issue, Severity};
+use slides_rs::Error;
+use std::fmt::Display;
+use std::io::Write;
+
+const RULE: &str = "─────────────────────────────────────────────────────";
+
+/// `println!` to the stream for text, see `Console::with_text_on_stderr`
+macro_rules! say {
+    ($out:expr) => {{
+        let _ = writeln!($out);
+    }};
+    ($out:expr, $($arg:tt)*) => {{
+        let _ = writeln!($out, $($arg)*);
+    }};
+}
+
+/// Where and how output goes, from `--message-format` and `--quiet`
+#[derive(Clone, Copy)]
+pub struct Console {
+    format: OutputFormat,
+    quiet: bool,
+    text_on_stderr: bool,
+}
+
+impl Console {
+    pub fn new(format: OutputFormat, quiet: bool) -> Self {
+        Self {
+            format,
+            quiet,
+            text_on_stderr: false,
+        }
+    }
+
+    /// Print text for people to stderr, keeping stdout for data such as the
+    /// passwords of `--passwords-out -`
+    pub fn with_text_on_stderr(mut self, text_on_stderr: bool) -> Self {
+        self.text_on_stderr = text_on_stderr;
+        self
+    }
+
+    /// Stream for text for people
+    fn out(self) -> Box<dyn Write> {
+        if self.text_on_stderr {
+            Box::new(std::io::stderr())
+        } else {
+            Box::new(std::io::stdout())
+        }
+    }
+
+    /// Log warnings to stderr, or as `warning` events in JSON mode; quiet
+    /// mode only logs errors
+    pub fn init_logger(self) {
+        let level = if self.quiet { "error" } else { "warn" };
+        let mut builder = env_logger::Builder::from_env(Env::default().default_filter_or(level));
+        if self.format == OutputFormat::Json {
+            builder
+                .target(env_logger::Target::Stdout)
+                .format(|buf, record| {
+                    let event = match record.level() {
+                        Level::Error | Level::Warn => "warning",
+                        _ => "log",
+                    };
+                    let line = json!({
+                        "event": event,
+                        "level": record.level().as_str().to_lowercase(),
+                        "message": record.args().to_string(),
+                    });
+                    writeln!(buf, "{line}")
+                });
+        }
+        builder.init();
+    }
+
+    /// Text for people, left out in quiet and JSON mode
+    pub fn human(self) -> bool {
+        self.format == OutputFormat::Human && !self.quiet
+    }
+
+    pub fn say(self, text: impl Display) {
+        if self.human() {
+            say!(self.out(), "{text}");
+        }
+    }
+
+    /// Handler for the progress events of the library
+    pub fn progress(self) -> impl Fn(&ProgressEvent) + Send + Sync + 'static {
+        move |event: &ProgressEvent| match self.format {
+            _ if self.quiet => {}
+            OutputFormat::Human => print_progress(&mut self.out(), event),
+            OutputFormat::Json => print_json(&json!(event)),
+        }
+    }
+
+    /// Final result object of a successful command in JSON mode
+    pub fn succeed(self, command: &str, result: Value) {
+        if self.format == OutputFormat::Json {
+            print_json(&json!({
+                "event": "result",
+                "command": command,
+                "ok": true,
+                "result": result,
+            }));
+        }
+    }
+
+    /// Report a failed command and return the process exit code
+    pub fn fail(self, command: &str, error: &Error) -> i32 {
+        let category = error.category();
+        match self.format {
+            OutputFormat::Human => eprintln!("❌ Error: {error}"),
+            OutputFormat::Json => print_json(&json!({
+                "event": "result",
+                "command": command,
+                "ok": false,
+                "error": {
+                    "category": category,
+                    "exit_code": category.exit_code(),
+                    "message": error.to_string(),
+                },
+            })),
+        }
+        category.exit_code()
+    }
+}
+
+impl Console {
+    pub fn built(self, report: &BuildReport) {
+        if self.human() {
+            build_done(&mut self.out(), report);
+        }
+    }
+
+    /// Passwords are printed even in quiet mode, as they are not kept anywhere
+    /// else unless written with `--passwords-out`; JSON results include them anyway
+    pub fn exported_pdf(self, report: &ExportReport, passwords_out: Option<&str>) {
+        if self.format != OutputFormat::Human {
+            return;
+        }
+        if let (Some("-"), Some(passwords)) = (passwords_out, &report.passwords) {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(passwords).unwrap_or_default()
+            );
+        }
+        pdf_done(&mut self.out(), report, passwords_out, self.quiet);
+    }
+
+    /// Issues are printed in every mode; quiet mode leaves out warnings
+    pub fn linted(self, report: &LintReport) {
+        for issue in self.shown(&report.issues) {
+            match self.format {
+                OutputFormat::Human => println!("{issue}"),
+                OutputFormat::Json => print_issue("lint_issue", issue, Value::Null),
+            }
+        }
+        self.say(lint_done(report));
+    }
+
+    /// Issues grouped by slide, like `linted`
+    pub fn checked_a11y(self, report: &A11yReport) {
+        for slide in &report.slides {
+            let mut issues = self.shown(&slide.issues).peekable();
+            if issues.peek().is_none() {
+                continue;
+            }
+            match self.format {
+                OutputFormat::Human => {
+                    println!("Slide {} ({})", slide.number, slide.template);
+                    for issue in issues {
+                        println!("  {issue}");
+                    }
+                }
+                OutputFormat::Json => {
+                    for issue in issues {
+                        print_issue("a11y_issue", issue, json!(slide.number));
+                    }
+                }
+            }
+        }
+        self.say(a11y_done(report));
+    }
+
+    fn shown(self, issues: &[Issue]) -> impl Iterator<Item = &Issue> {
+        issues
+            .iter()
+            .filter(move |issue| !self.quiet || issue.severity == Severity::Error)
+    }
+
+    pub fn exported_video(self, report: &ExportReport) {
+        if self.human() {
+            video_done(&mut self.out(), report);
+        }
+    }
+}
+
+/// An issue as event, with the slide it belongs to if given
+fn print_issue(event: &str, issue: &Issue, slide: Value) {
+    let mut value = json!(issue);
+    value["event"] = json!(event);
+    if !slide.is_null() {
+        value["slide"] = slide;
+    }
+    print_json(&value);
+}
+
+/// One JSON object per line; a closed pipe is not worth a panic
+fn print_json(value: &Value) {
+    let _ = writeln!(std::io::stdout().lock(), "{value}");
+}
+
+/// Print a progress event the way the CLI always has
+fn print_progress(out: &mut dyn Write, event: &ProgressEvent) {
+    match event {
+        ProgressEvent::BuildStarted { .. } => say!(out, "🎬 Building presentation\n"),
+        ProgressEvent::SlidesLoaded { deck, count } => {
+            say!(out, "📄 Loading deck '{deck}'... ✅ {count} slides")
+        }
+        ProgressEvent::TemplatesLoaded { count } => {
+            say!(out, "🧱 Loading templates... ✅ {count} templates")
+        }
+        ProgressEvent::PagesRendered { count } => say!(out, "🔨 Rendering... ✅ {count} pages"),
+        ProgressEvent::FilesWritten => say!(out, "💾 Writing files... ✅"),
+        ProgressEvent::AssetsCopied => say!(out, "📦 Copying assets... ✅"),
+        ProgressEvent::ThumbnailsStarted => {
+            let _ = write!(out, "📸 Generating thumbnails... ");
+            let _ = out.flush();
+        }
+        ProgressEvent::ThumbnailsGenerated { total, cached } if total == cached => {
+            say!(out, "✅ {total} thumbnails (all cached)")
+        }
+        ProgressEvent::ThumbnailsGenerated { total, cached } => {
+            say!(out, "✅ {total} thumbnails ({cached} cached)")
+        }
+        ProgressEvent::ThumbnailsFound { count } => say!(out, "📸 {count} thumbnails found"),
+        ProgressEvent::RecipientsLoaded { count } => say!(out, "👥 {count} recipients found"),
+        ProgressEvent::PdfGenerated { bytes } => {
+            say!(out, "   ✅ PDF generated ({} KB)\n", bytes / 1024)
+        }
+        ProgressEvent::ApplyingWatermark => say!(out, "💧 Applying watermark..."),
+        ProgressEvent::Encrypting { algorithm } => say!(out, "🔒 Encrypting ({algorithm})..."),
+        ProgressEvent::PasswordsWritten { path } => {
+            say!(out, "   ✅ Passwords written to {path}")
+        }
+        ProgressEvent::EncodingVideo {
+            format,
+            encoder,
+            clips,
+        } => match (format, encoder) {
+            (VideoFormat::Narrated, Some(ffmpeg)) => say!(
+                out,
+                "🎬 Encoding narrated MP4 ({clips} clips) with {}...",
+                ffmpeg.display()
+            ),
+            (_, Some(ffmpeg)) => say!(out, "🎬 Encoding MP4 with {}...", ffmpeg.display()),
+            (_, None) => say!(out, "🎬 Encoding GIF..."),
+        },
+        ProgressEvent::ServerStarted {
+            url,
+            remote_pin,
+            session,
+        } => {
+            say!(out, "🌐 Serving at {url}");
+            say!(
+                out,
+                "   Open presenter mode with P; all connected pages follow it"
+            );
+            if let Some(pin) = remote_pin {
+                say!(out, "📱 Remote control at {url}remote/ - pin: {pin}");
+            }
+            if let Some(session) = session {
+                say!(
+                    out,
+                    "🗳️  Poll votes and questions are saved to {}",
+                    session.display()
+                );
+            }
+            say!(out);
+        }
+        ProgressEvent::RemotePaired { pin } => {
+            say!(out, "📱 Remote paired - pin for another device: {pin}")
+        }
+        ProgressEvent::RemoteClientLockedOut { client, seconds } => {
+            say!(
+                out,
+                "🔒 Too many wrong pins from {client} - locked out for {seconds}s"
+            )
+        }
+        ProgressEvent::WatchStarted { paths } => {
+            say!(out, "\n{RULE}\n");
+            say!(out, "👀 Watching for changes in {}", paths.join(", "));
+            say!(out, "   Press Ctrl+C to exit\n");
+        }
+        ProgressEvent::ChangeDetected { path } => say!(out, "📝 Change detected: {path}"),
+        ProgressEvent::Rebuilding => say!(out, "\n🔨 Rebuilding..."),
+        ProgressEvent::BuildFailed { message } => eprintln!("❌ Error: {message}"),
+        _ => {}
+    }
+}
+
+fn build_done(out: &mut dyn Write, report: &BuildReport) {
+    let first_slide = report.output_dir.join("slide-1.html");
+    say!(
+        out,
+        "\n🎉 Done! Open {} in browser",
+        first_slide
+            .strip_prefix(std::env::current_dir().unwrap_or_default())
+            .unwrap_or(&first_slide)
+            .display()
+    );
+}
+
+/// Print the result of a PDF export, including generated passwords unless
+/// they were written to a file; quiet mode prints only the passwords
+fn pdf_done(out: &mut dyn Write, report: &ExportReport, passwords_out: Option<&str>, quiet: bool) {
+    if let [file] = report.files.as_slice() {
+        if file.recipient.is_none() {
+            if let (Some(encryption), None) = (&file.encryption, passwords_out) {
+                if !encryption.user_password.is_empty() {
+                    say!(out, "   ✅ User password: {}", encryption.user_password);
+                }
+                say!(out, "   ✅ Owner password: {}", encryption.owner_password);
+            }
+            if !quiet {
+                say!(out, "\n🎉 PDF saved to {}", file.path);
+            }
+            return;
+        }
+    }
+
+    if passwords_out.is_none() {
+        for file in &report.files {
+            if let (Some(recipient), Some(encryption)) = (&file.recipient, &file.encryption) {
+                say!(
+                    out,
+                    "   ✅ {recipient} → {} (password: {})",
+                    file.path,
+                    encryption.user_password
+                );
+            }
+        }
+    }
+    if !quiet {
+        say!(out, "\n🎉 {} PDFs saved", report.files.len());
+    }
+}
+
+fn lint_done(report: &LintReport) -> String {
+    let decks = report.decks.len();
+    match (report.errors(), report.warnings()) {
+        (0, 0) => format!("✅ {decks} decks checked, no issues"),
+        (errors, warnings) => {
+            format!("\n🔎 {decks} decks checked: {errors} errors, {warnings} warnings")
+        }
+    }
+}
+
+fn a11y_done(report: &A11yReport) -> String {
+    let slides = report.slides.len();
+    let contrast = if report.contrast {
+        ""
+    } else {
+        " (contrast not measured)"
+    };
+    match (report.errors(), report.warnings()) {
+        (0, 0) => format!("✅ {slides} slides checked{contrast}, no issues"),
+        (errors, warnings) => {
+            format!("\n🔎 {slides} slides checked{contrast}: {errors} errors, {warnings} warnings")
+        }
+    }
+}
+
+fn video_done(out: &mut dyn Write, report: &ExportReport) {
+    match report.duration {
+        Some(duration) => say!(out, "   ✅ {} slides, {duration:.1} seconds", report.slides),
+        None => say!(out, "   ✅ {} slides", report.slides),
+    }
+    if let Some(file) = report.files.first() {
+        say!(out, "\n🎉 Video saved to {}", file.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slides_rs::model::{EncryptionReport, ExportedFile};
+
+    fn encrypted(path: &str, recipient: Option<&str>, user_password: &str) -> ExportedFile {
+        ExportedFile {
+            path: path.into(),
+            recipient: recipient.map(Into::into),
+            encryption: Some(EncryptionReport {
+                algorithm: "aes-256".into(),
+                owner_password: "owner-secret".into(),
+                user_password: user_password.into(),
+            }),
+        }
+    }
+
+    fn pdf_text(report: &ExportReport, passwords_out: Option<&str>, quiet: bool) -> String {
+        let mut out = Vec::new();
+        pdf_done(&mut out, report, passwords_out, quiet);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_pdf_export_prints_generated_passwords() {
+        let report = ExportReport {
+            files: vec![encrypted("output/talk.pdf", None, "user-secret")],
+            ..Default::default()
+        };
+        let text = pdf_text(&report, None, true);
+        assert!(text.contains("User password: user-secret"), "{text}");
+        assert!(text.contains("Owner password: owner-secret"), "{text}");
+        assert!(!text.contains("PDF saved"), "{text}");
+
+        let report = ExportReport {
+            files: vec![
+                encrypted("output/alice.pdf", Some("Alice"), "alice-secret"),
+                encrypted("output/bob.pdf", Some("Bob"), "bob-secret"),
+            ],
+            ..Default::default()
+        };
+        let text = pdf_text(&report, None, true);
+        assert!(text.contains("Alice → output/alice.pdf (password: alice-secret)"));
+        assert!(text.contains("Bob → output/bob.pdf (password: bob-secret)"));
+        assert!(!text.contains("PDFs saved"), "{text}");
+
+        // Written to a file instead
+        assert_eq!(pdf_text(&report, Some("passwords.json"), true), "");
+    }
+}
@@ -1,5 +1,6 @@
 //! Centralized error handling for slides-rs
 
+use serde::Serialize;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -37,6 +38,9 @@ pub enum Error {
     #[error("Could not get current directory: {0}")]
     CurrentDir(std::io::Error),
 
+    #[error("Project directory not found: {0}")]
+    ProjectNotFound(PathBuf),
+
     // Config errors
     #[error("decks.yaml not found")]
     DecksNotFound,
@@ -56,6 +60,18 @@ pub enum Error {
     #[error("YAML parse error in '{path}': {message}")]
     YamlParse { path: PathBuf, message: String },
 
+    #[error("Invalid recipients file '{path}': {message}")]
+    RecipientsParse { path: PathBuf, message: String },
+
+    #[error("Invalid build manifest '{path}': {message}")]
+    ManifestParse { path: PathBuf, message: String },
+
+    #[error("Could not serialize the build manifest: {0}")]
+    ManifestSerialize(String),
+
+    #[error("Invalid data file '{path}': {message}")]
+    DataParse { path: PathBuf, message: String },
+
     // Template errors
     #[error("Template '{0}' not found")]
     TemplateNotFound(String),
@@ -74,8 +90,11 @@ pub enum Error {
     #[error("Thumbnails not found. Run 'slides build' first.")]
     ThumbnailsNotFound,
 
-    #[error("No thumbnails found in output/thumbnails/")]
-    NoThumbnails,
+    #[error("No thumbnails found in {}", .0.display())]
+    NoThumbnails(PathBuf),
+
+    #[error("The build in '{}' {message}. Run 'slides build' first.", .path.display())]
+    StaleBuild { path: PathBuf, message: String },
 
     #[error("PDF generation error: {0}")]
     PdfGeneration(String),
@@ -83,10 +102,20 @@ pub enum Error {
     #[error("PDF encryption error: {0}")]
     PdfEncryption(String),
 
+    #[error("Video export error: {0}")]
+    VideoExport(String),
+
     // Browser errors
+    #[error("No Chrome, Chromium or Edge found.\n\n{0}")]
+    BrowserNotFound(String),
+
     #[error("Browser error: {0}")]
     Browser(String),
 
+    // Server errors
+    #[error("Server error: {0}")]
+    Server(String),
+
     // Import errors
     #[error("Invalid PPTX file: {0}")]
     InvalidPptx(String),
@@ -100,6 +129,84 @@ pub enum Error {
 
     #[error("Could not watch path '{path}': {message}")]
     WatchPath { path: PathBuf, message: String },
+
+    // Lint errors
+    #[error("Lint found {errors} errors and {warnings} warnings")]
+    LintFailed { errors: usize, warnings: usize },
+
+    #[error("Accessibility check found {errors} errors and {warnings} warnings")]
+    A11yFailed { errors: usize, warnings: usize },
+}
+
+/// Kind of error, e.g. for exit codes of the command line tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    Io,
+    Config,
+    Template,
+    Project,
+    Export,
+    Browser,
+    Server,
+    Import,
+    Lint,
+}
+
+impl ErrorCategory {
+    /// Process exit code; 1 is left for unexpected failures and 2 for usage errors
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Config => 3,
+            ErrorCategory::Template => 4,
+            ErrorCategory::Browser => 5,
+            ErrorCategory::Io => 6,
+            ErrorCategory::Project => 7,
+            ErrorCategory::Export => 8,
+            ErrorCategory::Server => 9,
+            ErrorCategory::Import => 10,
+            ErrorCategory::Lint => 11,
+        }
+    }
+}
+
+impl Error {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::FileRead { .. }
+            | Error::FileWrite { .. }
+            | Error::CreateDir { .. }
+            | Error::ReadDir { .. }
+            | Error::NotFound(_)
+            | Error::CurrentDir(_)
+            | Error::ManifestSerialize(_)
+            | Error::WatcherInit(_)
+            | Error::WatchPath { .. } => ErrorCategory::Io,
+            Error::DecksNotFound
+            | Error::DecksParseError(_)
+            | Error::DeckNotFound(_)
+            | Error::NoSlides(_)
+            | Error::TranslationNotFound(_)
+            | Error::YamlParse { .. }
+            | Error::RecipientsParse { .. }
+            | Error::ManifestParse { .. }
+            | Error::DataParse { .. } => ErrorCategory::Config,
+            Error::TemplateNotFound(_) | Error::TemplateRender { .. } => ErrorCategory::Template,
+            Error::ProjectNotFound(_) | Error::DirNotEmpty | Error::DirExists(_) => {
+                ErrorCategory::Project
+            }
+            Error::ThumbnailsNotFound
+            | Error::NoThumbnails(_)
+            | Error::StaleBuild { .. }
+            | Error::PdfGeneration(_)
+            | Error::PdfEncryption(_)
+            | Error::VideoExport(_) => ErrorCategory::Export,
+            Error::BrowserNotFound(_) | Error::Browser(_) => ErrorCategory::Browser,
+            Error::Server(_) => ErrorCategory::Server,
+            Error::InvalidPptx(_) | Error::ZipError(_) => ErrorCategory::Import,
+            Error::LintFailed { .. } | Error::A11yFailed { .. } => ErrorCategory::Lint,
+        }
+    }
 }
 
 /// Result type alias for slides-rs
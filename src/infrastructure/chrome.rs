@@ -1,19 +1,28 @@
 //! Chrome module - Headless browser operations for thumbnails
 
 use crate::error::{Error, Result};
+use ::image::codecs::jpeg::JpegEncoder;
+use ::image::imageops::FilterType;
+use ::image::{DynamicImage, ImageFormat};
 use headless_chrome::{
     protocol::cdp::{Emulation, Page},
-    Browser,
+    Browser, LaunchOptions, Tab,
 };
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::Path;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-fn device_metrics(height: u32) -> Emulation::SetDeviceMetricsOverride {
+fn device_metrics(height: u32, scale_factor: f64) -> Emulation::SetDeviceMetricsOverride {
     Emulation::SetDeviceMetricsOverride {
         width: 1920,
         height,
-        device_scale_factor: 1.0,
+        device_scale_factor: scale_factor,
         mobile: false,
         scale: None,
         screen_width: None,
@@ -28,9 +37,161 @@ fn device_metrics(height: u32) -> Emulation::SetDeviceMetricsOverride {
     }
 }
 
-pub fn generate_thumbnails(output_dir: &Path, total_slides: usize) -> Result<()> {
-    print!("📸 Generating thumbnails... ");
+/// Size variant of a slide thumbnail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailSize {
+    /// 480 px wide JPEG for overview grids
+    Small,
+    /// 960 px wide JPEG, e.g. for the presenter's next slide preview
+    Medium,
+    /// 1920 px wide PNG, used for PDF export
+    Full,
+    /// 3840 px wide PNG rendered at device scale factor 2; only built on request
+    Retina,
+}
+
+impl ThumbnailSize {
+    /// Variants downscaled from the full thumbnail
+    const SCALED: [ThumbnailSize; 2] = [ThumbnailSize::Small, ThumbnailSize::Medium];
+
+    /// File path relative to the thumbnails directory
+    pub fn file_name(self, number: usize) -> String {
+        match self {
+            ThumbnailSize::Small => format!("small/slide-{number}.jpg"),
+            ThumbnailSize::Medium => format!("medium/slide-{number}.jpg"),
+            ThumbnailSize::Full => format!("slide-{number}.png"),
+            ThumbnailSize::Retina => format!("2x/slide-{number}.png"),
+        }
+    }
+
+    /// File path relative to the output directory
+    pub fn path(self, number: usize) -> String {
+        format!("thumbnails/{}", self.file_name(number))
+    }
+
+    fn width(self) -> u32 {
+        match self {
+            ThumbnailSize::Small => 480,
+            ThumbnailSize::Medium => 960,
+            ThumbnailSize::Full => 1920,
+            ThumbnailSize::Retina => 3840,
+        }
+    }
+}
+
+impl FromStr for ThumbnailSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "small" => Ok(ThumbnailSize::Small),
+            "medium" => Ok(ThumbnailSize::Medium),
+            "full" => Ok(ThumbnailSize::Full),
+            "2x" | "retina" => Ok(ThumbnailSize::Retina),
+            _ => Err(format!(
+                "invalid thumbnail size '{s}' (use small, medium, full or 2x)"
+            )),
+        }
+    }
+}
+
+/// Quality of the downscaled JPEG thumbnails
+const JPEG_QUALITY: u8 = 80;
+
+/// Thumbnails rendered in parallel browser tabs at most
+const MAX_TABS: usize = 4;
+
+/// Cache of slide fingerprints next to the thumbnails
+const CACHE_FILE: &str = "thumbnails.json";
 
+/// Part of every fingerprint; change it to invalidate all cached thumbnails
+/// when the way they are captured changes
+const CACHE_VERSION: &str = "1920x1080@2";
+
+/// Resolves once web fonts and images are loaded and the page has been painted,
+/// or after 10 seconds at the latest
+const WAIT_FOR_RENDER: &str = r#"
+Promise.race([
+  Promise.all([
+    document.fonts.ready,
+    ...Array.from(document.images, img => img.complete ? null : new Promise(resolve => {
+      img.addEventListener('load', resolve);
+      img.addEventListener('error', resolve);
+    })),
+  ]).then(() => new Promise(resolve => requestAnimationFrame(() => requestAnimationFrame(resolve)))),
+  new Promise(resolve => setTimeout(resolve, 10000)),
+]).then(() => true)
+"#;
+
+/// Reveals `step` fragments without transitions, resolving after the next paint
+fn show_fragment_script(step: usize) -> String {
+    format!(
+        r#"
+if (!document.getElementById('slides-capture')) {{
+  const style = document.createElement('style');
+  style.id = 'slides-capture';
+  style.textContent = '[data-fragment] {{ transition: none !important; }}';
+  document.head.appendChild(style);
+}}
+window.Slides && window.Slides.showFragment && window.Slides.showFragment({step});
+new Promise(resolve => requestAnimationFrame(() => requestAnimationFrame(() => resolve(true))))
+"#
+    )
+}
+
+/// Directory of the fragment state thumbnails, inside a thumbnails directory
+pub const FRAGMENTS_DIR: &str = "fragments";
+
+/// Thumbnail of slide `number` with `step` fragments revealed, relative to a
+/// thumbnails directory. The state with all fragments revealed is the slide thumbnail.
+pub fn fragment_file_name(number: usize, step: usize) -> String {
+    format!("{FRAGMENTS_DIR}/slide-{number}-{step}.png")
+}
+
+/// Environment variable pointing at the browser binary to use
+pub const CHROME_ENV: &str = "SLIDES_CHROME";
+
+/// Locate Chrome, Chromium or Edge: `chrome_path` if given, then the
+/// `SLIDES_CHROME` environment variable, then the usual install locations
+pub fn find_chrome(chrome_path: Option<&Path>) -> Result<PathBuf> {
+    let configured = chrome_path.map(Path::to_path_buf).or_else(|| {
+        std::env::var_os(CHROME_ENV)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    });
+
+    match configured {
+        Some(path) if path.is_file() => Ok(path),
+        Some(path) => Err(Error::Browser(format!(
+            "Browser not found at '{}'",
+            path.display()
+        ))),
+        None => headless_chrome::browser::default_executable()
+            .map_err(|_| Error::BrowserNotFound(get_chrome_install_hint().to_string())),
+    }
+}
+
+/// Thumbnails of a build: all slides, and how many were still up to date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ThumbnailStats {
+    pub total: usize,
+    pub cached: usize,
+}
+
+/// Generate the thumbnail variants of every slide in `output_dir` with the
+/// browser at `chrome`; `retina` adds 2x renders. `fragments` holds the fragment
+/// step count per slide to also capture every fragment state; when empty, only
+/// fully revealed slides are captured. Slides whose HTML and referenced
+/// assets did not change since the last run keep their thumbnails; the browser
+/// is only started if needed.
+pub fn generate_thumbnails(
+    output_dir: &Path,
+    total_slides: usize,
+    chrome: &Path,
+    retina: bool,
+    fragments: &[usize],
+    on_captured: &(dyn Fn(usize) + Sync),
+) -> Result<ThumbnailStats> {
     // Get absolute path
     let output_dir = output_dir.canonicalize().map_err(|e| Error::FileRead {
         path: output_dir.to_path_buf(),
@@ -44,54 +205,508 @@ pub fn generate_thumbnails(output_dir: &Path, total_slides: usize) -> Result<()>
         source: e,
     })?;
 
+    let mut cache = ThumbnailCache::load(&thumbnails_dir);
+    cache.remove_after(&thumbnails_dir, total_slides);
+    if !retina {
+        fs::remove_dir_all(thumbnails_dir.join("2x")).ok();
+    }
+    if fragments.is_empty() {
+        fs::remove_dir_all(thumbnails_dir.join(FRAGMENTS_DIR)).ok();
+        fs::remove_dir_all(thumbnails_dir.join("2x").join(FRAGMENTS_DIR)).ok();
+    }
+    let fragment_count = |number: usize| fragments.get(number - 1).copied().unwrap_or(0);
+
+    let mut sizes = ThumbnailSize::SCALED.to_vec();
+    if retina {
+        sizes.push(ThumbnailSize::Retina);
+    }
+
+    let mut stale = Vec::new();
+    for number in 1..=total_slides {
+        let fingerprint = slide_fingerprint(&output_dir, number)?;
+        let complete = sizes
+            .iter()
+            .all(|size| thumbnails_dir.join(size.file_name(number)).exists())
+            && (0..fragment_count(number)).all(|step| {
+                let name = fragment_file_name(number, step);
+                thumbnails_dir.join(&name).exists()
+                    && (!retina || thumbnails_dir.join("2x").join(&name).exists())
+            });
+        if !complete || !cache.is_fresh(&thumbnails_dir, number, &fingerprint) {
+            stale.push((number, fingerprint));
+        }
+    }
+
+    let cached = total_slides - stale.len();
+    let stats = ThumbnailStats {
+        total: total_slides,
+        cached,
+    };
+    if stale.is_empty() {
+        return Ok(stats);
+    }
+
     // Start browser
-    let browser = Browser::default().map_err(|e| {
-        let install_hint = get_chrome_install_hint();
+    let options = LaunchOptions::default_builder()
+        .path(Some(chrome.to_path_buf()))
+        .build()
+        .map_err(|e| Error::Browser(format!("Invalid browser options: {e}")))?;
+    let browser = Browser::new(options).map_err(|e| {
+        Error::Browser(format!(
+            "Could not start browser '{}': {e}",
+            chrome.display()
+        ))
+    })?;
+
+    let tabs = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .clamp(1, MAX_TABS)
+        .min(stale.len());
+    let queue = Mutex::new(stale.into_iter());
+    let cache = Mutex::new(cache);
+
+    let results: Vec<Result<()>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..tabs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let tab = open_tab(&browser, retina)?;
+                    loop {
+                        let Some((number, fingerprint)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        capture_slide(
+                            &tab,
+                            &output_dir,
+                            &thumbnails_dir,
+                            number,
+                            retina,
+                            fragment_count(number),
+                        )?;
+                        cache.lock().unwrap().insert(number, fingerprint);
+                        on_captured(number);
+                    }
+                    tab.close(false).ok();
+                    Ok(())
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|_| Err(Error::Browser("Thumbnail worker panicked".into())))
+            })
+            .collect()
+    });
+
+    // Keep the thumbnails captured so far, even if another slide failed
+    cache.into_inner().unwrap().save(&thumbnails_dir)?;
+    results.into_iter().collect::<Result<Vec<()>>>()?;
+
+    Ok(stats)
+}
+
+/// Text whose color contrast with its background is below the WCAG AA minimum
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LowContrast {
+    /// Element containing the text, e.g. "p"
+    pub tag: String,
+    /// Start of the text
+    pub text: String,
+    pub ratio: f32,
+    /// 4.5, or 3 for large text
+    pub required: f32,
+}
+
+/// Collects visible text below the contrast minimum as JSON. Backgrounds are
+/// the blended background colors of the ancestors; text on background images
+/// cannot be measured and is left out.
+const MEASURE_CONTRAST: &str = r#"
+(() => {
+  const parse = color => {
+    const match = color.match(/rgba?\(([^)]+)\)/);
+    if (!match) return null;
+    const parts = match[1].split(/[\s,\/]+/).filter(Boolean).map(Number);
+    return [parts[0], parts[1], parts[2], parts.length > 3 ? parts[3] : 1];
+  };
+  const blend = (top, bottom) => [0, 1, 2].map(i => top[i] * top[3] + bottom[i] * (1 - top[3])).concat(1);
+  const luminance = rgb => {
+    const channel = value => {
+      value /= 255;
+      return value <= 0.03928 ? value / 12.92 : Math.pow((value + 0.055) / 1.055, 2.4);
+    };
+    return 0.2126 * channel(rgb[0]) + 0.7152 * channel(rgb[1]) + 0.0722 * channel(rgb[2]);
+  };
+  const background = element => {
+    const layers = [];
+    for (let current = element; current; current = current.parentElement) {
+      const style = getComputedStyle(current);
+      if (style.backgroundImage !== 'none') return null;
+      const color = parse(style.backgroundColor);
+      if (color && color[3] > 0) {
+        layers.push(color);
+        if (color[3] >= 1) break;
+      }
+    }
+    return layers.reverse().reduce((bottom, top) => blend(top, bottom), [255, 255, 255, 1]);
+  };
+  const visible = element => {
+    for (let current = element; current; current = current.parentElement) {
+      if (getComputedStyle(current).opacity === '0') return false;
+    }
+    const style = getComputedStyle(element);
+    return style.visibility !== 'hidden' && element.getClientRects().length > 0;
+  };
+
+  const found = [];
+  for (const element of document.body.querySelectorAll('*')) {
+    const text = Array.from(element.childNodes)
+      .filter(node => node.nodeType === Node.TEXT_NODE)
+      .map(node => node.textContent)
+      .join(' ').replace(/\s+/g, ' ').trim();
+    if (!text || !visible(element)) continue;
+
+    const back = background(element);
+    if (!back) continue;
+    const style = getComputedStyle(element);
+    const fore = blend(parse(style.color) || [0, 0, 0, 1], back);
+    const [light, dark] = [luminance(fore), luminance(back)].sort((a, b) => b - a);
+    const ratio = (light + 0.05) / (dark + 0.05);
+
+    const size = parseFloat(style.fontSize);
+    const large = size >= 24 || (parseInt(style.fontWeight, 10) >= 700 && size >= 18.66);
+    const required = large ? 3 : 4.5;
+    if (ratio < required) {
+      found.push({
+        tag: element.tagName.toLowerCase(),
+        text: text.slice(0, 40),
+        ratio: Math.round(ratio * 100) / 100,
+        required,
+      });
+    }
+  }
+  return JSON.stringify(found);
+})()
+"#;
+
+/// Measure the text contrast of slides 1 to `total_slides` in `output_dir`
+/// with all fragments revealed, returning the low contrast text per slide
+pub fn measure_contrast(
+    output_dir: &Path,
+    total_slides: usize,
+    chrome: &Path,
+) -> Result<Vec<Vec<LowContrast>>> {
+    let output_dir = output_dir.canonicalize().map_err(|e| Error::FileRead {
+        path: output_dir.to_path_buf(),
+        source: e,
+    })?;
+
+    let options = LaunchOptions::default_builder()
+        .path(Some(chrome.to_path_buf()))
+        .build()
+        .map_err(|e| Error::Browser(format!("Invalid browser options: {e}")))?;
+    let browser = Browser::new(options).map_err(|e| {
         Error::Browser(format!(
-            "Could not start browser: {e}\n\n\
-            Chrome/Chromium is required for thumbnail generation.\n\n\
-            {install_hint}"
+            "Could not start browser '{}': {e}",
+            chrome.display()
         ))
     })?;
+    let tab = open_tab(&browser, false)?;
 
+    let mut slides = Vec::new();
+    for number in 1..=total_slides {
+        let file_url = format!(
+            "file://{}/slide-{number}.html#fragment-last",
+            output_dir.display()
+        );
+        tab.navigate_to(&file_url)
+            .and_then(|tab| tab.wait_until_navigated())
+            .map_err(|e| Error::Browser(format!("Could not load slide {number}: {e}")))?;
+        tab.evaluate(WAIT_FOR_RENDER, true)
+            .map_err(|e| Error::Browser(format!("Timeout rendering slide {number}: {e}")))?;
+
+        let result = tab
+            .evaluate(MEASURE_CONTRAST, false)
+            .map_err(|e| Error::Browser(format!("Could not measure slide {number}: {e}")))?;
+        let json = result
+            .value
+            .as_ref()
+            .and_then(|value| value.as_str())
+            .unwrap_or("[]");
+        slides.push(serde_json::from_str(json).map_err(|e| {
+            Error::Browser(format!("Invalid contrast result of slide {number}: {e}"))
+        })?);
+    }
+    tab.close(false).ok();
+
+    Ok(slides)
+}
+
+fn open_tab(browser: &Browser, retina: bool) -> Result<Arc<Tab>> {
     let tab = browser
         .new_tab()
         .map_err(|e| Error::Browser(format!("Could not open tab: {e}")))?;
 
     tab.set_default_timeout(Duration::from_secs(30));
+    let scale_factor = if retina { 2.0 } else { 1.0 };
+    tab.call_method(device_metrics(1080, scale_factor)).ok();
 
-    for i in 1..=total_slides {
-        let filename = format!("slide-{i}.html");
-        let output_display = output_dir.display();
-        let file_url = format!("file://{output_display}/{filename}");
+    Ok(tab)
+}
 
-        tab.call_method(device_metrics(1080)).ok();
+fn capture_slide(
+    tab: &Tab,
+    output_dir: &Path,
+    thumbnails_dir: &Path,
+    number: usize,
+    retina: bool,
+    fragments: usize,
+) -> Result<()> {
+    let output_display = output_dir.display();
+    // Slide thumbnails show all fragments
+    let file_url = format!("file://{output_display}/slide-{number}.html#fragment-last");
 
-        // Load page
-        tab.navigate_to(&file_url)
-            .map_err(|e| Error::Browser(format!("Could not load slide {i}: {e}")))?;
+    // Load page
+    tab.navigate_to(&file_url)
+        .and_then(|tab| tab.wait_until_navigated())
+        .map_err(|e| Error::Browser(format!("Could not load slide {number}: {e}")))?;
 
-        // Wait for page load
-        tab.wait_for_element("body")
-            .map_err(|e| Error::Browser(format!("Timeout loading slide {i}: {e}")))?;
+    // Wait for fonts, images and the first paint
+    tab.evaluate(WAIT_FOR_RENDER, true)
+        .map_err(|e| Error::Browser(format!("Timeout rendering slide {number}: {e}")))?;
 
-        std::thread::sleep(Duration::from_millis(300));
+    // Capture full-page screenshot
+    let screenshot = tab
+        .capture_screenshot(Page::CaptureScreenshotFormatOption::Png, None, None, true)
+        .map_err(|e| Error::Browser(format!("Could not capture slide {number}: {e}")))?;
 
-        // Capture full-page screenshot
+    // A 2x render is saved as is and downscaled for the full thumbnail
+    if retina {
+        write_thumbnail(thumbnails_dir, ThumbnailSize::Retina, number, &screenshot)?;
+        let png = downscale_to_full(&screenshot, number)?;
+        write_thumbnail(thumbnails_dir, ThumbnailSize::Full, number, &png)?;
+    } else {
+        write_thumbnail(thumbnails_dir, ThumbnailSize::Full, number, &screenshot)?;
+    }
+    write_scaled_thumbnails(thumbnails_dir, number)?;
+
+    for step in 0..fragments {
+        tab.evaluate(&show_fragment_script(step), true)
+            .map_err(|e| {
+                Error::Browser(format!("Could not show fragments of slide {number}: {e}"))
+            })?;
         let screenshot = tab
             .capture_screenshot(Page::CaptureScreenshotFormatOption::Png, None, None, true)
-            .map_err(|e| Error::Browser(format!("Could not capture slide {i}: {e}")))?;
+            .map_err(|e| Error::Browser(format!("Could not capture slide {number}: {e}")))?;
+
+        let name = fragment_file_name(number, step);
+        if retina {
+            write_file(&thumbnails_dir.join("2x").join(&name), &screenshot)?;
+            write_file(
+                &thumbnails_dir.join(&name),
+                &downscale_to_full(&screenshot, number)?,
+            )?;
+        } else {
+            write_file(&thumbnails_dir.join(&name), &screenshot)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn downscale_to_full(screenshot: &[u8], number: usize) -> Result<Vec<u8>> {
+    let full = scale_image(screenshot, ThumbnailSize::Full, number)?;
+    let mut png = Vec::new();
+    full.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| Error::Browser(format!("Could not encode slide {number}: {e}")))?;
+    Ok(png)
+}
+
+/// Write the small and medium JPEG variants from the full thumbnail
+pub fn write_scaled_thumbnails(thumbnails_dir: &Path, number: usize) -> Result<()> {
+    let full_path = thumbnails_dir.join(ThumbnailSize::Full.file_name(number));
+    let full = fs::read(&full_path).map_err(|e| Error::FileRead {
+        path: full_path,
+        source: e,
+    })?;
+
+    for size in ThumbnailSize::SCALED {
+        let image = scale_image(&full, size, number)?.to_rgb8();
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY)
+            .encode_image(&image)
+            .map_err(|e| Error::Browser(format!("Could not encode slide {number}: {e}")))?;
+        write_thumbnail(thumbnails_dir, size, number, &jpeg)?;
+    }
+
+    Ok(())
+}
+
+/// Decode an image and scale it to the width of `size`, keeping the aspect ratio
+fn scale_image(data: &[u8], size: ThumbnailSize, number: usize) -> Result<DynamicImage> {
+    let image = ::image::load_from_memory(data)
+        .map_err(|e| Error::Browser(format!("Invalid screenshot of slide {number}: {e}")))?;
+    Ok(image.resize(size.width(), u32::MAX, FilterType::Triangle))
+}
+
+fn write_thumbnail(
+    thumbnails_dir: &Path,
+    size: ThumbnailSize,
+    number: usize,
+    data: &[u8],
+) -> Result<()> {
+    write_file(&thumbnails_dir.join(size.file_name(number)), data)
+}
 
-        // Save screenshot
-        let screenshot_path = thumbnails_dir.join(format!("slide-{i}.png"));
-        fs::write(&screenshot_path, &screenshot).map_err(|e| Error::FileWrite {
-            path: screenshot_path,
+fn write_file(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::CreateDir {
+            path: dir.to_path_buf(),
             source: e,
         })?;
     }
+    fs::write(path, data).map_err(|e| Error::FileWrite {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
 
-    println!("✅ {} thumbnails", total_slides);
-    Ok(())
+/// Fingerprints of the slides the existing thumbnails were captured from
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ThumbnailCache {
+    slides: BTreeMap<usize, String>,
+}
+
+impl ThumbnailCache {
+    /// Load the cache of a thumbnails directory; a missing or invalid cache is empty
+    pub fn load(thumbnails_dir: &Path) -> Self {
+        fs::read_to_string(thumbnails_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, thumbnails_dir: &Path) -> Result<()> {
+        let path = thumbnails_dir.join(CACHE_FILE);
+        let content = serde_json::to_string_pretty(self).unwrap_or_default();
+        fs::write(&path, content).map_err(|e| Error::FileWrite { path, source: e })
+    }
+
+    /// The thumbnail exists and was captured from a slide with this fingerprint
+    pub fn is_fresh(&self, thumbnails_dir: &Path, number: usize, fingerprint: &str) -> bool {
+        self.slides.get(&number).map(String::as_str) == Some(fingerprint)
+            && thumbnails_dir
+                .join(ThumbnailSize::Full.file_name(number))
+                .exists()
+    }
+
+    pub fn insert(&mut self, number: usize, fingerprint: String) {
+        self.slides.insert(number, fingerprint);
+    }
+
+    /// Forget and delete thumbnails of slides beyond `total_slides`, so that
+    /// a shorter deck does not export leftover pages
+    pub fn remove_after(&mut self, thumbnails_dir: &Path, total_slides: usize) {
+        self.slides.retain(|number, _| *number <= total_slides);
+
+        for number in (total_slides + 1).. {
+            let path = thumbnails_dir.join(ThumbnailSize::Full.file_name(number));
+            if fs::remove_file(path).is_err() {
+                break;
+            }
+            for size in [
+                ThumbnailSize::Small,
+                ThumbnailSize::Medium,
+                ThumbnailSize::Retina,
+            ] {
+                fs::remove_file(thumbnails_dir.join(size.file_name(number))).ok();
+            }
+        }
+    }
+}
+
+/// Hash of a rendered slide and the local files it references (stylesheets,
+/// scripts, images and their `url(...)` references)
+pub fn slide_fingerprint(output_dir: &Path, number: usize) -> Result<String> {
+    let html_path = output_dir.join(format!("slide-{number}.html"));
+    let html = fs::read(&html_path).map_err(|e| Error::FileRead {
+        path: html_path.clone(),
+        source: e,
+    })?;
+
+    let mut hasher = Md5::new();
+    hasher.update(CACHE_VERSION.as_bytes());
+    hasher.update(&html);
+
+    let mut seen = BTreeSet::new();
+    let mut pending: Vec<PathBuf> = local_references(&String::from_utf8_lossy(&html))
+        .into_iter()
+        .map(|reference| resolve_reference(output_dir, output_dir, &reference))
+        .collect();
+
+    while let Some(path) = pending.pop() {
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        // Missing files are part of the fingerprint as well
+        hasher.update(path.to_string_lossy().as_bytes());
+        let Ok(content) = fs::read(&path) else {
+            continue;
+        };
+        hasher.update(&content);
+
+        if path.extension().is_some_and(|ext| ext == "css") {
+            let base = path.parent().unwrap_or(output_dir);
+            pending.extend(
+                local_references(&String::from_utf8_lossy(&content))
+                    .into_iter()
+                    .map(|reference| resolve_reference(output_dir, base, &reference)),
+            );
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Local, non-HTML files referenced by `src`, `href`, `poster` attributes or CSS `url(...)`
+fn local_references(content: &str) -> Vec<String> {
+    let mut references = Vec::new();
+
+    for marker in ["src=", "href=", "poster=", "url("] {
+        for (index, _) in content.match_indices(marker) {
+            let rest = content[index + marker.len()..].trim_start();
+            let (quote, rest) = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => (Some(quote), &rest[1..]),
+                _ if marker == "url(" => (None, rest),
+                _ => continue,
+            };
+            let end = rest
+                .find(|c: char| Some(c) == quote || (quote.is_none() && c == ')'))
+                .unwrap_or(rest.len());
+            let reference = rest[..end].split(['?', '#']).next().unwrap_or_default();
+
+            let external = reference.is_empty()
+                || reference.contains("://")
+                || reference.starts_with("//")
+                || reference.starts_with("data:")
+                || reference.starts_with("mailto:")
+                || reference.starts_with("javascript:");
+            if !external && !reference.ends_with(".html") {
+                references.push(reference.to_string());
+            }
+        }
+    }
+
+    references
+}
+
+fn resolve_reference(output_dir: &Path, base: &Path, reference: &str) -> PathBuf {
+    match reference.strip_prefix('/') {
+        Some(absolute) => output_dir.join(absolute),
+        None => base.join(reference),
+    }
 }
 
 fn get_chrome_install_hint() -> &'static str {
@@ -0,0 +1,82 @@
+//! Git module - Commit and branch of a project, read from `.git` without
+//! running git
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Checked out commit of a repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitInfo {
+    pub commit: String,
+    /// None for a detached HEAD
+    pub branch: Option<String>,
+}
+
+impl GitInfo {
+    /// First 7 characters of the commit, as shown by `git log --oneline`
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(7)]
+    }
+}
+
+/// Commit and branch of the repository containing `root`, if any
+pub fn read(root: &Path) -> Option<GitInfo> {
+    let git_dir = root.ancestors().find_map(git_dir)?;
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref:").map(str::trim) else {
+        return Some(GitInfo {
+            commit: head.to_string(),
+            branch: None,
+        });
+    };
+
+    let branch = reference
+        .strip_prefix("refs/heads/")
+        .unwrap_or(reference)
+        .to_string();
+    // A branch without commits has no ref yet
+    let commit = resolve_ref(&git_dir, reference).unwrap_or_default();
+    Some(GitInfo {
+        commit,
+        branch: Some(branch),
+    })
+}
+
+/// `.git` directory, or the directory a `.git` file of a worktree points to
+fn git_dir(root: &Path) -> Option<PathBuf> {
+    let dot_git = root.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let path = content.trim().strip_prefix("gitdir:")?.trim();
+    Some(root.join(path))
+}
+
+fn resolve_ref(git_dir: &Path, reference: &str) -> Option<String> {
+    if let Ok(commit) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+
+    // Worktrees keep shared refs in the main repository
+    let common_dir = fs::read_to_string(git_dir.join("commondir"))
+        .map(|dir| git_dir.join(dir.trim()))
+        .unwrap_or_else(|_| git_dir.to_path_buf());
+    if let Ok(commit) = fs::read_to_string(common_dir.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+
+    // "<commit> <ref>" lines of packed refs
+    let packed = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+    packed
+        .lines()
+        .filter(|line| !line.starts_with(['#', '^']))
+        .find_map(|line| {
+            let (commit, name) = line.split_once(' ')?;
+            (name == reference).then(|| commit.to_string())
+        })
+}
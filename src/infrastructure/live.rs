@@ -0,0 +1,206 @@
+//! Live module - Poll votes and audience questions collected while serving,
+//! saved as one JSON file per session for later analysis
+
+use crate::error::{Error, Result};
+use crate::util;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Longest accepted poll id, option or voter id
+const MAX_ID_LEN: usize = 64;
+const MAX_OPTION_LEN: usize = 200;
+
+/// Longest accepted question and name
+const MAX_QUESTION_LEN: usize = 500;
+const MAX_NAME_LEN: usize = 80;
+
+/// Questions accepted per session
+const MAX_QUESTIONS: usize = 1000;
+
+/// Everything the audience sent during one `slides serve` run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionData {
+    pub deck: String,
+    /// Start of the session, e.g. "2026-10-19T14:30:00Z"
+    pub started: String,
+    /// Chosen option by voter, per poll id
+    pub polls: BTreeMap<String, BTreeMap<String, String>>,
+    pub questions: Vec<Question>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Question {
+    pub id: usize,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Seconds since the start of the session
+    pub asked: u64,
+    pub answered: bool,
+}
+
+/// Vote counts of a poll by option
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PollResults {
+    pub poll: String,
+    pub votes: BTreeMap<String, usize>,
+    pub total: usize,
+}
+
+/// Polls and questions of a running session
+#[derive(Debug)]
+pub struct LiveSession {
+    data: Mutex<SessionData>,
+    path: Option<PathBuf>,
+    started: Instant,
+}
+
+impl LiveSession {
+    /// Session for `deck`, saved to `path` after every change if given
+    pub fn new(deck: &str, path: Option<PathBuf>) -> Self {
+        Self {
+            data: Mutex::new(SessionData {
+                deck: deck.to_string(),
+                started: util::utc_now(),
+                ..Default::default()
+            }),
+            path,
+            started: Instant::now(),
+        }
+    }
+
+    /// File the session is saved to
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn data(&self) -> SessionData {
+        self.data.lock().unwrap().clone()
+    }
+
+    /// Record the voter's choice, replacing an earlier vote in the same poll
+    pub fn vote(&self, poll: &str, voter: &str, option: &str) -> Option<PollResults> {
+        let option = option.trim();
+        if !is_valid_id(poll) || !is_valid_id(voter) {
+            return None;
+        }
+        if option.is_empty() || option.chars().count() > MAX_OPTION_LEN {
+            return None;
+        }
+
+        let mut data = self.data.lock().unwrap();
+        data.polls
+            .entry(poll.to_string())
+            .or_default()
+            .insert(voter.to_string(), option.to_string());
+        self.save(&data);
+        Some(results(&data, poll))
+    }
+
+    pub fn results(&self, poll: &str) -> Option<PollResults> {
+        is_valid_id(poll).then(|| results(&self.data.lock().unwrap(), poll))
+    }
+
+    /// Add a question to the queue
+    pub fn ask(&self, text: &str, name: Option<&str>) -> Option<Question> {
+        let text = text.trim();
+        let name = name.map(str::trim).filter(|name| !name.is_empty());
+        if text.is_empty() || text.chars().count() > MAX_QUESTION_LEN {
+            return None;
+        }
+        if name.is_some_and(|name| name.chars().count() > MAX_NAME_LEN) {
+            return None;
+        }
+
+        let mut data = self.data.lock().unwrap();
+        if data.questions.len() >= MAX_QUESTIONS {
+            return None;
+        }
+        let question = Question {
+            id: data.questions.len() + 1,
+            text: text.to_string(),
+            name: name.map(str::to_string),
+            asked: self.started.elapsed().as_secs(),
+            answered: false,
+        };
+        data.questions.push(question.clone());
+        self.save(&data);
+        Some(question)
+    }
+
+    pub fn questions(&self) -> Vec<Question> {
+        self.data.lock().unwrap().questions.clone()
+    }
+
+    /// Mark a question as answered; false for unknown ids
+    pub fn answer(&self, id: usize) -> bool {
+        let mut data = self.data.lock().unwrap();
+        let Some(question) = data.questions.iter_mut().find(|q| q.id == id) else {
+            return false;
+        };
+        question.answered = true;
+        self.save(&data);
+        true
+    }
+
+    fn save(&self, data: &SessionData) {
+        if let Some(path) = &self.path {
+            if let Err(e) = write_session(path, data) {
+                warn!("Could not save session: {e}");
+            }
+        }
+    }
+}
+
+fn write_session(path: &Path, data: &SessionData) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::CreateDir {
+            path: dir.to_path_buf(),
+            source: e,
+        })?;
+    }
+
+    let json = serde_json::to_string_pretty(data).unwrap_or_default();
+    fs::write(path, json).map_err(|e| Error::FileWrite {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+fn results(data: &SessionData, poll: &str) -> PollResults {
+    let mut votes = BTreeMap::new();
+    let ballots = data.polls.get(poll);
+    for option in ballots.into_iter().flat_map(|ballots| ballots.values()) {
+        *votes.entry(option.clone()).or_insert(0) += 1;
+    }
+
+    PollResults {
+        poll: poll.to_string(),
+        total: ballots.map_or(0, |ballots| ballots.len()),
+        votes,
+    }
+}
+
+/// Ids are used in URLs and file contents: letters, digits, `-` and `_`
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Session file name for a deck, e.g. "talk-20261019-143000.json"
+pub fn session_file_name(deck: &str) -> String {
+    let stamp: String = util::utc_now()
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'T')
+        .map(|c| if c == 'T' { '-' } else { c })
+        .collect();
+    format!("{deck}-{stamp}.json")
+}
@@ -1,4 +1,9 @@
 //! Infrastructure Layer
 
 pub mod chrome;
+pub mod git;
+pub mod live;
 pub mod pdf;
+pub mod server;
+pub mod source;
+pub mod video;
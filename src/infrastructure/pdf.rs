@@ -1,31 +1,169 @@
 //! PDF module - PDF generation and encryption for slide exports
 
 use crate::error::{Error, Result};
+use crate::util;
+use ::image::codecs::jpeg::JpegEncoder;
+use log::warn;
 use md5::{Digest, Md5};
-use printpdf::{ColorBits, ColorSpace, Image, ImageTransform, ImageXObject, Mm, PdfDocument, Px};
+use printpdf::{
+    Color, ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject, Mm,
+    PdfDocument, Px, Rect, Rgb,
+};
 use rand::Rng;
 use std::io::{BufWriter, Cursor};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-pub fn generate_from_thumbnails(thumbnails_dir: &Path, slide_count: usize) -> Result<Vec<u8>> {
-    // 16:9 aspect ratio, similar to PowerPoint defaults
-    let width_mm = 338.666; // ~13.33 inches
-    let height_mm = 190.5; // ~7.5 inches
+/// Page size of exported PDFs
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PageSize {
+    /// 16:9, similar to PowerPoint defaults (~13.33 x 7.5 inches)
+    #[default]
+    Widescreen,
+    /// 4:3 (10 x 7.5 inches)
+    Standard,
+    A4Landscape,
+    Custom {
+        width_mm: f32,
+        height_mm: f32,
+    },
+}
+
+impl PageSize {
+    pub fn dimensions_mm(self) -> (f32, f32) {
+        match self {
+            PageSize::Widescreen => (338.666, 190.5),
+            PageSize::Standard => (254.0, 190.5),
+            PageSize::A4Landscape => (297.0, 210.0),
+            PageSize::Custom {
+                width_mm,
+                height_mm,
+            } => (width_mm, height_mm),
+        }
+    }
+}
+
+impl FromStr for PageSize {
+    type Err = String;
+
+    /// Accepts "16:9", "4:3", "a4" or a custom size in millimeters such as "280x210"
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "16:9" | "widescreen" => Ok(PageSize::Widescreen),
+            "4:3" | "standard" => Ok(PageSize::Standard),
+            "a4" | "a4-landscape" => Ok(PageSize::A4Landscape),
+            custom => {
+                let parse = |v: &str| v.trim().parse::<f32>().ok().filter(|v| *v > 0.0);
+                custom
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((parse(w)?, parse(h)?)))
+                    .map(|(width_mm, height_mm)| PageSize::Custom {
+                        width_mm,
+                        height_mm,
+                    })
+                    .ok_or_else(|| {
+                        format!("invalid page size '{s}' (use 16:9, 4:3, a4 or WIDTHxHEIGHT in mm)")
+                    })
+            }
+        }
+    }
+}
+
+/// How slide images are placed on the page; both preserve the aspect ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scaling {
+    /// Show the whole slide, letterboxed with black bars
+    #[default]
+    Fit,
+    /// Cover the whole page, cropping what does not fit
+    Fill,
+}
+
+impl FromStr for Scaling {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fit" => Ok(Scaling::Fit),
+            "fill" => Ok(Scaling::Fill),
+            _ => Err(format!("invalid scaling '{s}' (use fit or fill)")),
+        }
+    }
+}
+
+/// Compression of the embedded slide images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageCompression {
+    None,
+    /// Lossless (Deflate)
+    #[default]
+    Flate,
+    /// Lossy, with a quality from 1 to 100
+    Jpeg(u8),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfOptions {
+    pub page_size: PageSize,
+    pub scaling: Scaling,
+    pub compression: ImageCompression,
+}
+
+/// Page of a generated PDF
+#[derive(Debug, Clone)]
+pub struct PdfPage {
+    pub image: PathBuf,
+    /// Bookmark title; none for pages without a bookmark, e.g. fragment steps
+    pub bookmark: Option<String>,
+}
+
+/// Generate a PDF with one page per thumbnail. `titles` (in slide order) are
+/// used as bookmark names; missing or empty titles fall back to "Slide N".
+pub fn generate_from_thumbnails(
+    thumbnails_dir: &Path,
+    slide_count: usize,
+    titles: &[String],
+    options: &PdfOptions,
+) -> Result<Vec<u8>> {
+    let pages: Vec<PdfPage> = (1..=slide_count)
+        .map(|i| PdfPage {
+            image: thumbnails_dir.join(format!("slide-{i}.png")),
+            bookmark: Some(
+                titles
+                    .get(i - 1)
+                    .filter(|title| !title.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| format!("Slide {i}")),
+            ),
+        })
+        .collect();
+
+    generate_from_images(&pages, options)
+}
+
+/// Generate a PDF with one page per image
+pub fn generate_from_images(pages: &[PdfPage], options: &PdfOptions) -> Result<Vec<u8>> {
+    let (width_mm, height_mm) = options.page_size.dimensions_mm();
 
     let (doc, mut page_index, mut layer_index) =
         PdfDocument::new("Presentation", Mm(width_mm), Mm(height_mm), "Slide 1");
 
-    for i in 1..=slide_count {
-        let png_path = thumbnails_dir.join(format!("slide-{i}.png"));
-
+    for (i, page) in (1..).zip(pages) {
         // Load PNG image using image crate
-        let img = ::image::open(&png_path).map_err(|e| {
-            Error::PdfGeneration(format!("Could not open {}: {e}", png_path.display()))
+        let img = ::image::open(&page.image).map_err(|e| {
+            Error::PdfGeneration(format!("Could not open {}: {e}", page.image.display()))
         })?;
 
         let img_rgb = img.to_rgb8();
         let (img_width, img_height) = img_rgb.dimensions();
 
+        let (image_data, image_filter) = match options.compression {
+            ImageCompression::Jpeg(quality) => {
+                (encode_jpeg(&img_rgb, quality)?, Some(ImageFilter::DCT))
+            }
+            ImageCompression::None | ImageCompression::Flate => (img_rgb.into_raw(), None),
+        };
+
         // Create printpdf Image
         let image = Image::from(ImageXObject {
             width: Px(img_width as usize),
@@ -33,8 +171,8 @@ pub fn generate_from_thumbnails(thumbnails_dir: &Path, slide_count: usize) -> Re
             color_space: ColorSpace::Rgb,
             bits_per_component: ColorBits::Bit8,
             interpolate: true,
-            image_data: img_rgb.into_raw(),
-            image_filter: None,
+            image_data,
+            image_filter,
             clipping_bbox: None,
             smask: None,
         });
@@ -47,26 +185,41 @@ pub fn generate_from_thumbnails(thumbnails_dir: &Path, slide_count: usize) -> Re
             layer_index = new_layer;
         }
 
+        if let Some(title) = &page.bookmark {
+            doc.add_bookmark(title.clone(), page_index);
+        }
         let current_layer = doc.get_page(page_index).get_layer(layer_index);
 
-        // Calculate DPI to fit image to page
         // printpdf uses 300 DPI by default for image placement
         let dpi = 300.0;
         let img_width_mm = img_width as f32 / dpi * 25.4;
         let img_height_mm = img_height as f32 / dpi * 25.4;
 
-        // Scale to fit page
+        // Uniform scale keeps the aspect ratio of the slide
         let scale_x = width_mm / img_width_mm;
         let scale_y = height_mm / img_height_mm;
+        let scale = match options.scaling {
+            Scaling::Fit => scale_x.min(scale_y),
+            Scaling::Fill => scale_x.max(scale_y),
+        };
+
+        // Center the image; with `Fill` the overflow is cropped by the page box
+        let offset_x = (width_mm - img_width_mm * scale) / 2.0;
+        let offset_y = (height_mm - img_height_mm * scale) / 2.0;
+
+        // Ignore sub-micrometer rounding when the aspect ratios match
+        if offset_x > 0.001 || offset_y > 0.001 {
+            current_layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+            current_layer.add_rect(Rect::new(Mm(0.0), Mm(0.0), Mm(width_mm), Mm(height_mm)));
+        }
 
-        // Add image to page, scaled to fill
         image.add_to_layer(
             current_layer,
             ImageTransform {
-                translate_x: Some(Mm(0.0)),
-                translate_y: Some(Mm(0.0)),
-                scale_x: Some(scale_x),
-                scale_y: Some(scale_y),
+                translate_x: Some(Mm(offset_x)),
+                translate_y: Some(Mm(offset_y)),
+                scale_x: Some(scale),
+                scale_y: Some(scale),
                 ..Default::default()
             },
         );
@@ -76,8 +229,572 @@ pub fn generate_from_thumbnails(thumbnails_dir: &Path, slide_count: usize) -> Re
     doc.save(&mut buf)
         .map_err(|e| Error::PdfGeneration(format!("Could not generate PDF: {e}")))?;
 
-    buf.into_inner()
-        .map_err(|e| Error::PdfGeneration(format!("Buffer error: {e}")))
+    let pdf_bytes = buf
+        .into_inner()
+        .map_err(|e| Error::PdfGeneration(format!("Buffer error: {e}")))?;
+
+    apply_compression(pdf_bytes, options.compression)
+}
+
+fn encode_jpeg(img: &::image::RgbImage, quality: u8) -> Result<Vec<u8>> {
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, quality.clamp(1, 100))
+        .encode_image(img)
+        .map_err(|e| Error::PdfGeneration(format!("Could not encode JPEG: {e}")))?;
+    Ok(jpeg)
+}
+
+/// Deflate all uncompressed streams, or inflate all streams for
+/// `ImageCompression::None`, whatever printpdf did in this build profile.
+/// Apply again after adding streams, e.g. with `apply_watermark`.
+pub fn apply_compression(pdf_bytes: Vec<u8>, compression: ImageCompression) -> Result<Vec<u8>> {
+    let mut document = lopdf::Document::load_from(Cursor::new(&pdf_bytes))
+        .map_err(|e| Error::PdfGeneration(format!("Could not load PDF: {e}")))?;
+
+    match compression {
+        ImageCompression::None => document.decompress(),
+        ImageCompression::Flate | ImageCompression::Jpeg(_) => document.compress(),
+    }
+
+    let mut buffer = Vec::new();
+    document
+        .save_to(&mut buffer)
+        .map_err(|e| Error::PdfGeneration(format!("Could not save PDF: {e}")))?;
+
+    Ok(buffer)
+}
+
+/// Document information and outline applied to a generated PDF
+#[derive(Debug, Clone, Default)]
+pub struct DocumentInfo {
+    pub title: String,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Vec<String>,
+    /// Creation date as "2026-10-19" or "2026-10-19T08:30:00"; defaults to
+    /// the current time
+    pub creation_date: Option<String>,
+    pub outline: Vec<OutlineItem>,
+    /// Slide number of every page, for PDFs with several pages per slide;
+    /// empty when pages and slides match
+    pub page_labels: Vec<usize>,
+}
+
+/// Bookmark in the PDF outline
+#[derive(Debug, Clone)]
+pub struct OutlineItem {
+    pub title: String,
+    /// 1-based page number
+    pub page: usize,
+    pub children: Vec<OutlineItem>,
+}
+
+/// Set the document information dictionary, replace the outline with `info.outline`
+/// and label pages with their slide numbers
+pub fn apply_document_info(pdf_bytes: Vec<u8>, info: &DocumentInfo) -> Result<Vec<u8>> {
+    use lopdf::{dictionary, text_string, Document, Object};
+
+    let mut document = Document::load_from(Cursor::new(&pdf_bytes))
+        .map_err(|e| Error::PdfGeneration(format!("Could not load PDF: {e}")))?;
+
+    let mut info_dict = dictionary! {
+        "Title" => text_string(&info.title),
+        "Creator" => text_string("slides-rs"),
+        "Producer" => text_string("slides-rs"),
+        "CreationDate" => Object::string_literal(pdf_date(info.creation_date.as_deref())),
+    };
+    if let Some(author) = &info.author {
+        info_dict.set("Author", text_string(author));
+    }
+    if let Some(subject) = &info.subject {
+        info_dict.set("Subject", text_string(subject));
+    }
+    if !info.keywords.is_empty() {
+        info_dict.set("Keywords", text_string(&info.keywords.join(", ")));
+    }
+    let info_id = document.add_object(info_dict);
+    document.trailer.set("Info", info_id);
+
+    let pages = document.get_pages();
+    if !info.outline.is_empty() {
+        for item in &info.outline {
+            add_outline_item(&mut document, &pages, item, None);
+        }
+        if let Some(outline_id) = document.build_outline() {
+            let catalog = document
+                .catalog_mut()
+                .map_err(|e| Error::PdfGeneration(format!("Invalid PDF catalog: {e}")))?;
+            catalog.set("Outlines", outline_id);
+            catalog.set("PageMode", "UseOutlines");
+        }
+    }
+
+    let catalog = document
+        .catalog_mut()
+        .map_err(|e| Error::PdfGeneration(format!("Invalid PDF catalog: {e}")))?;
+    catalog.set(
+        "PageLabels",
+        dictionary! { "Nums" => page_label_ranges(&info.page_labels) },
+    );
+
+    document.prune_objects();
+
+    let mut buffer = Vec::new();
+    document
+        .save_to(&mut buffer)
+        .map_err(|e| Error::PdfGeneration(format!("Could not save PDF: {e}")))?;
+
+    Ok(buffer)
+}
+
+/// Number tree entries for `PageLabels`: a decimal range starting at every
+/// page whose slide number does not continue the previous one
+fn page_label_ranges(labels: &[usize]) -> Vec<lopdf::Object> {
+    use lopdf::dictionary;
+
+    if labels.is_empty() {
+        return vec![0.into(), dictionary! { "S" => "D", "St" => 1 }.into()];
+    }
+
+    let mut nums = Vec::new();
+    for (index, label) in labels.iter().enumerate() {
+        if index == 0 || labels[index - 1] + 1 != *label {
+            nums.push((index as i64).into());
+            nums.push(dictionary! { "S" => "D", "St" => *label as i64 }.into());
+        }
+    }
+    nums
+}
+
+fn add_outline_item(
+    document: &mut lopdf::Document,
+    pages: &std::collections::BTreeMap<u32, lopdf::ObjectId>,
+    item: &OutlineItem,
+    parent: Option<u32>,
+) {
+    let Some(page_id) = pages.get(&(item.page as u32)) else {
+        return;
+    };
+
+    let bookmark = lopdf::Bookmark::new(item.title.clone(), [0.0, 0.0, 0.0], 0, *page_id);
+    let id = document.add_bookmark(bookmark, parent);
+    for child in &item.children {
+        add_outline_item(document, pages, child, Some(id));
+    }
+}
+
+/// PDF date string ("D:YYYYMMDDHHmmSSZ") from a date like "2026-10-19", or now
+fn pdf_date(date: Option<&str>) -> String {
+    let parsed = date.and_then(|date| {
+        let parsed = parse_date(date);
+        if parsed.is_none() {
+            warn!(
+                "Date '{date}' is not YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS - \
+                using the current time as PDF creation date"
+            );
+        }
+        parsed
+    });
+    let (year, month, day, hour, minute, second) = parsed.unwrap_or_else(util::utc_now_parts);
+    format!("D:{year:04}{month:02}{day:02}{hour:02}{minute:02}{second:02}Z")
+}
+
+/// Fields of "2026-10-19", "2026-10-19T08:30" or "2026-10-19T08:30:00Z";
+/// none for other formats or out-of-range fields
+fn parse_date(date: &str) -> Option<util::DateTime> {
+    let date = date.trim();
+    let date = date.strip_suffix('Z').unwrap_or(date);
+    let (day, time) = date.split_once(['T', ' ']).unwrap_or((date, "00:00"));
+    let field = |text: Option<&str>, len: usize, range: std::ops::RangeInclusive<u32>| {
+        text.filter(|text| text.len() == len && text.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|text| text.parse::<u32>().ok())
+            .filter(|number| range.contains(number))
+    };
+
+    let mut days = day.split('-');
+    let mut times = time.split(':');
+    let year = field(days.next(), 4, 1..=9999)?;
+    let month = field(days.next(), 2, 1..=12)?;
+    let day = field(days.next(), 2, 1..=31)?;
+    let hour = field(times.next(), 2, 0..=23)?;
+    let minute = field(times.next(), 2, 0..=59)?;
+    let second = field(times.next().or(Some("00")), 2, 0..=59)?;
+    if days.next().is_some() || times.next().is_some() {
+        return None;
+    }
+    Some((i64::from(year), month, day, hour, minute, second))
+}
+
+/// Where a watermark is placed on the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WatermarkPosition {
+    #[default]
+    Center,
+    Top,
+    Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl FromStr for WatermarkPosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "center" => Ok(WatermarkPosition::Center),
+            "top" => Ok(WatermarkPosition::Top),
+            "bottom" => Ok(WatermarkPosition::Bottom),
+            "top-left" => Ok(WatermarkPosition::TopLeft),
+            "top-right" => Ok(WatermarkPosition::TopRight),
+            "bottom-left" => Ok(WatermarkPosition::BottomLeft),
+            "bottom-right" => Ok(WatermarkPosition::BottomRight),
+            _ => Err(format!(
+                "invalid watermark position '{s}' (use center, top, bottom, top-left, top-right, bottom-left or bottom-right)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatermarkContent {
+    Text(String),
+    /// PNG or JPEG image; transparency is preserved
+    Image(std::path::PathBuf),
+}
+
+/// Text or image stamped onto every page of an exported PDF
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub content: WatermarkContent,
+    pub position: WatermarkPosition,
+    /// 0.0 (invisible) to 1.0 (opaque)
+    pub opacity: f32,
+    /// Counter-clockwise rotation in degrees
+    pub rotation: f32,
+    /// Font size in points for text, width in percent of the page for images
+    pub size: f32,
+}
+
+impl Watermark {
+    pub const DEFAULT_OPACITY: f32 = 0.3;
+    pub const DEFAULT_TEXT_SIZE: f32 = 36.0;
+    pub const DEFAULT_IMAGE_SIZE: f32 = 25.0;
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content: WatermarkContent::Text(text.into()),
+            position: WatermarkPosition::Center,
+            opacity: Self::DEFAULT_OPACITY,
+            rotation: 0.0,
+            size: Self::DEFAULT_TEXT_SIZE,
+        }
+    }
+
+    pub fn image(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            content: WatermarkContent::Image(path.into()),
+            size: Self::DEFAULT_IMAGE_SIZE,
+            ..Self::text("")
+        }
+    }
+}
+
+/// Distance of a corner or edge placed watermark from the page border, in points
+const WATERMARK_MARGIN: f32 = 24.0;
+
+/// Stamp `watermark` onto every page of the PDF
+pub fn apply_watermark(pdf_bytes: Vec<u8>, watermark: &Watermark) -> Result<Vec<u8>> {
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Document, Object, Stream, StringFormat};
+
+    let mut document = Document::load_from(Cursor::new(&pdf_bytes))
+        .map_err(|e| Error::PdfGeneration(format!("Could not load PDF: {e}")))?;
+
+    let graphics_state_id = document.add_object(dictionary! {
+        "Type" => "ExtGState",
+        "ca" => watermark.opacity.clamp(0.0, 1.0),
+        "CA" => watermark.opacity.clamp(0.0, 1.0),
+    });
+
+    // Page resource of the watermark and how to draw it centered on the origin,
+    // returning its size in points for a given page width
+    type Draw = Box<dyn Fn(f32) -> (f32, f32, Vec<Operation>)>;
+    let (category, name, resource, draw): (&[u8], &[u8], Object, Draw) = match &watermark.content {
+        WatermarkContent::Text(text) => {
+            let font_id = document.add_object(dictionary! {
+                "Type" => "Font",
+                "Subtype" => "Type1",
+                "BaseFont" => "Helvetica",
+                "Encoding" => "WinAnsiEncoding",
+            });
+            let encoded = win_ansi_encode(text);
+            let font_size = watermark.size;
+            let width = helvetica_width(&encoded) * font_size;
+            // Cap height of Helvetica
+            let height = 0.718 * font_size;
+
+            let draw = move |_page_width: f32| {
+                let operations = vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["FWatermark".into(), font_size.into()]),
+                    Operation::new("rg", vec![0.5.into(), 0.5.into(), 0.5.into()]),
+                    Operation::new("Td", vec![(-width / 2.0).into(), (-height / 2.0).into()]),
+                    Operation::new(
+                        "Tj",
+                        vec![Object::String(encoded.clone(), StringFormat::Literal)],
+                    ),
+                    Operation::new("ET", vec![]),
+                ];
+                (width, height, operations)
+            };
+            (b"Font", b"FWatermark", font_id.into(), Box::new(draw))
+        }
+        WatermarkContent::Image(path) => {
+            let image = ::image::open(path)
+                .map_err(|e| {
+                    Error::PdfGeneration(format!(
+                        "Could not load watermark image '{}': {e}",
+                        path.display()
+                    ))
+                })?
+                .to_rgba8();
+            let (pixel_width, pixel_height) = image.dimensions();
+            let rgb: Vec<u8> = image.pixels().flat_map(|p| [p[0], p[1], p[2]]).collect();
+            let alpha: Vec<u8> = image.pixels().map(|p| p[3]).collect();
+
+            let image_dict = |color_space: &str| {
+                dictionary! {
+                    "Type" => "XObject",
+                    "Subtype" => "Image",
+                    "Width" => pixel_width as i64,
+                    "Height" => pixel_height as i64,
+                    "ColorSpace" => color_space,
+                    "BitsPerComponent" => 8,
+                }
+            };
+            let mut mask = Stream::new(image_dict("DeviceGray"), alpha);
+            let _ = mask.compress();
+            let mask_id = document.add_object(mask);
+
+            let mut dict = image_dict("DeviceRGB");
+            dict.set("SMask", mask_id);
+            let mut stream = Stream::new(dict, rgb);
+            let _ = stream.compress();
+            let image_id = document.add_object(stream);
+
+            let percent = watermark.size;
+            let aspect = pixel_height as f32 / pixel_width.max(1) as f32;
+            let draw = move |page_width: f32| {
+                let width = page_width * percent / 100.0;
+                let height = width * aspect;
+                let operations = vec![
+                    Operation::new(
+                        "cm",
+                        vec![
+                            width.into(),
+                            0.into(),
+                            0.into(),
+                            height.into(),
+                            (-width / 2.0).into(),
+                            (-height / 2.0).into(),
+                        ],
+                    ),
+                    Operation::new("Do", vec!["XWatermark".into()]),
+                ];
+                (width, height, operations)
+            };
+            (b"XObject", b"XWatermark", image_id.into(), Box::new(draw))
+        }
+    };
+
+    let (cos, sin) = {
+        let radians = watermark.rotation.to_radians();
+        (radians.cos(), radians.sin())
+    };
+
+    for page_id in document.get_pages().into_values() {
+        let (page_width, page_height) = page_size(&document, page_id)?;
+        let (width, height, operations) = draw(page_width);
+
+        // Half extents of the rotated bounding box keep corner placements on the page
+        let half_width = (width * cos).abs() / 2.0 + (height * sin).abs() / 2.0;
+        let half_height = (width * sin).abs() / 2.0 + (height * cos).abs() / 2.0;
+        let left = WATERMARK_MARGIN + half_width;
+        let right = page_width - WATERMARK_MARGIN - half_width;
+        let bottom = WATERMARK_MARGIN + half_height;
+        let top = page_height - WATERMARK_MARGIN - half_height;
+        let (x, y) = match watermark.position {
+            WatermarkPosition::Center => (page_width / 2.0, page_height / 2.0),
+            WatermarkPosition::Top => (page_width / 2.0, top),
+            WatermarkPosition::Bottom => (page_width / 2.0, bottom),
+            WatermarkPosition::TopLeft => (left, top),
+            WatermarkPosition::TopRight => (right, top),
+            WatermarkPosition::BottomLeft => (left, bottom),
+            WatermarkPosition::BottomRight => (right, bottom),
+        };
+
+        let mut stamp = vec![
+            Operation::new("q", vec![]),
+            Operation::new("gs", vec!["GSWatermark".into()]),
+            Operation::new(
+                "cm",
+                vec![1.into(), 0.into(), 0.into(), 1.into(), x.into(), y.into()],
+            ),
+            Operation::new(
+                "cm",
+                vec![
+                    cos.into(),
+                    sin.into(),
+                    (-sin).into(),
+                    cos.into(),
+                    0.into(),
+                    0.into(),
+                ],
+            ),
+        ];
+        stamp.extend(operations);
+        stamp.push(Operation::new("Q", vec![]));
+
+        add_page_resource(
+            &mut document,
+            page_id,
+            b"ExtGState",
+            b"GSWatermark",
+            graphics_state_id.into(),
+        )?;
+        add_page_resource(&mut document, page_id, category, name, resource.clone())?;
+
+        // Isolate the existing content so its graphics state does not affect the stamp
+        let contents = document.get_page_contents(page_id);
+        let save_id = document.add_object(Stream::new(dictionary! {}, b"q\n".to_vec()));
+        let restore_id = document.add_object(Stream::new(dictionary! {}, b"\nQ\n".to_vec()));
+        let stamp = Content { operations: stamp }
+            .encode()
+            .map_err(|e| Error::PdfGeneration(format!("Could not encode watermark: {e}")))?;
+        let mut stamp = Stream::new(dictionary! {}, stamp);
+        let _ = stamp.compress();
+        let stamp_id = document.add_object(stamp);
+
+        let contents: Vec<Object> = std::iter::once(save_id)
+            .chain(contents)
+            .chain([restore_id, stamp_id])
+            .map(Object::Reference)
+            .collect();
+        document
+            .get_dictionary_mut(page_id)
+            .map_err(|e| Error::PdfGeneration(format!("Invalid PDF page: {e}")))?
+            .set("Contents", contents);
+    }
+
+    let mut buffer = Vec::new();
+    document
+        .save_to(&mut buffer)
+        .map_err(|e| Error::PdfGeneration(format!("Could not save PDF: {e}")))?;
+
+    Ok(buffer)
+}
+
+/// Width and height of a page's media box in points
+fn page_size(document: &lopdf::Document, page_id: lopdf::ObjectId) -> Result<(f32, f32)> {
+    let media_box = document
+        .get_dictionary(page_id)
+        .and_then(|page| page.get(b"MediaBox"))
+        .and_then(lopdf::Object::as_array)
+        .map_err(|e| Error::PdfGeneration(format!("Invalid PDF page: {e}")))?;
+
+    let values: Vec<f32> = media_box
+        .iter()
+        .filter_map(|value| value.as_float().ok())
+        .collect();
+    match values.as_slice() {
+        [x1, y1, x2, y2] => Ok(((x2 - x1).abs(), (y2 - y1).abs())),
+        _ => Err(Error::PdfGeneration("Invalid PDF media box".to_string())),
+    }
+}
+
+/// Add `value` under `name` to the resource dictionary `category` of a page
+fn add_page_resource(
+    document: &mut lopdf::Document,
+    page_id: lopdf::ObjectId,
+    category: &[u8],
+    name: &[u8],
+    value: lopdf::Object,
+) -> Result<()> {
+    use lopdf::{Dictionary, Object};
+
+    let invalid = |e: lopdf::Error| Error::PdfGeneration(format!("Invalid PDF resources: {e}"));
+
+    let resources = document
+        .get_or_create_resources(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(invalid)?;
+    if !resources.has(category) {
+        resources.set(category, Dictionary::new());
+    }
+    let category_id = match resources.get(category).map_err(invalid)? {
+        Object::Reference(id) => Some(*id),
+        _ => None,
+    };
+
+    let entries = match category_id {
+        Some(id) => document.get_dictionary_mut(id),
+        None => document
+            .get_or_create_resources(page_id)
+            .and_then(Object::as_dict_mut)
+            .and_then(|resources| resources.get_mut(category))
+            .and_then(Object::as_dict_mut),
+    }
+    .map_err(invalid)?;
+    entries.set(name, value);
+
+    Ok(())
+}
+
+/// Encode text for the standard Helvetica font; unsupported characters become '?'
+fn win_ansi_encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{ff}' => c as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '™' => 0x99,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Width of WinAnsi encoded text in Helvetica, in units of the font size
+fn helvetica_width(text: &[u8]) -> f32 {
+    // Advance widths of the printable ASCII characters from the Helvetica AFM
+    const ASCII_WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556,
+        556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722,
+        722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722,
+        667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556,
+        556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500,
+        500, 334, 260, 334, 584,
+    ];
+
+    let width: u32 = text
+        .iter()
+        .map(|&byte| match byte {
+            0x20..=0x7e => ASCII_WIDTHS[(byte - 0x20) as usize] as u32,
+            0x97 => 1000,
+            _ => 556,
+        })
+        .sum();
+    width as f32 / 1000.0
 }
 
 pub fn generate_secure_password() -> String {
@@ -93,13 +810,125 @@ pub fn generate_secure_password() -> String {
         .collect()
 }
 
-pub fn encrypt(
-    pdf_bytes: Vec<u8>,
-    password: &str,
-    no_print: bool,
-    no_copy: bool,
-) -> Result<(Vec<u8>, bool)> {
+/// Encryption algorithm for password-protected PDFs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncryptionAlgorithm {
+    /// AES-256 (PDF 2.0, V5)
+    #[default]
+    Aes256,
+    /// AES-128 (PDF 1.6, V4)
+    Aes128,
+    /// 128-bit RC4 (PDF 1.4, V2), only for very old readers
+    Rc4,
+}
+
+impl FromStr for EncryptionAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "aes256" | "aes-256" => Ok(EncryptionAlgorithm::Aes256),
+            "aes128" | "aes-128" => Ok(EncryptionAlgorithm::Aes128),
+            "rc4" => Ok(EncryptionAlgorithm::Rc4),
+            _ => Err(format!(
+                "invalid encryption '{s}' (use aes256, aes128 or rc4)"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for EncryptionAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EncryptionAlgorithm::Aes256 => "AES-256",
+            EncryptionAlgorithm::Aes128 => "AES-128",
+            EncryptionAlgorithm::Rc4 => "RC4-128",
+        })
+    }
+}
+
+/// Passwords and permissions of an encrypted PDF
+#[derive(Debug, Clone, Default)]
+pub struct Encryption {
+    pub algorithm: EncryptionAlgorithm,
+    /// Required to change permissions or remove the encryption
+    pub owner_password: String,
+    /// Required to open the PDF; empty opens it without a password
+    pub user_password: String,
+    pub no_print: bool,
+    pub no_copy: bool,
+}
+
+/// Password value that requests a generated password
+pub const AUTO_PASSWORD: &str = "auto";
+
+/// Requested protection of an exported PDF, before passwords are generated
+#[derive(Debug, Clone, Default)]
+pub struct Protection {
+    pub algorithm: EncryptionAlgorithm,
+    /// `None` or `AUTO_PASSWORD` generates a distinct owner password
+    pub owner_password: Option<String>,
+    /// `AUTO_PASSWORD` generates a password; `None` opens without one
+    pub user_password: Option<String>,
+    pub no_print: bool,
+    pub no_copy: bool,
+}
+
+impl Protection {
+    /// Encryption is needed for any password or permission restriction
+    pub fn is_requested(&self) -> bool {
+        self.owner_password.is_some()
+            || self.user_password.is_some()
+            || self.no_print
+            || self.no_copy
+    }
+
+    /// Resolve the requested passwords, generating where asked for or missing
+    pub fn resolve(&self) -> Encryption {
+        let resolve = |password: &str| match password {
+            AUTO_PASSWORD => generate_secure_password(),
+            password => password.to_string(),
+        };
+
+        Encryption {
+            algorithm: self.algorithm,
+            owner_password: self
+                .owner_password
+                .as_deref()
+                .map(resolve)
+                .unwrap_or_else(generate_secure_password),
+            user_password: self
+                .user_password
+                .as_deref()
+                .map(resolve)
+                .unwrap_or_default(),
+            no_print: self.no_print,
+            no_copy: self.no_copy,
+        }
+    }
+}
+
+impl Encryption {
+    /// Whether the passwords differ; a user password that is also the owner
+    /// password gives everyone who opens the PDF full permissions
+    pub fn has_distinct_passwords(&self) -> bool {
+        self.owner_password != self.user_password
+    }
+}
+
+/// Encrypt a PDF; fails when the owner password is the user password, which
+/// would undo any permission restriction
+pub fn encrypt(pdf_bytes: Vec<u8>, encryption: &Encryption) -> Result<Vec<u8>> {
+    use lopdf::encryption::crypt_filters::{Aes128CryptFilter, Aes256CryptFilter, CryptFilter};
     use lopdf::{Document, EncryptionState, EncryptionVersion, Object, Permissions, StringFormat};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    if !encryption.has_distinct_passwords() {
+        return Err(Error::PdfEncryption(
+            "The owner password must differ from the user password".to_string(),
+        ));
+    }
 
     let mut document = Document::load_from(Cursor::new(&pdf_bytes))
         .map_err(|e| Error::PdfEncryption(format!("Could not load PDF: {e}")))?;
@@ -111,7 +940,7 @@ pub fn encrypt(
             .unwrap()
             .as_nanos();
         hasher.update(nanos.to_string().as_bytes());
-        hasher.update(password.as_bytes());
+        hasher.update(encryption.owner_password.as_bytes());
         let file_id = hasher.finalize().to_vec();
 
         let id_array = vec![
@@ -131,21 +960,49 @@ pub fn encrypt(
         | Permissions::ASSEMBLABLE
         | Permissions::PRINTABLE_IN_HIGH_QUALITY;
 
-    if no_print {
+    if encryption.no_print {
         permissions.remove(Permissions::PRINTABLE);
         permissions.remove(Permissions::PRINTABLE_IN_HIGH_QUALITY);
     }
-    if no_copy {
+    if encryption.no_copy {
         permissions.remove(Permissions::COPYABLE);
         permissions.remove(Permissions::COPYABLE_FOR_ACCESSIBILITY);
     }
 
-    let encryption_version = EncryptionVersion::V2 {
-        document: &document,
-        owner_password: password,
-        user_password: password,
-        key_length: 128,
-        permissions,
+    let owner_password = encryption.owner_password.as_str();
+    let user_password = encryption.user_password.as_str();
+    let crypt_filter = |filter: Arc<dyn CryptFilter>| BTreeMap::from([(b"StdCF".to_vec(), filter)]);
+    let mut file_encryption_key = [0u8; 32];
+    rand::thread_rng().fill(&mut file_encryption_key);
+
+    let encryption_version = match encryption.algorithm {
+        EncryptionAlgorithm::Aes256 => EncryptionVersion::V5 {
+            encrypt_metadata: true,
+            crypt_filters: crypt_filter(Arc::new(Aes256CryptFilter)),
+            file_encryption_key: &file_encryption_key,
+            stream_filter: b"StdCF".to_vec(),
+            string_filter: b"StdCF".to_vec(),
+            owner_password,
+            user_password,
+            permissions,
+        },
+        EncryptionAlgorithm::Aes128 => EncryptionVersion::V4 {
+            document: &document,
+            encrypt_metadata: true,
+            crypt_filters: crypt_filter(Arc::new(Aes128CryptFilter)),
+            stream_filter: b"StdCF".to_vec(),
+            string_filter: b"StdCF".to_vec(),
+            owner_password,
+            user_password,
+            permissions,
+        },
+        EncryptionAlgorithm::Rc4 => EncryptionVersion::V2 {
+            document: &document,
+            owner_password,
+            user_password,
+            key_length: 128,
+            permissions,
+        },
     };
 
     let encryption_state = EncryptionState::try_from(encryption_version)
@@ -160,5 +1017,5 @@ pub fn encrypt(
         .save_to(&mut buffer)
         .map_err(|e| Error::PdfEncryption(format!("Could not save encrypted PDF: {e}")))?;
 
-    Ok((buffer, true))
+    Ok(buffer)
 }
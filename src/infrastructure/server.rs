@@ -0,0 +1,767 @@
+//! Server module - Local HTTP server for a built presentation that keeps
+//! presenter and audience pages on all connected devices in sync, collects
+//! poll votes and questions from the audience, and optionally serves a
+//! pin-protected remote control for phones
+
+use crate::error::{Error, Result};
+use crate::infrastructure::live::LiveSession;
+use crate::progress::{ProgressEvent, Reporter};
+use log::debug;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+/// Server-sent events stream of navigation states
+pub const SYNC_EVENTS_PATH: &str = "/_slides/events";
+
+/// Receives a navigation state as JSON, e.g. `{"slide": 3, "fragment": 1}`
+pub const SYNC_STATE_PATH: &str = "/_slides/state";
+
+/// Server-sent events about audience activity: poll results and question counts
+pub const LIVE_EVENTS_PATH: &str = "/_slides/live";
+
+/// Poll results by poll id (GET), or a vote like `{"voter": "x1", "option": "Rust"}` (POST)
+pub const POLLS_PATH: &str = "/_slides/polls/";
+
+/// Question queue (GET, presenter only), or a new question like `{"text": "Why?"}` (POST);
+/// `POST /_slides/questions/<id>/answered` marks one as answered
+pub const QUESTIONS_PATH: &str = "/_slides/questions";
+
+/// Remote control pages (remote/slide-N.html), only served to paired devices
+pub const REMOTE_PATH: &str = "/remote/";
+
+/// Presenter pages (presenter/slide-N.html), only served to this machine and
+/// paired devices
+pub const PRESENTER_PATH: &str = "/presenter/";
+
+/// Receives the pin form of the remote control, e.g. `pin=123456`
+pub const REMOTE_PAIR_PATH: &str = "/_slides/pair";
+
+/// Cookie holding the token of a paired device
+const REMOTE_COOKIE: &str = "slides_remote";
+
+/// Wrong pins a client may enter before it is locked out
+const MAX_PIN_ATTEMPTS: u32 = 5;
+
+/// First lockout of a client; every further wrong pin doubles it
+const PIN_LOCKOUT: Duration = Duration::from_secs(30);
+
+/// Longest lockout, reached after 10 doublings
+const MAX_PIN_LOCKOUT: Duration = Duration::from_secs(30 * 1024);
+
+/// Largest accepted request body
+const MAX_BODY: u64 = 64 * 1024;
+
+/// Comment sent to idle event streams, which also detects closed connections
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Latest navigation state and the event streams it is broadcast to
+#[derive(Debug, Default)]
+pub struct SyncHub {
+    state: Mutex<Option<String>>,
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl SyncHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest published state
+    pub fn state(&self) -> Option<String> {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Store the state and send it to every subscriber
+    pub fn publish(&self, state: String) {
+        *self.state.lock().unwrap() = Some(state.clone());
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(state.clone()).is_ok());
+    }
+
+    /// Receive all states published from now on, starting with the latest one
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        if let Some(state) = self.state() {
+            sender.send(state).ok();
+        }
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+}
+
+/// Outcome of entering a remote pin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pairing {
+    /// Token of the newly paired device
+    Paired(String),
+    WrongPin,
+    /// Too many wrong pins from this client; try again after the duration
+    LockedOut(Duration),
+}
+
+/// Wrong pins entered by one client
+#[derive(Debug, Default)]
+struct Failures {
+    count: u32,
+    locked_until: Option<Instant>,
+}
+
+/// One-time pins and tokens of devices paired as remote control
+#[derive(Debug)]
+pub struct RemoteAccess {
+    pin: Mutex<String>,
+    failures: Mutex<HashMap<IpAddr, Failures>>,
+    tokens: Mutex<HashSet<String>>,
+}
+
+impl Default for RemoteAccess {
+    fn default() -> Self {
+        Self {
+            pin: Mutex::new(new_pin()),
+            failures: Mutex::new(HashMap::new()),
+            tokens: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl RemoteAccess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin that pairs the next device
+    pub fn pin(&self) -> String {
+        self.pin.lock().unwrap().clone()
+    }
+
+    /// Trade the pin for a device token; the pin is replaced after a
+    /// successful pairing. A client that enters too many wrong pins is locked
+    /// out, longer with every further wrong pin, while the pin stays valid for
+    /// everyone else.
+    pub fn pair(&self, client: IpAddr, pin: &str) -> Pairing {
+        let mut current = self.pin.lock().unwrap();
+        let mut failures = self.failures.lock().unwrap();
+        let now = Instant::now();
+
+        let client_failures = failures.entry(client).or_default();
+        if let Some(until) = client_failures.locked_until.filter(|&until| until > now) {
+            return Pairing::LockedOut(until - now);
+        }
+
+        if constant_time_eq(pin.trim().as_bytes(), current.as_bytes()) {
+            failures.remove(&client);
+            let token = new_token();
+            self.tokens.lock().unwrap().insert(token.clone());
+            *current = new_pin();
+            return Pairing::Paired(token);
+        }
+
+        client_failures.count += 1;
+        if client_failures.count < MAX_PIN_ATTEMPTS {
+            return Pairing::WrongPin;
+        }
+        let doublings = (client_failures.count - MAX_PIN_ATTEMPTS).min(10);
+        let lockout = (PIN_LOCKOUT * 2u32.pow(doublings)).min(MAX_PIN_LOCKOUT);
+        client_failures.locked_until = Some(now + lockout);
+        Pairing::LockedOut(lockout)
+    }
+
+    pub fn is_paired(&self, token: &str) -> bool {
+        self.tokens.lock().unwrap().contains(token)
+    }
+}
+
+/// Compare without stopping at the first difference, so that response times
+/// do not reveal how many digits of a guess are right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Address of this machine on the local network, for URLs shown to other devices
+pub fn lan_ip() -> Option<IpAddr> {
+    // Connecting a UDP socket only picks the outgoing interface; nothing is sent
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+/// Whether a client is this machine: loopback, or the address given by
+/// `lan_ip()` that the URL printed by `slides serve` points to
+pub fn is_presenter_machine(client: IpAddr, lan_ip: Option<IpAddr>) -> bool {
+    client.is_loopback() || Some(client) == lan_ip
+}
+
+fn new_pin() -> String {
+    format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))
+}
+
+fn new_token() -> String {
+    let bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// HTTP server for an output directory
+pub struct DevServer {
+    server: tiny_http::Server,
+    root: PathBuf,
+    hub: Arc<SyncHub>,
+    live_hub: Arc<SyncHub>,
+    live: Arc<LiveSession>,
+    remote: Option<Arc<RemoteAccess>>,
+    progress: Reporter,
+    base_path: String,
+}
+
+/// What every request handler needs
+struct Shared {
+    root: PathBuf,
+    hub: Arc<SyncHub>,
+    live_hub: Arc<SyncHub>,
+    live: Arc<LiveSession>,
+    remote: Option<Arc<RemoteAccess>>,
+    progress: Reporter,
+    /// URL path of the deck's pages, e.g. "/en/"
+    base_path: String,
+    /// Network address of this machine, whose pages count as the presenter's
+    lan_ip: Option<IpAddr>,
+}
+
+impl DevServer {
+    /// Listen on `address`, e.g. "127.0.0.1:8000" (port 0 picks a free port)
+    pub fn bind(root: &Path, address: &str) -> Result<Self> {
+        let server = tiny_http::Server::http(address)
+            .map_err(|e| Error::Server(format!("Could not listen on {address}: {e}")))?;
+
+        Ok(Self {
+            server,
+            root: root.to_path_buf(),
+            hub: Arc::new(SyncHub::new()),
+            live_hub: Arc::new(SyncHub::new()),
+            live: Arc::new(LiveSession::new("", None)),
+            remote: None,
+            progress: Reporter::default(),
+            base_path: "/".to_string(),
+        })
+    }
+
+    /// Serve the deck's pages below `path` of the root, e.g. "/en/" for the
+    /// English build in a directory of locale builds. `/` and `/remote`
+    /// redirect there.
+    pub fn with_base_path(mut self, path: &str) -> Self {
+        self.base_path = format!("/{}/", path.trim_matches('/')).replace("//", "/");
+        self
+    }
+
+    /// Report new remote pins to `progress`
+    pub fn with_progress(mut self, progress: Reporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Collect polls and questions into `session`, e.g. to save them to a file
+    pub fn with_live_session(mut self, session: LiveSession) -> Self {
+        self.live = Arc::new(session);
+        self
+    }
+
+    /// Serve the remote control pages to devices paired with a pin. Besides
+    /// this machine, only paired devices may change the slide.
+    pub fn with_remote(mut self) -> Self {
+        self.remote = Some(Arc::new(RemoteAccess::new()));
+        self
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    pub fn hub(&self) -> Arc<SyncHub> {
+        Arc::clone(&self.hub)
+    }
+
+    pub fn remote(&self) -> Option<Arc<RemoteAccess>> {
+        self.remote.clone()
+    }
+
+    pub fn live(&self) -> Arc<LiveSession> {
+        Arc::clone(&self.live)
+    }
+
+    /// Handle requests until the process ends, each on its own thread
+    pub fn run(self) -> Result<()> {
+        let shared = Arc::new(Shared {
+            root: self.root,
+            hub: self.hub,
+            live_hub: self.live_hub,
+            live: self.live,
+            remote: self.remote,
+            progress: self.progress,
+            base_path: self.base_path,
+            lan_ip: lan_ip(),
+        });
+
+        for request in self.server.incoming_requests() {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || handle(request, &shared));
+        }
+        Ok(())
+    }
+}
+
+fn handle(mut request: Request, shared: &Shared) {
+    let (root, hub, remote) = (&shared.root, &shared.hub, shared.remote.as_deref());
+    let path = request
+        .url()
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("/")
+        .to_string();
+    let paired = remote
+        .zip(cookie(&request, REMOTE_COOKIE))
+        .is_some_and(|(remote, token)| remote.is_paired(&token));
+    let local = request
+        .remote_addr()
+        .is_some_and(|addr| is_presenter_machine(addr.ip(), shared.lan_ip));
+    let access = page_access(&path, remote.is_some(), local, paired);
+    let base = shared.base_path.as_str();
+    // Path within the deck's pages, for the redirects to its first slide
+    let deck_path = path
+        .strip_prefix(base.trim_end_matches('/'))
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+        .unwrap_or(&path);
+    let first_remote_page = format!("{base}remote/slide-1.html");
+
+    let result = match (request.method(), path.as_str()) {
+        (Method::Get, SYNC_EVENTS_PATH) => return stream_events(request, hub),
+        (Method::Get, LIVE_EVENTS_PATH) => return stream_events(request, &shared.live_hub),
+        (_, live) if live.starts_with(POLLS_PATH) || live.starts_with(QUESTIONS_PATH) => {
+            handle_live(request, &path, shared, local || paired)
+        }
+        // Audience devices only follow; navigation comes from this machine or a paired remote
+        (Method::Post, SYNC_STATE_PATH) if !local && !paired => request.respond(text_response(
+            403,
+            if remote.is_some() {
+                "Pair this device with the remote pin first"
+            } else {
+                "Only the presenter can change the slide"
+            },
+        )),
+        (Method::Post, SYNC_STATE_PATH) => match read_json(&mut request) {
+            Some(state) => {
+                hub.publish(state);
+                request.respond(Response::empty(StatusCode(204)))
+            }
+            None => request.respond(text_response(400, "Invalid state")),
+        },
+        (Method::Post, REMOTE_PAIR_PATH) if remote.is_some() => {
+            let remote = remote.expect("checked by the match guard");
+            let client = request
+                .remote_addr()
+                .map_or(IpAddr::from([0, 0, 0, 0]), |addr| addr.ip());
+            let pairing = match read_form_value(&mut request, "pin") {
+                Some(pin) => remote.pair(client, &pin),
+                None => Pairing::WrongPin,
+            };
+            match pairing {
+                Pairing::Paired(token) => {
+                    shared
+                        .progress
+                        .report(ProgressEvent::RemotePaired { pin: remote.pin() });
+                    request.respond(
+                        Response::empty(StatusCode(303))
+                            .with_header(header("Location", &first_remote_page))
+                            .with_header(header(
+                                "Set-Cookie",
+                                &format!(
+                                    "{REMOTE_COOKIE}={token}; Path=/; HttpOnly; SameSite=Strict"
+                                ),
+                            )),
+                    )
+                }
+                Pairing::WrongPin => request.respond(pair_page(Some(
+                    "Wrong pin - check the terminal running slides serve.",
+                ))),
+                Pairing::LockedOut(wait) => {
+                    shared
+                        .progress
+                        .report(ProgressEvent::RemoteClientLockedOut {
+                            client: client.to_string(),
+                            seconds: wait.as_secs(),
+                        });
+                    request.respond(
+                        pair_page(Some(&format!(
+                            "Too many wrong pins - try again in {} seconds.",
+                            wait.as_secs().max(1)
+                        )))
+                        .with_status_code(StatusCode(429))
+                        .with_header(header("Retry-After", &wait.as_secs().max(1).to_string())),
+                    )
+                }
+            }
+        }
+        (Method::Get | Method::Head, _) if access == PageAccess::NotFound => {
+            request.respond(text_response(404, "Not found"))
+        }
+        (Method::Get | Method::Head, _) if access == PageAccess::Pair => {
+            request.respond(pair_page(None))
+        }
+        (Method::Get | Method::Head, _) if access == PageAccess::Forbidden => request.respond(
+            text_response(403, "The presenter view is only served to the presenter"),
+        ),
+        (Method::Get | Method::Head, _) if matches!(deck_path, "/remote" | REMOTE_PATH) => request
+            .respond(
+                Response::empty(StatusCode(302))
+                    .with_header(header("Location", &first_remote_page)),
+            ),
+        (Method::Get | Method::Head, _) if matches!(deck_path, "" | "/") => request.respond(
+            Response::empty(StatusCode(302))
+                .with_header(header("Location", &format!("{base}slide-1.html"))),
+        ),
+        (Method::Get | Method::Head, _) => match resolve(root, &path) {
+            Some(file) => match fs::File::open(&file) {
+                Ok(content) => request.respond(
+                    Response::from_file(content)
+                        .with_header(header("Content-Type", content_type(&file)))
+                        .with_header(header("Cache-Control", "no-cache")),
+                ),
+                Err(_) => request.respond(text_response(404, "Not found")),
+            },
+            None => request.respond(text_response(404, "Not found")),
+        },
+        _ => request.respond(text_response(405, "Method not allowed")),
+    };
+
+    if let Err(e) = result {
+        debug!("Could not answer request for {path}: {e}");
+    }
+}
+
+/// Poll votes and audience questions. Only this machine and paired remotes
+/// see the questions; everyone else learns how many there are.
+fn handle_live(mut request: Request, path: &str, shared: &Shared, trusted: bool) -> io::Result<()> {
+    let live = &shared.live;
+    let method = request.method().clone();
+
+    if let Some(poll) = path.strip_prefix(POLLS_PATH) {
+        let poll = percent_decode(poll);
+        return match method {
+            Method::Get => match live.results(&poll) {
+                Some(results) => request.respond(json_response(200, &results)),
+                None => request.respond(text_response(400, "Invalid poll")),
+            },
+            Method::Post => {
+                let vote = read_json_value(&mut request);
+                let results = vote.as_ref().and_then(|vote| {
+                    live.vote(&poll, vote["voter"].as_str()?, vote["option"].as_str()?)
+                });
+                match results {
+                    Some(results) => {
+                        let event = serde_json::json!({ "type": "poll", "results": results });
+                        shared.live_hub.publish(event.to_string());
+                        request.respond(json_response(200, &results))
+                    }
+                    None => request.respond(text_response(400, "Invalid vote")),
+                }
+            }
+            _ => request.respond(text_response(405, "Method not allowed")),
+        };
+    }
+
+    let publish_count = || {
+        let questions = live.questions();
+        let open = questions.iter().filter(|q| !q.answered).count();
+        let event = serde_json::json!({
+            "type": "questions",
+            "total": questions.len(),
+            "open": open,
+        });
+        shared.live_hub.publish(event.to_string());
+    };
+
+    let answered = path
+        .strip_prefix(QUESTIONS_PATH)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .and_then(|rest| rest.strip_suffix("/answered"))
+        .map(|id| id.parse::<usize>().ok());
+
+    match (method, answered) {
+        (Method::Get, None) if path == QUESTIONS_PATH && trusted => {
+            request.respond(json_response(200, &live.questions()))
+        }
+        (Method::Post, None) if path == QUESTIONS_PATH => {
+            let question = read_json_value(&mut request);
+            let question = question.as_ref().and_then(|question| {
+                live.ask(question["text"].as_str()?, question["name"].as_str())
+            });
+            match question {
+                Some(question) => {
+                    publish_count();
+                    request.respond(json_response(201, &question))
+                }
+                None => request.respond(text_response(400, "Invalid question")),
+            }
+        }
+        (Method::Get | Method::Post, _) if !trusted => {
+            request.respond(text_response(403, "Only the presenter can do this"))
+        }
+        (Method::Post, Some(Some(id))) if live.answer(id) => {
+            publish_count();
+            request.respond(Response::empty(StatusCode(204)))
+        }
+        (Method::Post, Some(_)) => request.respond(text_response(404, "Unknown question")),
+        (Method::Get | Method::Post, _) => request.respond(text_response(404, "Not found")),
+        _ => request.respond(text_response(405, "Method not allowed")),
+    }
+}
+
+/// Keep the connection open and write every published state as an event
+fn stream_events(request: Request, hub: &SyncHub) {
+    let events = hub.subscribe();
+    let mut writer = request.into_writer();
+    let mut send = |data: &str| {
+        writer
+            .write_all(data.as_bytes())
+            .and_then(|_| writer.flush())
+            .is_ok()
+    };
+
+    if !send(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: text/event-stream\r\n\
+         Cache-Control: no-cache\r\n\
+         Connection: close\r\n\r\n",
+    ) {
+        return;
+    }
+
+    loop {
+        let open = match events.recv_timeout(KEEP_ALIVE) {
+            Ok(state) => send(&format!("data: {state}\n\n")),
+            Err(RecvTimeoutError::Timeout) => send(": keep-alive\n\n"),
+            Err(RecvTimeoutError::Disconnected) => false,
+        };
+        if !open {
+            break;
+        }
+    }
+}
+
+/// Request body as compact JSON object, if it is one
+fn read_json(request: &mut Request) -> Option<String> {
+    read_json_value(request).map(|value| value.to_string())
+}
+
+fn read_json_value(request: &mut Request) -> Option<serde_json::Value> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut body)
+        .ok()?;
+
+    serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .filter(|value| value.is_object())
+}
+
+/// Whether a client may load a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageAccess {
+    Allowed,
+    /// Remote pages without `--remote`
+    NotFound,
+    /// Needs the pin form first
+    Pair,
+    Forbidden,
+}
+
+/// Access to a URL path: remote pages need a paired device, presenter pages
+/// (with the speaker notes) this machine or a paired device
+pub fn page_access(url_path: &str, remote: bool, local: bool, paired: bool) -> PageAccess {
+    if in_pages_dir(url_path, REMOTE_PATH) {
+        match (remote, paired) {
+            (false, _) => PageAccess::NotFound,
+            (true, false) => PageAccess::Pair,
+            (true, true) => PageAccess::Allowed,
+        }
+    } else if in_pages_dir(url_path, PRESENTER_PATH) && !local && !paired {
+        if remote {
+            PageAccess::Pair
+        } else {
+            PageAccess::Forbidden
+        }
+    } else {
+        PageAccess::Allowed
+    }
+}
+
+/// Whether a URL path resolves into a pages directory such as `REMOTE_PATH`
+/// of any locale build, whatever its encoding, duplicate slashes or (on
+/// case-insensitive file systems) case
+fn in_pages_dir(url_path: &str, dir: &str) -> bool {
+    percent_decode(url_path)
+        .split(['/', '\\'])
+        .any(|segment| segment.eq_ignore_ascii_case(dir.trim_matches('/')))
+}
+
+/// Value of a field in a form-encoded request body
+fn read_form_value(request: &mut Request, name: &str) -> Option<String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut body)
+        .ok()?;
+
+    body.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then(|| percent_decode(&value.replace('+', " ")))
+    })
+}
+
+fn cookie(request: &Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .filter(|header| header.field.equiv("Cookie"))
+        .flat_map(|header| header.value.as_str().split(';'))
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then(|| value.to_string())
+        })
+}
+
+/// Pin form shown to devices that are not paired yet
+fn pair_page(error: Option<&str>) -> Response<std::io::Cursor<Vec<u8>>> {
+    let error = error
+        .map(|error| format!(r#"<p class="error">{error}</p>"#))
+        .unwrap_or_default();
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>Remote control</title>
+<style>
+body {{ background: #0a0a12; color: #e0e0e0; font-family: system-ui, sans-serif; display: grid; place-items: center; min-height: 100vh; margin: 0; }}
+form {{ display: grid; gap: 1rem; text-align: center; }}
+input, button {{ font: inherit; font-size: 1.5rem; padding: 0.75rem; border: none; border-radius: 0.5rem; text-align: center; }}
+button {{ background: #0f3460; color: white; }}
+.error {{ color: #e94560; }}
+</style>
+</head>
+<body>
+<form method="post" action="{REMOTE_PAIR_PATH}">
+<label for="pin">Pin shown by <code>slides serve --remote</code></label>
+<input id="pin" name="pin" inputmode="numeric" autocomplete="one-time-code" autofocus required>
+{error}
+<button type="submit">Connect</button>
+</form>
+</body>
+</html>
+"#
+    );
+
+    Response::from_string(html)
+        .with_status_code(StatusCode(if error.is_empty() { 200 } else { 401 }))
+        .with_header(header("Content-Type", "text/html; charset=utf-8"))
+}
+
+/// File below `root` for a URL path; none for paths leaving the root or missing files
+fn resolve(root: &Path, url_path: &str) -> Option<PathBuf> {
+    let relative = PathBuf::from(percent_decode(url_path.trim_start_matches('/')));
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    let path = root.join(relative);
+    let path = if path.is_dir() {
+        path.join("index.html")
+    } else {
+        path
+    };
+    path.is_file().then_some(path)
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match extension.as_str() {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+fn json_response(status: u16, value: &impl serde::Serialize) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(serde_json::to_string(value).unwrap_or_default())
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn text_response(status: u16, text: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(text)
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "text/plain; charset=utf-8"))
+}
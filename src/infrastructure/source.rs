@@ -0,0 +1,198 @@
+//! Source module - Where decks.yaml, templates and translations are read from:
+//! the project directory, an in-memory map or a zip archive
+
+use crate::error::{Error, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// Read access to the files of a project. Paths are relative to the project
+/// root and use forward slashes, e.g. "slides/templates/intro.html".
+pub trait TemplateSource: Send + Sync + fmt::Debug {
+    /// Content of a file, or None if it does not exist
+    fn read(&self, path: &str) -> Result<Option<String>>;
+
+    /// All files below a directory, recursively, relative to it and sorted.
+    /// A missing directory has no files.
+    fn list(&self, dir: &str) -> Result<Vec<String>>;
+
+    /// Project directory on disk, for the git info of `build`; None for
+    /// sources not read from a directory
+    fn root(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Files of a project directory on disk
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    root: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl TemplateSource for FileSource {
+    fn read(&self, path: &str) -> Result<Option<String>> {
+        let path = self.root.join(path);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        fs::read_to_string(&path)
+            .map(Some)
+            .map_err(|e| Error::FileRead { path, source: e })
+    }
+
+    fn list(&self, dir: &str) -> Result<Vec<String>> {
+        let base = self.root.join(dir);
+        let mut files = Vec::new();
+        if base.is_dir() {
+            list_recursive(&base, &base, &mut files)?;
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn root(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+fn list_recursive(base: &Path, current: &Path, files: &mut Vec<String>) -> Result<()> {
+    let entries = fs::read_dir(current).map_err(|e| Error::ReadDir {
+        path: current.to_path_buf(),
+        source: e,
+    })?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_recursive(base, &path, files)?;
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(base) else {
+            continue;
+        };
+        if let Some(relative) = relative.to_str() {
+            // Normalize to forward slashes for cross-platform compatibility
+            files.push(relative.replace('\\', "/"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Files held in memory, e.g. loaded from a database or written by a test
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: BTreeMap<String, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, e.g. `.with_file("decks.yaml", "default:\n  - \"*.html\"")`
+    pub fn with_file(mut self, path: &str, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    pub fn insert(&mut self, path: &str, content: impl Into<String>) {
+        self.files.insert(normalize(path), content.into());
+    }
+}
+
+impl<P: AsRef<str>, C: Into<String>> FromIterator<(P, C)> for MemorySource {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(iter: I) -> Self {
+        let mut source = Self::new();
+        for (path, content) in iter {
+            source.insert(path.as_ref(), content);
+        }
+        source
+    }
+}
+
+impl TemplateSource for MemorySource {
+    fn read(&self, path: &str) -> Result<Option<String>> {
+        Ok(self.files.get(&normalize(path)).cloned())
+    }
+
+    fn list(&self, dir: &str) -> Result<Vec<String>> {
+        let dir = normalize(dir);
+        let prefix = if dir.is_empty() { dir } else { dir + "/" };
+        Ok(self
+            .files
+            .keys()
+            .filter_map(|path| path.strip_prefix(&prefix))
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Files of a zip archive, e.g. an uploaded project. Text files are read into
+/// memory when the archive is opened; other files are left out.
+#[derive(Debug, Clone)]
+pub struct ZipSource {
+    files: MemorySource,
+}
+
+impl ZipSource {
+    pub fn open(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).map_err(|e| Error::FileRead {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_bytes(bytes)
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(bytes)).map_err(|e| Error::ZipError(e.to_string()))?;
+
+        let mut files = MemorySource::new();
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .map_err(|e| Error::ZipError(e.to_string()))?;
+            if file.is_dir() {
+                continue;
+            }
+
+            let name = file.name().to_string();
+            let mut content = String::new();
+            if file.read_to_string(&mut content).is_ok() {
+                files.insert(&name, content);
+            }
+        }
+
+        Ok(Self { files })
+    }
+}
+
+impl TemplateSource for ZipSource {
+    fn read(&self, path: &str) -> Result<Option<String>> {
+        self.files.read(path)
+    }
+
+    fn list(&self, dir: &str) -> Result<Vec<String>> {
+        self.files.list(dir)
+    }
+}
+
+/// "./slides\\templates/" -> "slides/templates"
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").trim_matches('/').to_string()
+}
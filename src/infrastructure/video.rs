@@ -0,0 +1,285 @@
+//! Video module - MP4 (via ffmpeg), narrated MP4 and animated GIF export of slide thumbnails
+
+use crate::error::{Error, Result};
+use ::image::codecs::gif::{GifEncoder, Repeat};
+use ::image::{Delay, Frame};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// Environment variable pointing at the ffmpeg binary to use
+pub const FFMPEG_ENV: &str = "SLIDES_FFMPEG";
+
+/// Seconds a slide is shown when neither the command line nor decks.yaml set a duration
+pub const DEFAULT_DURATION: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoFormat {
+    /// H.264 video, encoded with ffmpeg
+    #[default]
+    Mp4,
+    /// Animated GIF, encoded without external tools
+    Gif,
+    /// H.264 video with the slides' narration clips as AAC audio track
+    Narrated,
+}
+
+impl VideoFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            VideoFormat::Mp4 | VideoFormat::Narrated => "mp4",
+            VideoFormat::Gif => "gif",
+        }
+    }
+}
+
+impl FromStr for VideoFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mp4" => Ok(VideoFormat::Mp4),
+            "gif" => Ok(VideoFormat::Gif),
+            "video" | "narrated" => Ok(VideoFormat::Narrated),
+            _ => Err(format!(
+                "invalid video format '{s}' (use mp4, gif or video)"
+            )),
+        }
+    }
+}
+
+/// An image shown for `duration` seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoFrame {
+    pub image: PathBuf,
+    pub duration: f32,
+    /// Narration clip; in narrated videos the frame is shown as long as the clip plays
+    pub audio: Option<PathBuf>,
+}
+
+/// Locate ffmpeg: the `SLIDES_FFMPEG` environment variable, then the `PATH`
+pub fn find_ffmpeg() -> Option<PathBuf> {
+    std::env::var_os(FFMPEG_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .or_else(|| which::which("ffmpeg").ok())
+}
+
+/// Encode the frames as an H.264 MP4 of `width`x`height` pixels; frames with a
+/// different aspect ratio are letterboxed
+pub fn encode_mp4(
+    ffmpeg: &Path,
+    frames: &[VideoFrame],
+    output: &Path,
+    (width, height): (u32, u32),
+) -> Result<()> {
+    let Some(last) = frames.last() else {
+        return Err(Error::VideoExport("No frames to encode".to_string()));
+    };
+
+    // ffmpeg concat list; the last image is repeated so its duration is applied
+    let mut list = String::new();
+    for frame in frames {
+        list.push_str(&format!(
+            "file '{}'\nduration {:.3}\n",
+            concat_path(&frame.image)?,
+            frame.duration
+        ));
+    }
+    list.push_str(&format!("file '{}'\n", concat_path(&last.image)?));
+
+    let list_path = output.with_extension("frames.txt");
+    fs::write(&list_path, list).map_err(|e| Error::FileWrite {
+        path: list_path.clone(),
+        source: e,
+    })?;
+
+    let result = run_ffmpeg(
+        ffmpeg,
+        Command::new(ffmpeg)
+            .args(["-y", "-loglevel", "error"])
+            .args(["-f", "concat", "-safe", "0", "-i"])
+            .arg(&list_path)
+            .args([
+                "-vf",
+                &scale_filter(width, height),
+                "-r",
+                "30",
+                "-c:v",
+                "libx264",
+            ])
+            .args(["-movflags", "+faststart"])
+            .arg(output),
+    );
+    fs::remove_file(&list_path).ok();
+
+    result
+}
+
+/// Encode the frames with their narration clips as an H.264/AAC MP4. Each
+/// slide becomes a segment as long as its clip, or `duration` seconds of
+/// silence without one; the segments are then joined without re-encoding.
+pub fn encode_narrated(
+    ffmpeg: &Path,
+    frames: &[VideoFrame],
+    output: &Path,
+    (width, height): (u32, u32),
+) -> Result<()> {
+    if frames.is_empty() {
+        return Err(Error::VideoExport("No frames to encode".to_string()));
+    }
+
+    let segments_dir = output.with_extension("segments");
+    fs::create_dir_all(&segments_dir).map_err(|e| Error::CreateDir {
+        path: segments_dir.clone(),
+        source: e,
+    })?;
+
+    let result = encode_segments(ffmpeg, frames, &segments_dir, (width, height))
+        .and_then(|segments| join_segments(ffmpeg, &segments, output));
+    fs::remove_dir_all(&segments_dir).ok();
+
+    result
+}
+
+fn encode_segments(
+    ffmpeg: &Path,
+    frames: &[VideoFrame],
+    segments_dir: &Path,
+    (width, height): (u32, u32),
+) -> Result<Vec<PathBuf>> {
+    let mut segments = Vec::new();
+
+    for (index, frame) in frames.iter().enumerate() {
+        let segment = segments_dir.join(format!("segment-{}.mp4", index + 1));
+        let mut command = Command::new(ffmpeg);
+        command
+            .args(["-y", "-loglevel", "error"])
+            .args(["-loop", "1", "-framerate", "30", "-i"])
+            .arg(&frame.image);
+
+        match &frame.audio {
+            Some(audio) => {
+                if !audio.is_file() {
+                    return Err(Error::VideoExport(format!(
+                        "Audio clip not found: {}",
+                        audio.display()
+                    )));
+                }
+                command.arg("-i").arg(audio).arg("-shortest");
+            }
+            None => {
+                command
+                    .args(["-f", "lavfi", "-i", "anullsrc=r=48000:cl=stereo"])
+                    .args(["-t", &format!("{:.3}", frame.duration.max(0.0))]);
+            }
+        }
+
+        command
+            .args(["-vf", &scale_filter(width, height), "-r", "30"])
+            .args(["-c:v", "libx264", "-tune", "stillimage"])
+            .args(["-c:a", "aac", "-ar", "48000", "-ac", "2"])
+            .arg(&segment);
+        run_ffmpeg(ffmpeg, &mut command)?;
+        segments.push(segment);
+    }
+
+    Ok(segments)
+}
+
+fn join_segments(ffmpeg: &Path, segments: &[PathBuf], output: &Path) -> Result<()> {
+    let mut list = String::new();
+    for segment in segments {
+        list.push_str(&format!("file '{}'\n", concat_path(segment)?));
+    }
+
+    let list_path = output.with_extension("segments.txt");
+    fs::write(&list_path, list).map_err(|e| Error::FileWrite {
+        path: list_path.clone(),
+        source: e,
+    })?;
+
+    let result = run_ffmpeg(
+        ffmpeg,
+        Command::new(ffmpeg)
+            .args(["-y", "-loglevel", "error"])
+            .args(["-f", "concat", "-safe", "0", "-i"])
+            .arg(&list_path)
+            .args(["-c", "copy", "-movflags", "+faststart"])
+            .arg(output),
+    );
+    fs::remove_file(&list_path).ok();
+
+    result
+}
+
+/// Scale to fit `width`x`height`, letterboxing other aspect ratios
+fn scale_filter(width: u32, height: u32) -> String {
+    format!(
+        "scale={width}:{height}:force_original_aspect_ratio=decrease,\
+         pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,format=yuv420p"
+    )
+}
+
+fn run_ffmpeg(ffmpeg: &Path, command: &mut Command) -> Result<()> {
+    let result = command
+        .output()
+        .map_err(|e| Error::VideoExport(format!("Could not run '{}': {e}", ffmpeg.display())))?;
+    if !result.status.success() {
+        return Err(Error::VideoExport(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Encode the frames as an endlessly looping animated GIF
+pub fn encode_gif(frames: &[VideoFrame], output: &Path) -> Result<()> {
+    if frames.is_empty() {
+        return Err(Error::VideoExport("No frames to encode".to_string()));
+    }
+
+    let file = File::create(output).map_err(|e| Error::FileWrite {
+        path: output.to_path_buf(),
+        source: e,
+    })?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|e| Error::VideoExport(format!("Could not encode GIF: {e}")))?;
+
+    for frame in frames {
+        let image = ::image::open(&frame.image)
+            .map_err(|e| {
+                Error::VideoExport(format!("Could not open {}: {e}", frame.image.display()))
+            })?
+            .to_rgba8();
+        let milliseconds = (frame.duration.max(0.0) * 1000.0).round() as u32;
+        encoder
+            .encode_frame(Frame::from_parts(
+                image,
+                0,
+                0,
+                Delay::from_numer_denom_ms(milliseconds, 1),
+            ))
+            .map_err(|e| Error::VideoExport(format!("Could not encode GIF: {e}")))?;
+    }
+
+    Ok(())
+}
+
+/// Absolute path quoted for an ffmpeg concat list
+fn concat_path(path: &Path) -> Result<String> {
+    let path = path.canonicalize().map_err(|e| Error::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(path.to_string_lossy().replace('\'', "'\\''"))
+}
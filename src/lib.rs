@@ -4,7 +4,8 @@ pub mod error;
 pub mod infrastructure;
 pub mod minijinja;
 pub mod model;
+pub mod progress;
 pub mod services;
 pub mod util;
 
-pub use error::{Error, Result};
+pub use error::{Error, ErrorCategory, Result};
@@ -1,49 +1,244 @@
 mod cli;
+mod console;
 
 use clap::Parser;
-use cli::{Cli, Commands};
-use env_logger::Env;
-use slides_rs::model::Project;
+use cli::{Check, Cli, Commands, Compression, ExportFormat};
+use console::Console;
+use serde_json::{json, Value};
+use slides_rs::infrastructure::chrome;
+use slides_rs::infrastructure::pdf::{ImageCompression, PdfOptions, Protection};
+use slides_rs::infrastructure::video::VideoFormat;
+use slides_rs::model::{A11yOptions, BuildOptions, PdfExport, Project, VideoExport};
+use slides_rs::{Error, Result};
 
 fn main() {
-    // Initialize logger with default level of "warn" for warnings to show
-    env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init();
-
     let cli = Cli::parse();
+    // Passwords on stdout leave it to them; everything else goes to stderr
+    let passwords_on_stdout = matches!(
+        &cli.command,
+        Some(Commands::Export { passwords_out: Some(path), .. }) if path == "-"
+    );
+    let console =
+        Console::new(cli.message_format, cli.quiet).with_text_on_stderr(passwords_on_stdout);
+    console.init_logger();
+
+    let Some(command) = cli.command else {
+        eprintln!("No subcommand provided. Use 'slides --help' for usage information.");
+        std::process::exit(1);
+    };
+
+    let name = command.name();
+    match run(command, console) {
+        Ok(result) => console.succeed(name, result),
+        Err(e) => std::process::exit(console.fail(name, &e)),
+    }
+}
 
-    let result = match cli.command {
-        Some(Commands::Init) => Project::init().map(|_| ()),
-        Some(Commands::New { name, path }) => Project::create(&name, path.as_deref()).map(|_| ()),
+/// Run a command, returning its result object for `--message-format json`
+fn run(command: Commands, console: Console) -> Result<Value> {
+    // Commands working on the project in the current directory
+    let current = || Project::current().map(|p| p.with_progress(console.progress()));
 
-        Some(Commands::Build { deck, lang }) => {
-            Project::current().and_then(|p| p.deck(&deck, &lang).build())
+    match command {
+        Commands::Init => {
+            console.say("🚀 Initializing slides project...\n");
+            let project = Project::init()?;
+            console.say("🎉 Project ready! Run 'slides build' to see your presentation.");
+            Ok(json!({ "root": project.root() }))
         }
-        Some(Commands::Watch { deck, lang }) => {
-            Project::current().and_then(|p| p.deck(&deck, &lang).watch())
+        Commands::New { name, path } => {
+            console.say(format!("📁 Creating project '{name}'...\n"));
+            let project = Project::create(&name, path.as_deref())?;
+            console.say("🎉 Project ready! Run 'slides build' to see your presentation.");
+            console.say(format!("\n💡 Run: cd {name} && slides build"));
+            Ok(json!({ "root": project.root() }))
         }
-        Some(Commands::Export {
+
+        Commands::Build {
+            deck,
+            lang,
+            no_thumbnails,
+            retina,
+            chrome_path,
+        } => current().and_then(|p| {
+            let options = BuildOptions::default()
+                .with_lang(&lang)
+                .with_thumbnails(!no_thumbnails)
+                .with_retina(retina)
+                .with_chrome_path(chrome_path);
+            let report = p.build(&deck, &options)?;
+            console.built(&report);
+            Ok(json!(report))
+        }),
+        Commands::Watch {
+            deck,
+            lang,
+            no_thumbnails,
+            retina,
+            chrome_path,
+        } => current().and_then(|p| {
+            console.say("👀 Watch mode started\n");
+            p.deck(&deck, &lang)
+                .with_thumbnails(!no_thumbnails)
+                .with_retina(retina)
+                .with_chrome_path(chrome_path)
+                .watch()?;
+            Ok(Value::Null)
+        }),
+        Commands::Serve {
+            deck,
+            lang,
+            host,
+            port,
+            watch,
+            remote,
+            no_thumbnails,
+            chrome_path,
+        } => current().and_then(|p| {
+            let host =
+                host.unwrap_or_else(|| if remote { "0.0.0.0" } else { "127.0.0.1" }.to_string());
+            p.deck(&deck, &lang)
+                .with_thumbnails(!no_thumbnails)
+                .with_chrome_path(chrome_path)
+                .serve(&format!("{host}:{port}"), watch, remote)?;
+            Ok(Value::Null)
+        }),
+        Commands::Lint { deck, lang, strict } => current().and_then(|p| {
+            let report = p.lint(deck.as_deref(), &lang)?;
+            console.linted(&report);
+            let (errors, warnings) = (report.errors(), report.warnings());
+            if errors > 0 || (strict && warnings > 0) {
+                return Err(Error::LintFailed { errors, warnings });
+            }
+            Ok(json!(report))
+        }),
+        Commands::Check {
+            check:
+                Check::A11y {
+                    deck,
+                    lang,
+                    no_contrast,
+                    chrome_path,
+                    strict,
+                },
+        } => current().and_then(|p| {
+            let options = A11yOptions::default()
+                .with_lang(&lang)
+                .with_contrast(!no_contrast)
+                .with_chrome_path(chrome_path);
+            let report = p.check_a11y(&deck, &options)?;
+            console.checked_a11y(&report);
+            let (errors, warnings) = (report.errors(), report.warnings());
+            if errors > 0 || (strict && warnings > 0) {
+                return Err(Error::A11yFailed { errors, warnings });
+            }
+            Ok(json!(report))
+        }),
+        Commands::Export {
             deck,
             lang,
             password,
+            owner_password,
+            encryption,
+            passwords_out,
+            chrome_path,
+            retina,
+            fragments,
             no_print,
             no_copy,
             output,
-        }) => Project::current().and_then(|p| {
-            p.deck(&deck, &lang).build()?;
-            p.export_pdf(password, no_print, no_copy, &output)
-        }),
-        Some(Commands::ImportImages { pptx }) => {
-            Project::current().and_then(|p| p.import_images(&pptx))
-        }
+            format,
+            duration,
+            timings,
+            page_size,
+            scaling,
+            compression,
+            quality,
+            watermark,
+            recipients,
+        } => current().and_then(|p| {
+            let options = PdfOptions {
+                page_size,
+                scaling,
+                compression: match compression {
+                    Compression::None => ImageCompression::None,
+                    Compression::Flate => ImageCompression::Flate,
+                    Compression::Jpeg => ImageCompression::Jpeg(quality),
+                },
+            };
+            // Exports need up-to-date thumbnails, so a missing browser is an error here
+            let chrome = chrome::find_chrome(chrome_path.as_deref())?;
+            let build = BuildOptions::default()
+                .with_lang(&lang)
+                .with_retina(retina)
+                .with_fragments(fragments)
+                .with_chrome_path(Some(chrome));
+            p.build(&deck, &build)?;
+            console.say("");
 
-        None => {
-            eprintln!("No subcommand provided. Use 'slides --help' for usage information.");
-            std::process::exit(1);
-        }
-    };
+            let output =
+                output.unwrap_or_else(|| format!("output/presentation.{}", format.extension()));
+            let video_format = match format {
+                ExportFormat::Pdf => None,
+                ExportFormat::Mp4 => Some(VideoFormat::Mp4),
+                ExportFormat::Gif => Some(VideoFormat::Gif),
+                ExportFormat::Video => Some(VideoFormat::Narrated),
+            };
+            if let Some(format) = video_format {
+                console.say("🎞️  Video Export\n");
+                let report = p.export_video(&VideoExport {
+                    deck: deck.clone(),
+                    lang: lang.clone(),
+                    format,
+                    output_path: output,
+                    duration,
+                    timings,
+                })?;
+                console.exported_video(&report);
+                return Ok(json!(report));
+            }
 
-    if let Err(e) = result {
-        eprintln!("❌ Error: {}", e);
-        std::process::exit(1);
+            let protection = Protection {
+                algorithm: encryption,
+                owner_password,
+                user_password: password,
+                no_print,
+                no_copy,
+            };
+            let export = PdfExport {
+                deck,
+                lang,
+                output_path: output,
+                options,
+                protection,
+                watermark: watermark.watermark(),
+                retina,
+                fragments,
+                passwords_out,
+            };
+            let report = match recipients {
+                Some(recipients) => {
+                    console.say("📄 PDF Export for recipients\n");
+                    p.export_pdf_for_recipients(&export, &recipients)?
+                }
+                None => {
+                    console.say("📄 PDF Export\n");
+                    p.export_pdf(&export)?
+                }
+            };
+            console.exported_pdf(&report, export.passwords_out.as_deref());
+            Ok(json!(report))
+        }),
+        Commands::ImportImages { pptx } => current().and_then(|p| {
+            console.say(format!("📥 Extracting images from {pptx}"));
+            let count = p.import_images(&pptx)?;
+            match count {
+                0 => console.say("   No images found"),
+                count => console.say(format!(
+                    "   ✅ {count} images → slides/assets/import/images"
+                )),
+            }
+            Ok(json!({ "images": count }))
+        }),
     }
 }
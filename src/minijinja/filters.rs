@@ -49,6 +49,16 @@ pub fn markdown_filter(value: &Value) -> Result<Value, Error> {
     Ok(Value::from_safe_string(markdown_to_html(text)))
 }
 
+/// Usage: {{ "Revealed next" | fragment }} or {{ "Third" | fragment(3) }}
+/// Wraps the value in a span that is revealed step by step; without a step
+/// number, fragments appear in document order
+pub fn fragment_filter(value: &Value, step: Option<u32>) -> Value {
+    let step = step.map(|step| format!("=\"{step}\"")).unwrap_or_default();
+    Value::from_safe_string(format!(
+        "<span class=\"fragment\" data-fragment{step}>{value}</span>"
+    ))
+}
+
 /// Usage: {{ variable | dump }}
 pub fn dump_filter(value: &Value) -> Result<Value, Error> {
     Ok(Value::from_safe_string(format_dump(value)))
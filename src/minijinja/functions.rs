@@ -1,22 +1,35 @@
 //! MiniJinja functions for template rendering
 
 use super::filters::format_dump;
+use crate::infrastructure::chrome::ThumbnailSize;
+use crate::infrastructure::live;
+use crate::services::data::{self, DataFiles};
 use crate::util::html_escape;
 use log::warn;
+use minijinja::value::{Enumerator, Kwargs, Object, ValueKind};
 use minijinja::{Error, ErrorKind, State, Value};
-use std::fs;
-use std::path::Path;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Usage: {{ dump() }} or {{ dump(variable) }}
 pub fn dump_function(state: &State, value: Option<Value>) -> Result<Value, Error> {
     match value {
         Some(v) => Ok(Value::from_safe_string(format_dump(&v))),
         None => {
+            // Everything in scope except functions and private `_` variables
+            let mut names = state.known_variables();
+            names.sort();
             let mut context_map = serde_json::Map::new();
-            for name in ["app", "slide", "CURRENT", "TOTAL", "PREV", "NEXT"] {
-                let Some(val) = state.lookup(name) else {
+            for name in names {
+                if name.starts_with('_') {
+                    continue;
+                }
+                let Some(val) = state.lookup(&name) else {
                     continue;
                 };
+                if val.kind() == ValueKind::Plain {
+                    continue;
+                }
                 let Ok(json_val) = serde_json::to_value(&val) else {
                     continue;
                 };
@@ -32,6 +45,35 @@ pub fn dump_function(state: &State, value: Option<Value>) -> Result<Value, Error
     }
 }
 
+/// Usage: {% for row in load_data('sales.csv') %} - any file of `slides/data`,
+/// also in subdirectories or when `data.sales` is taken by another format
+pub fn make_load_data_function(
+    files: Arc<DataFiles>,
+) -> impl Fn(String) -> Result<Value, Error> + Send + Sync {
+    move |file: String| {
+        files.get(&file).map(Value::from_serialize).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("Data file '{file}' not found in slides/data"),
+            )
+        })
+    }
+}
+
+/// `data.<name>`: the top-level data files, converted when a template uses them
+#[derive(Debug)]
+pub struct DataObject(pub Arc<DataFiles>);
+
+impl Object for DataObject {
+    fn get_value(self: &Arc<Self>, key: &Value) -> Option<Value> {
+        data::by_name(&self.0, key.as_str()?).map(Value::from_serialize)
+    }
+
+    fn enumerate(self: &Arc<Self>) -> Enumerator {
+        Enumerator::Values(data::names(&self.0).into_keys().map(Value::from).collect())
+    }
+}
+
 /// Usage: {{ asset('logo.png') }} -> "assets/logo.png"
 pub fn asset_function(state: &State, filename: String) -> String {
     if filename.is_empty() {
@@ -39,30 +81,147 @@ pub fn asset_function(state: &State, filename: String) -> String {
         return "assets/".to_string();
     }
 
+    format!("{}assets/{}", output_prefix(state), filename)
+}
+
+/// Usage: {{ thumbnail(3, 'small') }} -> "thumbnails/small/slide-3.jpg"
+/// Sizes: small, medium, full (default) and 2x
+pub fn thumbnail_function(
+    state: &State,
+    number: usize,
+    size: Option<String>,
+) -> Result<String, Error> {
+    let size = match size {
+        Some(size) => size
+            .parse::<ThumbnailSize>()
+            .map_err(|e| Error::new(ErrorKind::InvalidOperation, e))?,
+        None => ThumbnailSize::Full,
+    };
+
+    Ok(format!("{}{}", output_prefix(state), size.path(number)))
+}
+
+/// Usage: {{ poll("q1", options=["Rust", "Go"], question="Favourite language?") }}
+/// Audience devices vote while `slides serve` runs; the slide shows live results
+pub fn poll_function(id: String, kwargs: Kwargs) -> Result<Value, Error> {
+    let options: Vec<String> = kwargs.get("options")?;
+    let question: Option<String> = kwargs.get("question")?;
+    kwargs.assert_all_used()?;
+
+    if !live::is_valid_id(&id) {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("Invalid poll id '{id}' (use letters, digits, - and _)"),
+        ));
+    }
+    if options.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("Poll '{id}' needs options"),
+        ));
+    }
+
+    let question = question
+        .map(|question| format!("<p class=\"poll-question\">{}</p>", html_escape(&question)))
+        .unwrap_or_default();
+    let options: String = options
+        .iter()
+        .map(|option| {
+            let option = html_escape(option);
+            format!(
+                "<li><button type=\"button\" class=\"poll-option\" data-option=\"{option}\">\
+                 <span class=\"poll-label\">{option}</span>\
+                 <span class=\"poll-bar\"></span><span class=\"poll-count\"></span></button></li>"
+            )
+        })
+        .collect();
+
+    Ok(Value::from_safe_string(format!(
+        "<div class=\"poll\" data-poll=\"{id}\">{question}<ul class=\"poll-options\">{options}</ul>\
+         <p class=\"poll-total\"></p></div>"
+    )))
+}
+
+/// Usage: {{ questions() }} or {{ questions(placeholder="Ask away", submit="Send") }}
+/// Form for audience questions, queued in presenter mode while `slides serve` runs
+pub fn questions_function(kwargs: Kwargs) -> Result<Value, Error> {
+    let placeholder: Option<String> = kwargs.get("placeholder")?;
+    let name: Option<String> = kwargs.get("name")?;
+    let submit: Option<String> = kwargs.get("submit")?;
+    kwargs.assert_all_used()?;
+
+    let placeholder = html_escape(placeholder.as_deref().unwrap_or("Your question"));
+    let name = html_escape(name.as_deref().unwrap_or("Name (optional)"));
+    let submit = html_escape(submit.as_deref().unwrap_or("Ask"));
+
+    Ok(Value::from_safe_string(format!(
+        "<form class=\"ask\" data-questions>\
+         <textarea name=\"text\" placeholder=\"{placeholder}\" maxlength=\"500\" required></textarea>\
+         <input name=\"name\" placeholder=\"{name}\" maxlength=\"80\">\
+         <button type=\"submit\">{submit}</button></form>"
+    )))
+}
+
+/// Relative path from the page being rendered back to the output directory
+fn output_prefix(state: &State) -> String {
     let depth = match state.lookup("_output_path") {
         Some(val) => val.as_str().map(|p| p.matches('/').count()).unwrap_or(0),
         None => 0,
     };
 
-    let prefix = "../".repeat(depth);
-    format!("{}assets/{}", prefix, filename)
+    "../".repeat(depth)
+}
+
+/// Usage: {{ locale_url('de') }} -> "../de/slide-3.html"
+/// The same page in the build of another locale, which is written next to this
+/// one to `output/<lang>/`
+pub fn locale_url_function(state: &State, lang: String) -> Result<String, Error> {
+    let known = state
+        .lookup("app")
+        .and_then(|app| app.get_attr("locales").ok())
+        .and_then(|locales| {
+            locales.try_iter().ok().map(|mut iter| {
+                iter.any(|locale| {
+                    locale
+                        .get_attr("code")
+                        .is_ok_and(|code| code.as_str() == Some(lang.as_str()))
+                })
+            })
+        });
+    if known == Some(false) {
+        warn!("locale_url(): no translations for locale '{lang}'");
+    }
+
+    let page = state
+        .lookup("_output_path")
+        .and_then(|path| path.as_str().map(str::to_string))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                "locale_url() is only available in pages with a path",
+            )
+        })?;
+    Ok(format!("{}../{lang}/{page}", output_prefix(state)))
+}
+
+/// Usage: {% for section in toc() %}{{ section.title }}{% endfor %}
+pub fn toc_function(state: &State) -> Value {
+    state
+        .lookup("_toc")
+        .unwrap_or_else(|| Value::from(Vec::<Value>::new()))
 }
 
 /// Usage: {{ source("code/example.js") }}
-pub fn make_source_function() -> impl Fn(String) -> Result<String, Error> + Send + Sync {
+/// Raw content of a file below `slides/templates`, e.g. to show code samples
+pub fn make_source_function(
+    files: Arc<HashMap<String, String>>,
+) -> impl Fn(String) -> Result<String, Error> + Send + Sync {
     move |name: String| {
-        let path = format!("slides/templates/{}", name);
-        if Path::new(&path).exists() {
-            return fs::read_to_string(&path).map_err(|e| {
-                Error::new(
-                    ErrorKind::InvalidOperation,
-                    format!("Could not read {path}: {e}"),
-                )
-            });
-        }
-        Err(Error::new(
-            ErrorKind::InvalidOperation,
-            format!("Template not found: {path}"),
-        ))
+        files.get(&name).cloned().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("Template not found: slides/templates/{name}"),
+            )
+        })
     }
 }
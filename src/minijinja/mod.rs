@@ -3,13 +3,25 @@
 pub(crate) mod filters;
 mod functions;
 
-pub use filters::{dump_filter, make_trans_filter, markdown_filter};
-pub use functions::{asset_function, dump_function, make_source_function};
+pub use filters::{dump_filter, fragment_filter, make_trans_filter, markdown_filter};
+pub use functions::{
+    asset_function, dump_function, locale_url_function, make_load_data_function,
+    make_source_function, poll_function, questions_function, thumbnail_function, toc_function,
+    DataObject,
+};
 
+use crate::services::data::DataFiles;
+use crate::util::get_template_extension;
+use log::warn;
+use minijinja::Value;
 use minijinja::{AutoEscape, Environment};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+/// Variables available in every template besides the page context: `deck`,
+/// `build`, `env` and the globals of decks.yaml
+pub type Globals = BTreeMap<String, serde_json::Value>;
+
 fn create_environment() -> Environment<'static> {
     let mut env = Environment::new();
     env.set_trim_blocks(true);
@@ -21,21 +33,40 @@ fn create_environment() -> Environment<'static> {
 pub fn setup_environment(
     layouts: &HashMap<String, String>,
     translations: &HashMap<String, String>,
+    globals: &Globals,
+    data: &Arc<DataFiles>,
 ) -> Environment<'static> {
     let mut env = create_environment();
 
+    // Before the functions, so that a global cannot replace one
+    for (name, value) in globals {
+        env.add_global(name.clone(), Value::from_serialize(value));
+    }
+    env.add_global("data", Value::from_object(DataObject(Arc::clone(data))));
+
     let translations_arc = Arc::new(translations.clone());
     env.add_filter("trans", make_trans_filter(translations_arc));
     env.add_filter("markdown", markdown_filter);
     env.add_filter("dump", dump_filter);
+    env.add_filter("fragment", fragment_filter);
 
     env.add_function("dump", dump_function);
     env.add_function("asset", asset_function);
-    env.add_function("source", make_source_function());
+    env.add_function("source", make_source_function(Arc::new(layouts.clone())));
+    env.add_function("toc", toc_function);
+    env.add_function("load_data", make_load_data_function(Arc::clone(data)));
+    env.add_function("locale_url", locale_url_function);
+    env.add_function("thumbnail", thumbnail_function);
+    env.add_function("poll", poll_function);
+    env.add_function("questions", questions_function);
 
-    for (name, content) in layouts {
+    // Other files are only there for source()
+    let templates = layouts
+        .iter()
+        .filter(|(name, _)| get_template_extension(name).is_some());
+    for (name, content) in templates {
         if let Err(e) = env.add_template_owned(name.clone(), content.clone()) {
-            eprintln!("⚠️  Warning: Could not load template '{}': {}", name, e);
+            warn!("Could not load template '{name}': {e}");
         }
     }
 
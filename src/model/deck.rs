@@ -1,69 +1,449 @@
 //! Deck - a collection of slides that can be built, watched, and exported
 
-use super::Slide;
+use super::{BuildReport, Manifest, Section, SectionRef, Slide, MANIFEST_FILE};
 use crate::error::{Error, Result};
 use crate::infrastructure::chrome;
-use crate::services::{render, translations};
+use crate::infrastructure::git;
+use crate::infrastructure::live::{self, LiveSession};
+use crate::infrastructure::server::{self, DevServer};
+use crate::infrastructure::source::{FileSource, TemplateSource};
+use crate::progress::{ProgressEvent, Reporter};
+use crate::services::data;
+use crate::services::render::{self, RenderContext};
+use crate::services::translations::{self, Locale};
 use crate::util;
 use indexmap::IndexMap;
 use log::warn;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Poll votes and questions collected by `slides serve`, one file per session
+const SESSIONS_DIR: &str = "sessions";
+
+pub(super) const DECKS_FILE: &str = "decks.yaml";
+pub(super) const TEMPLATES_DIR: &str = "slides/templates";
+/// Builds, one directory per language (`output/en/`, `output/de/`) so that
+/// `locale_url()` links between them resolve
+pub(super) const OUTPUT_DIR: &str = "output";
+const TRANSLATIONS_DIR: &str = "slides/translations";
+const DATA_DIR: &str = "slides/data";
+
+/// Render-context variables set by the build, not by decks.yaml
+const BUILTIN_GLOBALS: &[&str] = &["deck", "build", "env", "data"];
+
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum DeckConfig {
     /// Flat list of slide patterns
-    Flat(Vec<String>),
+    Flat(Vec<DeckEntry>),
+    /// Deck with metadata: `metadata` and `slides` (flat or sectioned)
+    Detailed(Box<DetailedDeckConfig>),
     /// Nested structure: Section key -> Slide patterns
-    Sectioned(IndexMap<String, Vec<String>>),
+    Sectioned(IndexMap<String, SectionConfig>),
+}
+
+/// Forms of `DeckConfig` without `slides` key, as serde tells them apart
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeckConfigForm {
+    Flat(Vec<DeckEntry>),
+    Sectioned(IndexMap<String, SectionConfig>),
+}
+
+impl<'de> Deserialize<'de> for DeckConfig {
+    /// `deck: {slides: [...]}` is a deck with one section named "slides",
+    /// not a deck with settings that has none of them. Other mappings with
+    /// `slides` are decks with settings unless they read as sections; their
+    /// errors name the setting that is wrong.
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let value = serde_yaml::Value::deserialize(deserializer)?;
+        let Some(map) = value.as_mapping().filter(|map| map.contains_key("slides")) else {
+            let form = serde_yaml::from_value(value).map_err(|_| {
+                serde::de::Error::custom(
+                    "a deck must be a list of slide patterns, sections or a deck with `slides`",
+                )
+            })?;
+            return Ok(match form {
+                DeckConfigForm::Flat(entries) => DeckConfig::Flat(entries),
+                DeckConfigForm::Sectioned(sections) => DeckConfig::Sectioned(sections),
+            });
+        };
+
+        let sections = || serde_yaml::from_value(value.clone()).map(DeckConfig::Sectioned);
+        if map.len() == 1 {
+            if let Ok(sections) = sections() {
+                return Ok(sections);
+            }
+        }
+        match serde_yaml::from_value(value.clone()) {
+            Ok(config) => Ok(DeckConfig::Detailed(config)),
+            Err(e) => sections().map_err(|_| serde::de::Error::custom(e)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetailedDeckConfig {
+    #[serde(default)]
+    pub metadata: DeckMetadata,
+    #[serde(default)]
+    pub video: VideoConfig,
+    #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub timing: TimingConfig,
+    /// Variables for all templates, e.g. `company: ACME`
+    #[serde(default)]
+    pub globals: IndexMap<String, serde_json::Value>,
+    /// Environment variables templates may read as `env.NAME`
+    #[serde(default)]
+    pub env: Vec<String>,
+    pub slides: Box<DeckConfig>,
+}
+
+/// Descriptive deck data, used e.g. for the PDF document information
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeckMetadata {
+    /// Literal text or translation key
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Literal text or translation key
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Creation date, e.g. "2026-10-19"
+    pub date: Option<String>,
+}
+
+impl DeckMetadata {
+    /// Resolve title and subject through the translations
+    pub fn translated(&self, translations: &HashMap<String, String>) -> Self {
+        let translate = |text: &Option<String>| {
+            text.as_ref()
+                .map(|t| translations.get(t).cloned().unwrap_or_else(|| t.clone()))
+        };
+
+        Self {
+            title: translate(&self.title),
+            subject: translate(&self.subject),
+            ..self.clone()
+        }
+    }
+}
+
+/// How long slides are shown in video exports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VideoConfig {
+    /// Seconds per slide, unless listed in `durations`
+    pub duration: Option<f32>,
+    /// Seconds per slide by template pattern, e.g. `slides/demo-*.html: 20`
+    #[serde(default)]
+    pub durations: IndexMap<String, f32>,
+}
+
+impl VideoConfig {
+    /// Duration of the first pattern matching the slide template
+    pub fn slide_duration(&self, template: &str) -> Option<f32> {
+        find_by_template(&self.durations, template).copied()
+    }
+}
+
+/// Narration clips played on the slide pages and muxed into narrated videos
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioConfig {
+    /// Go to the next slide when its clip has finished playing
+    #[serde(default)]
+    pub auto_advance: bool,
+    /// Clip by template pattern, relative to `slides/assets`,
+    /// e.g. `slides/intro.html: audio/intro.mp3`
+    #[serde(default)]
+    pub clips: IndexMap<String, String>,
+}
+
+impl AudioConfig {
+    /// Clip of the first pattern matching the slide template
+    pub fn slide_clip(&self, template: &str) -> Option<&str> {
+        find_by_template(&self.clips, template).map(String::as_str)
+    }
+}
+
+/// Planned presentation timing shown in presenter mode
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimingConfig {
+    /// Total target time, e.g. `20m`; shared by slides without a planned duration
+    pub target: Option<Seconds>,
+    /// Planned duration by template pattern, e.g. `slides/demo-*.html: 3m30s`
+    #[serde(default)]
+    pub durations: IndexMap<String, Seconds>,
+}
+
+impl TimingConfig {
+    /// Planned seconds per slide. Slides without a pattern share the time of
+    /// the target left over; without a target they have no plan.
+    pub fn plan(&self, templates: &[&str]) -> Vec<Option<f32>> {
+        let mut plan: Vec<Option<f32>> = templates
+            .iter()
+            .map(|template| find_by_template(&self.durations, template).map(|d| d.0))
+            .collect();
+
+        let unplanned = plan.iter().filter(|duration| duration.is_none()).count();
+        if let (Some(target), true) = (self.target, unplanned > 0) {
+            let planned: f32 = plan.iter().flatten().sum();
+            let share = ((target.0 - planned) / unplanned as f32).max(0.0);
+            for duration in plan.iter_mut().filter(|duration| duration.is_none()) {
+                *duration = Some(share);
+            }
+        }
+
+        plan
+    }
+}
+
+/// Duration in seconds, written as a number or text like `90s`, `2m` or `1m30s`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Seconds(pub f32);
+
+impl<'de> Deserialize<'de> for Seconds {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f32),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(seconds) if seconds >= 0.0 => Ok(Seconds(seconds)),
+            Raw::Number(seconds) => Err(serde::de::Error::custom(format!(
+                "invalid duration {seconds}"
+            ))),
+            Raw::Text(text) => util::parse_duration(&text).map(Seconds).ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "invalid duration '{text}' (use e.g. 90, 45s, 2m or 1m30s)"
+                ))
+            }),
+        }
+    }
+}
+
+/// Value of the first pattern matching the template. Patterns ending in
+/// `.html` match every template extension, like in slide lists.
+fn find_by_template<'a, T>(entries: &'a IndexMap<String, T>, template: &str) -> Option<&'a T> {
+    let html_name = util::get_template_extension(template)
+        .map(|ext| format!("{}.html", template.trim_end_matches(ext)));
+
+    entries
+        .iter()
+        .find(|(pattern, _)| {
+            let Ok(pattern) = glob::Pattern::new(pattern) else {
+                return false;
+            };
+            pattern.matches(template) || html_name.as_deref().is_some_and(|n| pattern.matches(n))
+        })
+        .map(|(_, value)| value)
+}
+
+/// Item of a slide list: a glob pattern or one or more nested sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeckEntry {
+    Pattern(String),
+    Sections(IndexMap<String, SectionConfig>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SectionConfig {
+    /// Slide patterns and nested sections
+    Slides(Vec<DeckEntry>),
+    /// Section with a display title (literal text or translation key)
+    Titled {
+        title: Option<String>,
+        slides: Vec<DeckEntry>,
+    },
+}
+
+impl SectionConfig {
+    fn title(&self) -> Option<String> {
+        match self {
+            SectionConfig::Slides(_) => None,
+            SectionConfig::Titled { title, .. } => title.clone(),
+        }
+    }
+
+    fn entries(&self) -> &[DeckEntry] {
+        match self {
+            SectionConfig::Slides(entries)
+            | SectionConfig::Titled {
+                slides: entries, ..
+            } => entries,
+        }
+    }
 }
 
 impl DeckConfig {
+    pub fn metadata(&self) -> DeckMetadata {
+        match self {
+            DeckConfig::Detailed(config) => config.metadata.clone(),
+            _ => DeckMetadata::default(),
+        }
+    }
+
+    pub fn video(&self) -> VideoConfig {
+        match self {
+            DeckConfig::Detailed(config) => config.video.clone(),
+            _ => VideoConfig::default(),
+        }
+    }
+
+    pub fn audio(&self) -> AudioConfig {
+        match self {
+            DeckConfig::Detailed(config) => config.audio.clone(),
+            _ => AudioConfig::default(),
+        }
+    }
+
+    pub fn timing(&self) -> TimingConfig {
+        match self {
+            DeckConfig::Detailed(config) => config.timing.clone(),
+            _ => TimingConfig::default(),
+        }
+    }
+
+    pub fn globals(&self) -> IndexMap<String, serde_json::Value> {
+        match self {
+            DeckConfig::Detailed(config) => config.globals.clone(),
+            _ => IndexMap::new(),
+        }
+    }
+
+    pub fn env(&self) -> Vec<String> {
+        match self {
+            DeckConfig::Detailed(config) => config.env.clone(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn load_slides(&self, templates_dir: &Path) -> Result<Vec<Slide>> {
-        let patterns_with_sections: Vec<(&str, Option<String>)> = match self {
-            DeckConfig::Flat(patterns) => patterns.iter().map(|p| (p.as_str(), None)).collect(),
-
-            DeckConfig::Sectioned(sections) => sections
-                .iter()
-                .flat_map(|(key, patterns)| {
-                    patterns
-                        .iter()
-                        .map(move |p| (p.as_str(), Some(key.clone())))
-                })
-                .collect(),
-        };
+        self.match_slides(&FileSource::new(templates_dir).list("")?)
+    }
 
+    /// Slide patterns of the deck, in order
+    pub fn patterns(&self) -> Vec<&str> {
+        let mut patterns = Vec::new();
+        match self {
+            DeckConfig::Detailed(config) => return config.slides.patterns(),
+            DeckConfig::Flat(entries) => collect_patterns(entries, &mut patterns),
+            DeckConfig::Sectioned(sections) => collect_section_patterns(sections, &mut patterns),
+        }
+        patterns
+    }
+
+    /// Slides of the deck among the given templates, named relative to the
+    /// templates directory
+    pub fn match_slides(&self, templates: &[String]) -> Result<Vec<Slide>> {
         let mut slides = Vec::new();
-        for (pattern, section_key) in patterns_with_sections {
-            slides.extend(Slide::load_collection(pattern, section_key, templates_dir)?);
+        let mut path = Vec::new();
+
+        match self {
+            DeckConfig::Detailed(config) => return config.slides.match_slides(templates),
+            DeckConfig::Flat(entries) => load_entries(entries, &mut path, templates, &mut slides)?,
+            DeckConfig::Sectioned(sections) => {
+                load_sections(sections, &mut path, templates, &mut slides)?
+            }
         }
 
         Ok(slides)
     }
 }
 
+fn collect_patterns<'a>(entries: &'a [DeckEntry], patterns: &mut Vec<&'a str>) {
+    for entry in entries {
+        match entry {
+            DeckEntry::Pattern(pattern) => patterns.push(pattern),
+            DeckEntry::Sections(sections) => collect_section_patterns(sections, patterns),
+        }
+    }
+}
+
+fn collect_section_patterns<'a>(
+    sections: &'a IndexMap<String, SectionConfig>,
+    patterns: &mut Vec<&'a str>,
+) {
+    for section in sections.values() {
+        collect_patterns(section.entries(), patterns);
+    }
+}
+
+fn load_entries(
+    entries: &[DeckEntry],
+    path: &mut Vec<SectionRef>,
+    templates: &[String],
+    slides: &mut Vec<Slide>,
+) -> Result<()> {
+    for entry in entries {
+        match entry {
+            DeckEntry::Pattern(pattern) => {
+                slides.extend(Slide::load_collection(pattern, path, templates)?);
+            }
+            DeckEntry::Sections(sections) => load_sections(sections, path, templates, slides)?,
+        }
+    }
+    Ok(())
+}
+
+fn load_sections(
+    sections: &IndexMap<String, SectionConfig>,
+    path: &mut Vec<SectionRef>,
+    templates: &[String],
+    slides: &mut Vec<Slide>,
+) -> Result<()> {
+    for (key, section) in sections {
+        path.push(SectionRef {
+            key: key.clone(),
+            title: section.title(),
+        });
+        load_entries(section.entries(), path, templates, slides)?;
+        path.pop();
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeckConfigCollection(IndexMap<String, DeckConfig>);
 
 impl DeckConfigCollection {
-    pub fn load(decks_path: &Path) -> Result<Self> {
-        if !decks_path.exists() {
-            return Err(Error::DecksNotFound);
-        }
-
-        let content = fs::read_to_string(decks_path).map_err(|e| Error::FileRead {
-            path: decks_path.to_path_buf(),
-            source: e,
-        })?;
+    /// Load `decks.yaml` from the root of a source
+    pub fn load_source(source: &dyn TemplateSource) -> Result<Self> {
+        let content = source.read(DECKS_FILE)?.ok_or(Error::DecksNotFound)?;
+        Self::parse(&content)
+    }
 
+    pub fn parse(content: &str) -> Result<Self> {
         let decks: IndexMap<String, DeckConfig> =
-            serde_yaml::from_str(&content).map_err(|e| Error::DecksParseError(e.to_string()))?;
+            serde_yaml::from_str(content).map_err(|e| Error::DecksParseError(e.to_string()))?;
 
         Ok(Self(decks))
     }
 
+    /// Deck names in file order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
     pub fn get(&self, name: &str) -> Result<DeckConfig> {
         self.0
             .get(name)
@@ -76,7 +456,18 @@ pub struct Deck {
     name: String,
     lang: String,
     root: PathBuf,
+    metadata: DeckMetadata,
+    video: VideoConfig,
+    audio: AudioConfig,
+    globals: IndexMap<String, serde_json::Value>,
+    env: Vec<String>,
     slides: Vec<Slide>,
+    thumbnails: bool,
+    retina: bool,
+    fragments: bool,
+    chrome_path: Option<PathBuf>,
+    progress: Reporter,
+    source: Arc<dyn TemplateSource>,
 }
 
 impl Deck {
@@ -85,20 +476,77 @@ impl Deck {
             name: name.to_string(),
             lang: lang.to_string(),
             root: root.to_path_buf(),
+            metadata: DeckMetadata::default(),
+            video: VideoConfig::default(),
+            audio: AudioConfig::default(),
+            globals: IndexMap::new(),
+            env: Vec::new(),
             slides: Vec::new(),
+            thumbnails: true,
+            retina: false,
+            fragments: false,
+            chrome_path: None,
+            progress: Reporter::default(),
+            source: Arc::new(FileSource::new(root)),
         }
     }
 
+    /// Read decks.yaml, templates and translations from `source` instead of
+    /// the project directory. Assets are still copied from the project.
+    pub fn with_source(mut self, source: Arc<dyn TemplateSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Report build, watch and server progress to `progress`
+    pub fn with_progress(mut self, progress: Reporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Enable or disable thumbnail generation in `build` (enabled by default)
+    pub fn with_thumbnails(mut self, thumbnails: bool) -> Self {
+        self.thumbnails = thumbnails;
+        self
+    }
+
+    /// Additionally render thumbnails at device scale factor 2, e.g. for sharper PDFs
+    pub fn with_retina(mut self, retina: bool) -> Self {
+        self.retina = retina;
+        self
+    }
+
+    /// Also capture a thumbnail of every fragment state, e.g. for PDFs with one page per step
+    pub fn with_fragments(mut self, fragments: bool) -> Self {
+        self.fragments = fragments;
+        self
+    }
+
+    /// Browser binary for thumbnails; defaults to `SLIDES_CHROME` or an installed browser
+    pub fn with_chrome_path(mut self, chrome_path: Option<PathBuf>) -> Self {
+        self.chrome_path = chrome_path;
+        self
+    }
+
+    /// Slides loaded by `load`, `build` or `render`
+    pub fn slides(&self) -> &[Slide] {
+        &self.slides
+    }
+
     fn decks_config(&self) -> PathBuf {
-        self.root.join("decks.yaml")
+        self.root.join(DECKS_FILE)
     }
 
     fn templates_dir(&self) -> PathBuf {
-        self.root.join("slides/templates")
+        self.root.join(TEMPLATES_DIR)
     }
 
     fn translations_dir(&self) -> PathBuf {
-        self.root.join("slides/translations")
+        self.root.join(TRANSLATIONS_DIR)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.root.join(DATA_DIR)
     }
 
     fn assets_dir(&self) -> PathBuf {
@@ -106,13 +554,24 @@ impl Deck {
     }
 
     fn output_dir(&self) -> PathBuf {
-        self.root.join("output")
+        self.root.join(OUTPUT_DIR).join(&self.lang)
     }
 
     pub fn load(&mut self) -> Result<()> {
-        self.slides = DeckConfigCollection::load(&self.decks_config())?
-            .get(&self.name)?
-            .load_slides(&self.templates_dir())?;
+        let config = DeckConfigCollection::load_source(self.source.as_ref())?.get(&self.name)?;
+        self.metadata = config.metadata();
+        self.video = config.video();
+        self.audio = config.audio();
+        self.globals = config.globals();
+        self.env = config.env();
+        self.slides = config.match_slides(&self.source.list(TEMPLATES_DIR)?)?;
+        let templates: Vec<&str> = self.slides.iter().map(|s| s.template.as_str()).collect();
+        let plan = config.timing().plan(&templates);
+        for (slide, planned) in self.slides.iter_mut().zip(plan) {
+            slide.audio = self.audio.slide_clip(&slide.template).map(str::to_string);
+            slide.auto_advance = self.audio.auto_advance && slide.audio.is_some();
+            slide.planned_duration = planned;
+        }
 
         if self.slides.is_empty() {
             return Err(Error::NoSlides(self.name.clone()));
@@ -121,62 +580,246 @@ impl Deck {
         Ok(())
     }
 
-    pub fn build(&mut self) -> Result<()> {
-        self.build_html()?;
-        chrome::generate_thumbnails(&self.output_dir(), self.slides.len())?;
-        println!("\n🎉 Done! Open output/slide-1.html in browser");
-        Ok(())
+    /// Build HTML and thumbnails. Without an installed browser, only the HTML is built.
+    pub fn build(&mut self) -> Result<BuildReport> {
+        let chrome = if self.thumbnails {
+            match chrome::find_chrome(self.chrome_path.as_deref()) {
+                Ok(chrome) => Some(chrome),
+                Err(Error::BrowserNotFound(install_hint)) => {
+                    warn!(
+                        "No Chrome, Chromium or Edge found - building HTML only, without thumbnails.\n\
+                        Use --chrome-path or {} to point to a browser, or --no-thumbnails \
+                        to skip this check.\n\n{install_hint}",
+                        chrome::CHROME_ENV
+                    );
+                    self.thumbnails = false;
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+
+        let mut report = self.build_html()?;
+        if let Some(chrome) = chrome {
+            let fragments: Vec<usize> = if self.fragments {
+                self.slides.iter().map(|slide| slide.fragments).collect()
+            } else {
+                Vec::new()
+            };
+            self.progress.report(ProgressEvent::ThumbnailsStarted);
+            let stats = chrome::generate_thumbnails(
+                &self.output_dir(),
+                self.slides.len(),
+                &chrome,
+                self.retina,
+                &fragments,
+                &|number| {
+                    self.progress
+                        .report(ProgressEvent::ThumbnailCaptured { number })
+                },
+            )?;
+            self.progress.report(ProgressEvent::ThumbnailsGenerated {
+                total: stats.total,
+                cached: stats.cached,
+            });
+            report.thumbnails = Some(stats);
+        }
+        Ok(report)
     }
 
     /// Build HTML only (no thumbnails) - useful for watch mode and testing
-    pub fn build_html(&mut self) -> Result<()> {
-        println!("🎬 Building presentation\n");
+    pub fn build_html(&mut self) -> Result<BuildReport> {
+        self.progress.report(ProgressEvent::BuildStarted {
+            deck: self.name.clone(),
+            lang: self.lang.clone(),
+        });
 
         let output_dir = self.output_dir();
+        let files = self.render()?;
 
-        print!("📄 Loading deck '{}'... ", self.name);
-        self.load()?;
-        println!("✅ {} slides", self.slides.len());
-
-        print!("🧱 Loading templates... ");
-        let layouts = render::load_layouts(&self.templates_dir())?;
-        println!("✅ {} templates", layouts.len());
-
-        let translations =
-            translations::load(&self.lang, &self.translations_dir()).unwrap_or_default();
-
-        print!("🔨 Rendering... ");
-        let pages = render::render_deck_pages(&self.slides, &layouts, &translations);
-        let overview = render::render_overview(&layouts, &translations, &self.slides);
-        let presenter_pages = render::render_presenter_pages(&layouts, &translations, &self.slides);
-        let print = render::render_print(&layouts, &translations, &self.slides);
-        println!("✅ {} pages", pages.len());
-
-        print!("💾 Writing files... ");
         fs::create_dir_all(&output_dir).map_err(|e| Error::CreateDir {
             path: output_dir.clone(),
             source: e,
         })?;
+        util::write_files(&output_dir, &files)?;
+        self.progress.report(ProgressEvent::FilesWritten);
 
-        util::write_pages(&output_dir, &pages)?;
-        util::write_pages(&output_dir.join("presenter"), &presenter_pages)?;
+        let assets_src = self.assets_dir();
+        if assets_src.exists() {
+            util::copy_dir_recursive(&assets_src, &output_dir.join("assets"))?;
+            self.progress.report(ProgressEvent::AssetsCopied);
+        }
 
+        Ok(BuildReport {
+            deck: self.name.clone(),
+            lang: self.lang.clone(),
+            slides: self.slides.len(),
+            files: files.len(),
+            output_dir,
+            thumbnails: None,
+        })
+    }
+
+    /// Render the deck without writing anything: pages, presenter and remote
+    /// pages, overview, print page and manifest, keyed by their path relative
+    /// to the output directory
+    pub fn render(&mut self) -> Result<BTreeMap<String, String>> {
+        self.load()?;
+        self.progress.report(ProgressEvent::SlidesLoaded {
+            deck: self.name.clone(),
+            count: self.slides.len(),
+        });
+
+        let layouts = render::load_source_layouts(self.source.as_ref(), TEMPLATES_DIR)?;
+        self.progress.report(ProgressEvent::TemplatesLoaded {
+            count: layouts.len(),
+        });
+
+        let translations =
+            translations::load_source(self.source.as_ref(), TRANSLATIONS_DIR, &self.lang)
+                .unwrap_or_default();
+        let locale = Locale::new(
+            &self.lang,
+            translations::available_locales(self.source.as_ref(), TRANSLATIONS_DIR)?,
+        );
+        let sections = render::section_tree(&self.slides, &translations);
+        let data_files = data::load_source(self.source.as_ref(), DATA_DIR)?;
+        let globals = self.render_globals(&translations, &sections);
+        let context = RenderContext::new(layouts, translations)
+            .with_locale(locale)
+            .with_globals(globals)
+            .with_data(data_files);
+        context.assign_slide_titles(&mut self.slides);
+
+        let pages = context.deck_pages(&self.slides);
+        for (index, slide) in self.slides.iter().enumerate() {
+            let number = index + 1;
+            if pages.contains_key(&format!("slide-{number}.html")) {
+                self.progress.report(ProgressEvent::SlideRendered {
+                    number,
+                    template: slide.template.clone(),
+                });
+            }
+        }
+        render::assign_fragment_counts(&mut self.slides, &pages);
+        let overview = context.overview(&self.slides, self.thumbnails);
+        let presenter_pages = context.presenter_pages(&self.slides);
+        let remote_pages = context.remote_pages(&self.slides);
+        let print = context.print(&self.slides);
+        self.progress
+            .report(ProgressEvent::PagesRendered { count: pages.len() });
+
+        let mut files: BTreeMap<String, String> = pages.into_iter().collect();
+        for (dir, pages) in [("presenter", presenter_pages), ("remote", remote_pages)] {
+            files.extend(
+                pages
+                    .into_iter()
+                    .map(|(name, html)| (format!("{dir}/{name}"), html)),
+            );
+        }
         for (name, html) in [("overview.html", overview), ("print.html", print)] {
             if let Some(content) = html {
-                let path = output_dir.join(name);
-                fs::write(&path, content).map_err(|e| Error::FileWrite { path, source: e })?;
+                files.insert(name.to_string(), content);
             }
         }
-        println!("✅");
 
-        let assets_src = self.assets_dir();
-        if assets_src.exists() {
-            print!("📦 Copying assets... ");
-            util::copy_dir_recursive(&assets_src, &output_dir.join("assets"))?;
-            println!("✅");
+        let manifest = Manifest::new(&self.name, &self.lang, &self.slides, sections)
+            .with_metadata(self.metadata.translated(&context.translations))
+            .with_video(self.video.clone());
+        files.insert(MANIFEST_FILE.to_string(), manifest.to_json()?);
+
+        Ok(files)
+    }
+
+    /// Variables every template sees: `deck`, `build`, `env` and the globals
+    /// of decks.yaml, which cannot replace these or `data`
+    fn render_globals(
+        &self,
+        translations: &HashMap<String, String>,
+        sections: &[Section],
+    ) -> render::Globals {
+        let git = self.source.root().and_then(git::read);
+        let env: serde_json::Map<String, serde_json::Value> = self
+            .env
+            .iter()
+            .filter_map(|name| Some((name.clone(), std::env::var(name).ok()?.into())))
+            .collect();
+
+        let mut globals = render::Globals::new();
+        for (name, value) in &self.globals {
+            if BUILTIN_GLOBALS.contains(&name.as_str()) {
+                warn!(
+                    "Global '{name}' of deck '{}' is reserved and ignored",
+                    self.name
+                );
+                continue;
+            }
+            globals.insert(name.clone(), value.clone());
         }
+        globals.insert(
+            "deck".to_string(),
+            serde_json::json!({
+                "name": self.name,
+                "lang": self.lang,
+                "metadata": self.metadata.translated(translations),
+                "sections": sections,
+            }),
+        );
+        globals.insert(
+            "build".to_string(),
+            serde_json::json!({
+                "timestamp": util::utc_now(),
+                "version": env!("CARGO_PKG_VERSION"),
+                "commit": git.as_ref().map(|git| git.commit.clone()),
+                "short_commit": git.as_ref().map(|git| git.short_commit().to_string()),
+                "branch": git.and_then(|git| git.branch),
+            }),
+        );
+        globals.insert("env".to_string(), env.into());
+        globals
+    }
 
-        Ok(())
+    /// Build, then serve the output directory on `address` with presenter and
+    /// audience sync. With `watch`, rebuild on changes while serving; with
+    /// `remote`, also serve the pin-protected remote control pages.
+    pub fn serve(&mut self, address: &str, watch: bool, remote: bool) -> Result<()> {
+        let session = self
+            .root
+            .join(SESSIONS_DIR)
+            .join(live::session_file_name(&self.name));
+        let mut server = DevServer::bind(&self.root.join(OUTPUT_DIR), address)?
+            .with_base_path(&format!("/{}/", self.lang))
+            .with_live_session(LiveSession::new(&self.name, Some(session.clone())));
+        if remote {
+            server = server.with_remote();
+        }
+        let url = server
+            .local_addr()
+            .map(
+                |addr| match (addr.ip().is_unspecified(), server::lan_ip()) {
+                    (true, Some(ip)) => format!("http://{}/", SocketAddr::new(ip, addr.port())),
+                    _ => format!("http://{addr}/"),
+                },
+            )
+            .unwrap_or_else(|| format!("http://{address}/"));
+        let server = server.with_progress(self.progress.clone());
+        let started = ProgressEvent::ServerStarted {
+            url,
+            remote_pin: server.remote().map(|remote| remote.pin()),
+            session: Some(session),
+        };
+
+        if watch {
+            self.progress.report(started);
+            std::thread::spawn(move || server.run());
+            return self.watch();
+        }
+
+        self.build()?;
+        self.progress.report(started);
+        server.run()
     }
 
     pub fn watch(&mut self) -> Result<()> {
@@ -184,24 +827,12 @@ impl Deck {
         use std::sync::mpsc::channel;
         use std::time::Duration;
 
-        println!("👀 Watch mode started");
-        println!();
-        println!("   Watching:");
-        println!("   • slides/");
-        println!("   • decks.yaml");
-        println!();
-        println!("   Press Ctrl+C to exit");
-        println!();
-        println!("─────────────────────────────────────────────────────");
-        println!();
-
         // Initial build with thumbnails
         if let Err(e) = self.build() {
-            eprintln!("❌ Error: {}", e);
+            self.progress.report(ProgressEvent::BuildFailed {
+                message: e.to_string(),
+            });
         }
-        println!();
-        println!("─────────────────────────────────────────────────────");
-        println!();
 
         // Setup file watcher
         let (tx, rx) = channel();
@@ -232,8 +863,14 @@ impl Deck {
             watch(&translations_dir, RecursiveMode::Recursive)?;
         }
 
-        println!("✅ Watching for changes...");
-        println!();
+        let data_dir = self.data_dir();
+        if data_dir.exists() {
+            watch(&data_dir, RecursiveMode::Recursive)?;
+        }
+
+        self.progress.report(ProgressEvent::WatchStarted {
+            paths: vec!["slides/".to_string(), "decks.yaml".to_string()],
+        });
 
         loop {
             let Ok(result) = rx.recv() else {
@@ -257,14 +894,17 @@ impl Deck {
                 if path.contains(".swp") || path.contains(".tmp") || path.contains("~") {
                     continue;
                 }
-                println!("📝 Change detected: {path}");
+                self.progress.report(ProgressEvent::ChangeDetected {
+                    path: path.to_string(),
+                });
             }
 
-            println!("\n🔨 Rebuilding...");
+            self.progress.report(ProgressEvent::Rebuilding);
             if let Err(e) = self.build_html() {
-                eprintln!("❌ Error: {e}");
+                self.progress.report(ProgressEvent::BuildFailed {
+                    message: e.to_string(),
+                });
             }
-            println!("\n─────────────────────────────────────────────────────\n");
         }
 
         Ok(())
@@ -0,0 +1,107 @@
+//! Manifest - machine-readable description of a built deck
+
+use super::{DeckMetadata, Section, Slide, VideoConfig};
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestSlide {
+    pub number: usize,
+    pub template: String,
+    pub title: String,
+    pub section: Option<String>,
+    /// Narration clip, relative to `slides/assets`
+    #[serde(default)]
+    pub audio: Option<String>,
+    /// Number of fragment steps
+    #[serde(default)]
+    pub fragments: usize,
+}
+
+/// Written to `output/<lang>/manifest.json` on every build so that later steps
+/// (e.g. PDF export) know slide titles and sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub deck: String,
+    pub lang: String,
+    #[serde(default)]
+    pub metadata: DeckMetadata,
+    #[serde(default)]
+    pub video: VideoConfig,
+    pub total: usize,
+    pub slides: Vec<ManifestSlide>,
+    pub sections: Vec<Section>,
+}
+
+impl Manifest {
+    pub fn new(deck: &str, lang: &str, slides: &[Slide], sections: Vec<Section>) -> Self {
+        let slides: Vec<ManifestSlide> = slides
+            .iter()
+            .enumerate()
+            .map(|(index, slide)| ManifestSlide {
+                number: index + 1,
+                template: slide.template.clone(),
+                title: slide.title.clone().unwrap_or_default(),
+                section: slide.section_key(),
+                audio: slide.audio.clone(),
+                fragments: slide.fragments,
+            })
+            .collect();
+
+        Self {
+            deck: deck.to_string(),
+            lang: lang.to_string(),
+            metadata: DeckMetadata::default(),
+            video: VideoConfig::default(),
+            total: slides.len(),
+            slides,
+            sections,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: DeckMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_video(mut self, video: VideoConfig) -> Self {
+        self.video = video;
+        self
+    }
+
+    pub fn load(output_dir: &Path) -> Result<Self> {
+        let path = output_dir.join(MANIFEST_FILE);
+        let content = fs::read_to_string(&path).map_err(|e| Error::FileRead {
+            path: path.clone(),
+            source: e,
+        })?;
+
+        serde_json::from_str(&content).map_err(|e| Error::ManifestParse {
+            path,
+            message: e.to_string(),
+        })
+    }
+
+    pub fn write(&self, output_dir: &Path) -> Result<()> {
+        let path = output_dir.join(MANIFEST_FILE);
+        let content = self.to_json()?;
+
+        fs::write(&path, content).map_err(|e| Error::FileWrite { path, source: e })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::ManifestSerialize(e.to_string()))
+    }
+
+    /// Slide titles in deck order
+    pub fn titles(&self) -> Vec<String> {
+        self.slides
+            .iter()
+            .map(|slide| slide.title.clone())
+            .collect()
+    }
+}
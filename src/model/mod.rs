@@ -1,8 +1,22 @@
 //! Domain models for the slides application
 
 mod deck;
+mod manifest;
 mod project;
+mod recipient;
+mod report;
+mod section;
 mod slide;
 
-pub use project::Project;
+pub use deck::{
+    AudioConfig, DeckConfig, DeckEntry, DeckMetadata, DetailedDeckConfig, Seconds, SectionConfig,
+    TimingConfig, VideoConfig,
+};
+pub use manifest::{Manifest, ManifestSlide, MANIFEST_FILE};
+pub use project::{A11yOptions, BuildOptions, PdfExport, Project, VideoExport};
+pub use recipient::Recipient;
+pub use report::{
+    A11yReport, BuildReport, EncryptionReport, ExportReport, ExportedFile, LintReport, SlideAudit,
+};
+pub use section::{Section, SectionRef};
 pub use slide::Slide;
@@ -1,45 +1,242 @@
 //! Project - the root aggregate representing a slides project
 
-use super::deck::Deck;
+use super::deck::{Deck, DeckConfigCollection, DECKS_FILE, OUTPUT_DIR, TEMPLATES_DIR};
+use super::{
+    A11yReport, BuildReport, EncryptionReport, ExportReport, ExportedFile, LintReport, Manifest,
+    ManifestSlide, Recipient, Section, Slide, SlideAudit, MANIFEST_FILE,
+};
 use crate::error::{Error, Result};
+use crate::infrastructure::chrome::{self, ThumbnailSize};
 use crate::infrastructure::pdf;
+use crate::infrastructure::source::{FileSource, TemplateSource};
+use crate::infrastructure::video::{self, VideoFormat, VideoFrame};
+use crate::progress::{Progress, ProgressEvent, Reporter};
+use crate::services::a11y;
 use crate::services::init;
+use crate::services::lint::{self, Issue};
+use crate::util::get_template_extension;
+use log::warn;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use zip::ZipArchive;
 
 const IMPORT_IMAGES_DIR: &str = "slides/assets/import/images";
-const THUMBNAILS_DIR: &str = "output/thumbnails";
+const ASSETS_DIR: &str = "slides/assets";
+const DEFAULT_RECIPIENT_WATERMARK: &str = "Confidential – prepared for {name}";
 
-/// A slides project with a root directory
+/// Settings of a build
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    pub lang: String,
+    /// Generate thumbnails; without an installed browser, only HTML is built
+    pub thumbnails: bool,
+    /// Also render thumbnails at device scale factor 2
+    pub retina: bool,
+    /// Also capture a thumbnail of every fragment state
+    pub fragments: bool,
+    /// Browser binary for thumbnails; defaults to `SLIDES_CHROME` or an installed browser
+    pub chrome_path: Option<PathBuf>,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            lang: "en".to_string(),
+            thumbnails: true,
+            retina: false,
+            fragments: false,
+            chrome_path: None,
+        }
+    }
+}
+
+impl BuildOptions {
+    pub fn with_lang(mut self, lang: &str) -> Self {
+        self.lang = lang.to_string();
+        self
+    }
+
+    pub fn with_thumbnails(mut self, thumbnails: bool) -> Self {
+        self.thumbnails = thumbnails;
+        self
+    }
+
+    pub fn with_retina(mut self, retina: bool) -> Self {
+        self.retina = retina;
+        self
+    }
+
+    pub fn with_fragments(mut self, fragments: bool) -> Self {
+        self.fragments = fragments;
+        self
+    }
+
+    pub fn with_chrome_path(mut self, chrome_path: Option<PathBuf>) -> Self {
+        self.chrome_path = chrome_path;
+        self
+    }
+}
+
+/// Settings of an accessibility check
+#[derive(Debug, Clone)]
+pub struct A11yOptions {
+    pub lang: String,
+    /// Measure text contrast in a browser, if one is installed
+    pub contrast: bool,
+    /// Browser binary for contrast; defaults to `SLIDES_CHROME` or an installed browser
+    pub chrome_path: Option<PathBuf>,
+}
+
+impl Default for A11yOptions {
+    fn default() -> Self {
+        Self {
+            lang: "en".to_string(),
+            contrast: true,
+            chrome_path: None,
+        }
+    }
+}
+
+impl A11yOptions {
+    pub fn with_lang(mut self, lang: &str) -> Self {
+        self.lang = lang.to_string();
+        self
+    }
+
+    pub fn with_contrast(mut self, contrast: bool) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    pub fn with_chrome_path(mut self, chrome_path: Option<PathBuf>) -> Self {
+        self.chrome_path = chrome_path;
+        self
+    }
+}
+
+/// Settings of a PDF export
+#[derive(Debug, Clone)]
+pub struct PdfExport {
+    /// Deck of the build to export, checked against its manifest
+    pub deck: String,
+    /// Language of the build to export, i.e. `output/<lang>/`
+    pub lang: String,
+    pub output_path: String,
+    pub options: pdf::PdfOptions,
+    pub protection: pdf::Protection,
+    /// Image paths are relative to the project root
+    pub watermark: Option<pdf::Watermark>,
+    /// Use the 2x thumbnails of a build with retina thumbnails
+    pub retina: bool,
+    /// One page per fragment state, from a build with fragment thumbnails
+    pub fragments: bool,
+    /// Write the used passwords as JSON to this file ("-" for stdout)
+    pub passwords_out: Option<String>,
+}
+
+impl Default for PdfExport {
+    fn default() -> Self {
+        Self {
+            deck: "default".to_string(),
+            lang: "en".to_string(),
+            output_path: String::new(),
+            options: pdf::PdfOptions::default(),
+            protection: pdf::Protection::default(),
+            watermark: None,
+            retina: false,
+            fragments: false,
+            passwords_out: None,
+        }
+    }
+}
+
+/// Settings of a video export
+#[derive(Debug, Clone)]
+pub struct VideoExport {
+    /// Deck of the build to export, checked against its manifest
+    pub deck: String,
+    /// Language of the build to export, i.e. `output/<lang>/`
+    pub lang: String,
+    pub format: VideoFormat,
+    pub output_path: String,
+    /// Seconds per slide, unless set in decks.yaml
+    pub duration: Option<f32>,
+    /// Slide timings recorded in presenter mode (`timings.json`), used
+    /// instead of configured durations
+    pub timings: Option<PathBuf>,
+}
+
+impl Default for VideoExport {
+    fn default() -> Self {
+        Self {
+            deck: "default".to_string(),
+            lang: "en".to_string(),
+            format: VideoFormat::default(),
+            output_path: String::new(),
+            duration: None,
+            timings: None,
+        }
+    }
+}
+
+/// A slides project with a root directory. Operations report progress to
+/// the handler set with `with_progress` and are silent otherwise.
 #[derive(Debug)]
 pub struct Project {
     root: PathBuf,
+    progress: Reporter,
+    source: Option<Arc<dyn TemplateSource>>,
 }
 
 impl Project {
+    /// Open the project in `root`
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref();
+        if !root.is_dir() {
+            return Err(Error::ProjectNotFound(root.to_path_buf()));
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            progress: Reporter::default(),
+            source: None,
+        })
+    }
+
+    /// A project whose decks.yaml, templates and translations come from
+    /// `source`, e.g. to render decks with `Deck::render` without a project
+    /// directory. Builds still write to `output/<lang>/` in the current directory.
+    pub fn from_source(source: impl TemplateSource + 'static) -> Self {
+        Self {
+            root: PathBuf::new(),
+            progress: Reporter::default(),
+            source: Some(Arc::new(source)),
+        }
+    }
+
     /// Open the current directory as a project
     pub fn current() -> Result<Self> {
-        let root = std::env::current_dir().map_err(Error::CurrentDir)?;
-        Ok(Self { root })
+        Self::open(std::env::current_dir().map_err(Error::CurrentDir)?)
     }
 
     /// Initialize a new project in the current directory
     pub fn init() -> Result<Self> {
         let root = std::env::current_dir().map_err(Error::CurrentDir)?;
+        Self::init_at(&root)
+    }
 
-        if !init::is_dir_empty(&root)? {
+    /// Initialize a new project in an existing, empty directory
+    pub fn init_at(root: &Path) -> Result<Self> {
+        if !init::is_dir_empty(root)? {
             return Err(Error::DirNotEmpty);
         }
 
-        println!("🚀 Initializing slides project...\n");
-
-        init::create_project_files(&root)?;
+        init::create_project_files(root)?;
 
-        println!("\n🎉 Project ready! Run 'slides build' to see your presentation.");
-
-        Ok(Self { root })
+        Self::open(root)
     }
 
     /// Create a new project in a new directory
@@ -57,33 +254,382 @@ impl Project {
             source: e,
         })?;
 
-        println!("📁 Creating project '{name}'...\n");
-        println!("🚀 Initializing slides project...\n");
-
         init::create_project_files(&project_dir)?;
 
-        println!("\n🎉 Project ready! Run 'slides build' to see your presentation.");
-        println!("\n💡 Run: cd {name} && slides build");
+        Self::open(project_dir)
+    }
+
+    /// Send progress events of this project's operations to `progress`
+    pub fn with_progress(mut self, progress: impl Progress + 'static) -> Self {
+        self.progress = Reporter::new(progress);
+        self
+    }
 
-        Ok(Self { root: project_dir })
+    /// Read decks.yaml, templates and translations from `source` instead of
+    /// the project directory
+    pub fn with_source(mut self, source: impl TemplateSource + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
     }
 
     /// Get a deck by name and language
     pub fn deck(&self, name: &str, lang: &str) -> Deck {
-        Deck::new(name, lang, &self.root)
+        let deck = Deck::new(name, lang, &self.root).with_progress(self.progress.clone());
+        match &self.source {
+            Some(source) => deck.with_source(Arc::clone(source)),
+            None => deck,
+        }
+    }
+
+    /// Build a deck with HTML pages and, unless disabled, thumbnails
+    pub fn build(&self, deck: &str, options: &BuildOptions) -> Result<BuildReport> {
+        self.deck(deck, &options.lang)
+            .with_thumbnails(options.thumbnails)
+            .with_retina(options.retina)
+            .with_fragments(options.fragments)
+            .with_chrome_path(options.chrome_path.clone())
+            .build()
+    }
+
+    /// Export the presentation as PDF. Generated passwords are written as JSON
+    /// to `export.passwords_out` ("-" for stdout) when given.
+    pub fn export_pdf(&self, export: &PdfExport) -> Result<ExportReport> {
+        let (pdf_bytes, slides) = self.generate_pdf(export)?;
+        let pdf_bytes = match &export.watermark {
+            Some(watermark) => {
+                self.progress.report(ProgressEvent::ApplyingWatermark);
+                let stamped = pdf::apply_watermark(pdf_bytes, &self.resolve_watermark(watermark))?;
+                pdf::apply_compression(stamped, export.options.compression)?
+            }
+            None => pdf_bytes,
+        };
+
+        let mut report = ExportReport {
+            slides,
+            ..Default::default()
+        };
+        let final_pdf = if export.protection.is_requested() {
+            let encryption = export.protection.resolve();
+            self.progress.report(ProgressEvent::Encrypting {
+                algorithm: encryption.algorithm.to_string(),
+            });
+            let encrypted = pdf::encrypt(pdf_bytes, &encryption)?;
+
+            if let Some(target) = &export.passwords_out {
+                let entry = password_entry(&export.output_path, None, &encryption);
+                self.write_passwords(target, &entry)?;
+                report.passwords = Some(entry);
+            }
+            report
+                .files
+                .push(exported_file(&export.output_path, None, Some(&encryption)));
+            encrypted
+        } else {
+            report
+                .files
+                .push(exported_file(&export.output_path, None, None));
+            pdf_bytes
+        };
+
+        self.write_pdf(&export.output_path, final_pdf)?;
+
+        Ok(report)
+    }
+
+    /// Export one watermarked, password protected PDF per recipient of a CSV file.
+    /// The watermark text may use `{column}` placeholders such as `{name}`.
+    pub fn export_pdf_for_recipients(
+        &self,
+        export: &PdfExport,
+        recipients_csv: &str,
+    ) -> Result<ExportReport> {
+        let recipients_path = self.root.join(recipients_csv);
+        let recipients = Recipient::load_csv(&recipients_path)?;
+        let output_paths =
+            recipient_output_paths(&export.output_path, &recipients).map_err(|message| {
+                Error::RecipientsParse {
+                    path: recipients_path.clone(),
+                    message,
+                }
+            })?;
+        let encryptions = recipients
+            .iter()
+            .map(|recipient| {
+                let protection = pdf::Protection {
+                    user_password: Some(
+                        recipient
+                            .get("password")
+                            .unwrap_or(pdf::AUTO_PASSWORD)
+                            .to_string(),
+                    ),
+                    ..export.protection.clone()
+                };
+                let encryption = protection.resolve();
+                if !encryption.has_distinct_passwords() {
+                    return Err(Error::RecipientsParse {
+                        path: recipients_path.clone(),
+                        message: format!(
+                            "the password of '{}' is the owner password",
+                            recipient.name()
+                        ),
+                    });
+                }
+                Ok(encryption)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.progress.report(ProgressEvent::RecipientsLoaded {
+            count: recipients.len(),
+        });
+
+        let (pdf_bytes, slides) = self.generate_pdf(export)?;
+        let watermark = self.resolve_watermark(
+            export
+                .watermark
+                .as_ref()
+                .unwrap_or(&pdf::Watermark::text(DEFAULT_RECIPIENT_WATERMARK)),
+        );
+
+        let mut report = ExportReport {
+            slides,
+            ..Default::default()
+        };
+        let mut entries = Vec::new();
+        for ((recipient, output_path), encryption) in
+            recipients.iter().zip(output_paths).zip(encryptions)
+        {
+            let mut watermark = watermark.clone();
+            if let pdf::WatermarkContent::Text(text) = &watermark.content {
+                watermark.content = pdf::WatermarkContent::Text(recipient.fill(text));
+            }
+            let stamped = pdf::apply_watermark(pdf_bytes.clone(), &watermark)?;
+            let stamped = pdf::apply_compression(stamped, export.options.compression)?;
+
+            let encrypted = pdf::encrypt(stamped, &encryption)?;
+
+            self.write_pdf(&output_path, encrypted)?;
+            report.files.push(exported_file(
+                &output_path,
+                Some(recipient),
+                Some(&encryption),
+            ));
+            entries.push(password_entry(&output_path, Some(recipient), &encryption));
+        }
+
+        if let Some(target) = &export.passwords_out {
+            let passwords = serde_json::Value::Array(entries);
+            self.write_passwords(target, &passwords)?;
+            report.passwords = Some(passwords);
+        }
+
+        Ok(report)
+    }
+
+    /// Generate the PDF from the thumbnails of the last build, with its slide count
+    fn generate_pdf(&self, export: &PdfExport) -> Result<(Vec<u8>, usize)> {
+        let output_dir = self.output_dir(&export.lang);
+        let thumbnails_dir = if export.retina {
+            output_dir.join("thumbnails").join("2x")
+        } else {
+            output_dir.join("thumbnails")
+        };
+        let slide_count = self.count_thumbnails(&thumbnails_dir)?;
+        let manifest = self.build_manifest(&output_dir, &export.deck, &export.lang, slide_count)?;
+        let mut info = document_info(manifest.as_ref());
+        let pdf_bytes = match manifest.as_ref().filter(|_| export.fragments) {
+            Some(manifest) => {
+                let pages = fragment_pages(&thumbnails_dir, manifest, slide_count);
+                let mut first_pages = Vec::new();
+                for (index, (number, _)) in pages.iter().enumerate() {
+                    if first_pages.len() < *number {
+                        first_pages.push(index + 1);
+                    }
+                }
+                info.page_labels = pages.iter().map(|(number, _)| *number).collect();
+                remap_outline(&mut info.outline, &first_pages);
+
+                let pages: Vec<pdf::PdfPage> = pages.into_iter().map(|(_, page)| page).collect();
+                pdf::generate_from_images(&pages, &export.options)?
+            }
+            None => {
+                let titles = manifest
+                    .as_ref()
+                    .map(|manifest| manifest.titles())
+                    .unwrap_or_default();
+                pdf::generate_from_thumbnails(
+                    &thumbnails_dir,
+                    slide_count,
+                    &titles,
+                    &export.options,
+                )?
+            }
+        };
+        let pdf_bytes = pdf::apply_document_info(pdf_bytes, &info)?;
+        self.progress.report(ProgressEvent::PdfGenerated {
+            bytes: pdf_bytes.len(),
+        });
+
+        Ok((pdf_bytes, slide_count))
+    }
+
+    /// Export the presentation as MP4 video, narrated MP4 or animated GIF from
+    /// the thumbnails of the last build. Slides are shown for their recorded
+    /// timing, their duration in decks.yaml, else `duration` seconds; in narrated
+    /// videos slides with a clip are shown while it plays. Without ffmpeg, MP4
+    /// falls back to GIF.
+    pub fn export_video(&self, export: &VideoExport) -> Result<ExportReport> {
+        let output_dir = self.output_dir(&export.lang);
+        let thumbnails_dir = output_dir.join("thumbnails");
+        let slide_count = self.count_thumbnails(&thumbnails_dir)?;
+        let manifest = self.build_manifest(&output_dir, &export.deck, &export.lang, slide_count)?;
+        let video = manifest
+            .as_ref()
+            .map(|manifest| manifest.video.clone())
+            .unwrap_or_default();
+        let timings = match &export.timings {
+            Some(path) => load_timings(&self.root.join(path))?,
+            None => BTreeMap::new(),
+        };
+
+        let ffmpeg = match export.format {
+            VideoFormat::Mp4 => {
+                let ffmpeg = video::find_ffmpeg();
+                if ffmpeg.is_none() {
+                    warn!(
+                        "ffmpeg not found - exporting an animated GIF instead. \
+                        Install ffmpeg or set {} to export MP4.",
+                        video::FFMPEG_ENV
+                    );
+                }
+                ffmpeg
+            }
+            VideoFormat::Narrated => Some(video::find_ffmpeg().ok_or_else(|| {
+                Error::VideoExport(format!(
+                    "Narrated videos need ffmpeg. Install ffmpeg or set {}.",
+                    video::FFMPEG_ENV
+                ))
+            })?),
+            VideoFormat::Gif => None,
+        };
+
+        let frames: Vec<VideoFrame> = (1..=slide_count)
+            .map(|number| {
+                let slide = manifest
+                    .as_ref()
+                    .and_then(|manifest| manifest.slides.get(number - 1));
+                let template = slide
+                    .map(|slide| slide.template.as_str())
+                    .unwrap_or_default();
+                // GIFs use the smaller preview thumbnails when available
+                let medium = thumbnails_dir.join(ThumbnailSize::Medium.file_name(number));
+                let image = if ffmpeg.is_none() && medium.exists() {
+                    medium
+                } else {
+                    thumbnails_dir.join(ThumbnailSize::Full.file_name(number))
+                };
+
+                VideoFrame {
+                    image,
+                    duration: timings
+                        .get(&number)
+                        .copied()
+                        .or_else(|| video.slide_duration(template))
+                        .or(export.duration)
+                        .or(video.duration)
+                        .unwrap_or(video::DEFAULT_DURATION),
+                    audio: slide
+                        .and_then(|slide| slide.audio.as_ref())
+                        .filter(|_| export.format == VideoFormat::Narrated)
+                        .map(|clip| self.root.join(ASSETS_DIR).join(clip)),
+                }
+            })
+            .collect();
+
+        let output_path = match ffmpeg {
+            Some(_) => export.output_path.clone(),
+            None => Path::new(&export.output_path)
+                .with_extension(VideoFormat::Gif.extension())
+                .to_string_lossy()
+                .into_owned(),
+        };
+        let output = self.root.join(&output_path);
+
+        let format = match ffmpeg {
+            Some(_) => export.format,
+            None => VideoFormat::Gif,
+        };
+        self.progress.report(ProgressEvent::EncodingVideo {
+            format,
+            encoder: ffmpeg.clone(),
+            clips: frames.iter().filter(|frame| frame.audio.is_some()).count(),
+        });
+        match &ffmpeg {
+            Some(ffmpeg) if format == VideoFormat::Narrated => {
+                video::encode_narrated(ffmpeg, &frames, &output, (1920, 1080))?
+            }
+            Some(ffmpeg) => video::encode_mp4(ffmpeg, &frames, &output, (1920, 1080))?,
+            None => video::encode_gif(&frames, &output)?,
+        }
+
+        Ok(ExportReport {
+            files: vec![exported_file(&output_path, None, None)],
+            slides: slide_count,
+            // Narrated slides last as long as their clips
+            duration: (format != VideoFormat::Narrated).then(|| total_duration(&frames)),
+            passwords: None,
+        })
+    }
+
+    fn resolve_watermark(&self, watermark: &pdf::Watermark) -> pdf::Watermark {
+        let mut watermark = watermark.clone();
+        if let pdf::WatermarkContent::Image(path) = &watermark.content {
+            watermark.content = pdf::WatermarkContent::Image(self.root.join(path));
+        }
+        watermark
+    }
+
+    /// Build directory of a language, e.g. `output/de`
+    fn output_dir(&self, lang: &str) -> PathBuf {
+        self.root.join(OUTPUT_DIR).join(lang)
     }
 
-    /// Export the presentation as PDF
-    pub fn export_pdf(
+    /// Manifest of the build in `output_dir`, which must be of the exported deck
+    /// and language and have a thumbnail per slide. None for builds without one.
+    fn build_manifest(
         &self,
-        password: Option<String>,
-        no_print: bool,
-        no_copy: bool,
-        output_path: &str,
-    ) -> Result<()> {
-        println!("📄 PDF Export\n");
-
-        let thumbnails_dir = self.root.join(THUMBNAILS_DIR);
+        output_dir: &Path,
+        deck: &str,
+        lang: &str,
+        slide_count: usize,
+    ) -> Result<Option<Manifest>> {
+        if !output_dir.join(MANIFEST_FILE).exists() {
+            return Ok(None);
+        }
+        let manifest = Manifest::load(output_dir)?;
+
+        let message = if manifest.deck != deck {
+            format!("is of deck '{}', not '{deck}'", manifest.deck)
+        } else if manifest.lang != lang {
+            format!("is in '{}', not '{lang}'", manifest.lang)
+        } else if manifest.total != slide_count {
+            format!("has {} slides but {slide_count} thumbnails", manifest.total)
+        } else {
+            return Ok(Some(manifest));
+        };
+        Err(Error::StaleBuild {
+            path: output_dir
+                .strip_prefix(&self.root)
+                .unwrap_or(output_dir)
+                .to_path_buf(),
+            message,
+        })
+    }
+
+    /// Number of consecutive `slide-N.png` thumbnails in a directory
+    fn count_thumbnails(&self, thumbnails_dir: &Path) -> Result<usize> {
         if !thumbnails_dir.exists() {
             return Err(Error::ThumbnailsNotFound);
         }
@@ -93,44 +639,44 @@ impl Project {
             .count();
 
         if slide_count == 0 {
-            return Err(Error::NoThumbnails);
+            let relative = thumbnails_dir
+                .strip_prefix(&self.root)
+                .unwrap_or(thumbnails_dir);
+            return Err(Error::NoThumbnails(relative.to_path_buf()));
         }
 
-        println!("📸 {slide_count} thumbnails found");
-        let pdf_bytes = pdf::generate_from_thumbnails(&thumbnails_dir, slide_count)?;
-        println!("   ✅ PDF generated ({} KB)\n", pdf_bytes.len() / 1024);
+        self.progress
+            .report(ProgressEvent::ThumbnailsFound { count: slide_count });
+        Ok(slide_count)
+    }
 
-        let final_pdf = match &password {
-            Some(pwd) => {
-                let pwd = if pwd == "auto" {
-                    pdf::generate_secure_password()
-                } else {
-                    pwd.clone()
-                };
-                println!("🔒 Encrypting...");
-                let (encrypted, ok) = pdf::encrypt(pdf_bytes, &pwd, no_print, no_copy)?;
-                if ok {
-                    println!("   ✅ Encrypted (password: {pwd})");
-                }
-                encrypted
-            }
-            None => pdf_bytes,
-        };
+    /// Write the passwords of encrypted PDFs as JSON to a file; "-" leaves
+    /// them to the caller via the export report
+    fn write_passwords(&self, target: &str, json: &serde_json::Value) -> Result<()> {
+        if target == "-" {
+            return Ok(());
+        }
+
+        let content = serde_json::to_string_pretty(json).unwrap_or_default();
+        let path = self.root.join(target);
+        fs::write(&path, content + "\n").map_err(|e| Error::FileWrite { path, source: e })?;
+        self.progress.report(ProgressEvent::PasswordsWritten {
+            path: target.to_string(),
+        });
+        Ok(())
+    }
 
+    fn write_pdf(&self, output_path: &str, pdf_bytes: Vec<u8>) -> Result<()> {
         let output = self.root.join(output_path);
-        fs::write(&output, final_pdf).map_err(|e| Error::FileWrite {
+        fs::write(&output, pdf_bytes).map_err(|e| Error::FileWrite {
             path: output,
             source: e,
-        })?;
-        println!("\n🎉 PDF saved to {output_path}");
-
-        Ok(())
+        })
     }
 
-    /// Import images from a PowerPoint file
-    pub fn import_images(&self, pptx_file: &str) -> Result<()> {
-        println!("📥 Extracting images from {pptx_file}");
-
+    /// Import images from a PowerPoint file into `slides/assets/import/images`,
+    /// returning how many were found
+    pub fn import_images(&self, pptx_file: &str) -> Result<usize> {
         let pptx_path = PathBuf::from(pptx_file);
         let file = File::open(&pptx_path).map_err(|e| Error::FileRead {
             path: pptx_path.clone(),
@@ -144,16 +690,444 @@ impl Project {
             source: e,
         })?;
 
-        let count = extract_pptx_images(&mut archive, &import_dir)?;
+        extract_pptx_images(&mut archive, &import_dir)
+    }
+
+    /// Check decks.yaml, the templates and the rendered pages of a deck, or
+    /// of all decks. Unused templates are only reported for all decks.
+    pub fn lint(&self, deck: Option<&str>, lang: &str) -> Result<LintReport> {
+        let source = self.template_source();
+        let content = source.read(DECKS_FILE)?.ok_or(Error::DecksNotFound)?;
 
-        if count > 0 {
-            println!("   ✅ {count} images → {IMPORT_IMAGES_DIR}");
+        let mut report = LintReport {
+            issues: lint::check_decks_file(DECKS_FILE, &content),
+            ..Default::default()
+        };
+        if report.errors() > 0 {
+            return Ok(report);
+        }
+
+        let collection = DeckConfigCollection::parse(&content)?;
+        report.decks = match deck {
+            Some(name) => {
+                collection.get(name)?;
+                vec![name.to_string()]
+            }
+            None => collection.names().map(str::to_string).collect(),
+        };
+
+        let files = source.list(TEMPLATES_DIR)?;
+        let mut templates = Vec::new();
+        for file in files.iter().filter(|f| get_template_extension(f).is_some()) {
+            let content = source
+                .read(&format!("{TEMPLATES_DIR}/{file}"))?
+                .unwrap_or_default();
+            templates.push((file.clone(), content));
+        }
+
+        let assets: HashSet<String> = source.list(ASSETS_DIR)?.into_iter().collect();
+        let mut slides = BTreeSet::new();
+        for name in &report.decks {
+            let config = collection.get(name)?;
+            for pattern in config.patterns() {
+                if Slide::matching_templates(pattern, &files).is_empty() {
+                    report.issues.push(Issue::warning(
+                        "unmatched-pattern",
+                        DECKS_FILE,
+                        lint::line_of(&content, pattern),
+                        format!("Pattern '{pattern}' of deck '{name}' matches no templates"),
+                    ));
+                }
+            }
+
+            let (deck_slides, issues) = self.lint_rendered(name, lang, &content);
+            report.issues.extend(issues);
+            slides.extend(deck_slides.into_iter().map(|slide| slide.template));
+        }
+
+        for (name, content) in &templates {
+            let file = format!("{TEMPLATES_DIR}/{name}");
+            if slides.contains(name) && !lint::has_block(content, "notes") {
+                report.issues.push(Issue::warning(
+                    "missing-notes",
+                    &file,
+                    None,
+                    "Slide has no `notes` block".to_string(),
+                ));
+            }
+            report
+                .issues
+                .extend(self.lint_assets(&assets, &file, content));
+            for line in lint::images_without_alt(content) {
+                report.issues.push(Issue::warning(
+                    "missing-alt",
+                    &file,
+                    Some(line),
+                    "Image without alt text".to_string(),
+                ));
+            }
+        }
+
+        if deck.is_none() {
+            for name in lint::unused_templates(&templates, &slides) {
+                report.issues.push(Issue::warning(
+                    "unused-template",
+                    &format!("{TEMPLATES_DIR}/{name}"),
+                    None,
+                    "Template is not used by any deck or template".to_string(),
+                ));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Build the HTML of a deck and check every slide page for accessibility
+    /// problems. Contrast is measured in a browser; without one, it is skipped.
+    pub fn check_a11y(&self, deck: &str, options: &A11yOptions) -> Result<A11yReport> {
+        let mut deck = self.deck(deck, &options.lang);
+        let build = deck.build_html()?;
+
+        let chrome = if options.contrast {
+            match chrome::find_chrome(options.chrome_path.as_deref()) {
+                Ok(chrome) => Some(chrome),
+                Err(Error::BrowserNotFound(install_hint)) => {
+                    warn!(
+                        "No Chrome, Chromium or Edge found - skipping the contrast check.\n\
+                        Use --chrome-path or {} to point to a browser.\n\n{install_hint}",
+                        chrome::CHROME_ENV
+                    );
+                    None
+                }
+                Err(e) => return Err(e),
+            }
         } else {
-            println!("   No images found");
+            None
+        };
+        let contrast = match &chrome {
+            Some(chrome) => {
+                chrome::measure_contrast(&build.output_dir, deck.slides().len(), chrome)?
+            }
+            None => Vec::new(),
+        };
+
+        let mut slides = Vec::new();
+        for (index, slide) in deck.slides().iter().enumerate() {
+            let number = index + 1;
+            let name = format!("slide-{number}.html");
+            let path = build.output_dir.join(&name);
+            let html =
+                fs::read_to_string(&path).map_err(|e| Error::FileRead { path, source: e })?;
+
+            let file = format!("{OUTPUT_DIR}/{}/{name}", options.lang);
+            let mut issues = a11y::check_page(&file, &html, &options.lang);
+            for low in contrast.get(index).into_iter().flatten() {
+                issues.push(Issue::error(
+                    "contrast",
+                    &file,
+                    None,
+                    format!(
+                        "<{}> \"{}\" has a contrast of {}:1, needs {}:1",
+                        low.tag, low.text, low.ratio, low.required
+                    ),
+                ));
+            }
+
+            slides.push(SlideAudit {
+                number,
+                template: slide.template.clone(),
+                issues,
+            });
         }
 
-        Ok(())
+        Ok(A11yReport {
+            deck: build.deck,
+            lang: build.lang,
+            contrast: chrome.is_some(),
+            slides,
+        })
     }
+
+    /// Render a deck without writing files and check the HTML of its pages,
+    /// returning its slides and the issues found
+    fn lint_rendered(&self, name: &str, lang: &str, decks: &str) -> (Vec<Slide>, Vec<Issue>) {
+        let mut deck = Deck::new(name, lang, &self.root).with_source(self.template_source());
+        let pages = match deck.render() {
+            Ok(pages) => pages,
+            Err(e) => {
+                let issue = Issue::error(
+                    "render",
+                    DECKS_FILE,
+                    lint::line_of(decks, name),
+                    format!("Deck '{name}' could not be rendered: {e}"),
+                );
+                return (deck.slides().to_vec(), vec![issue]);
+            }
+        };
+
+        let mut issues = Vec::new();
+        for (index, slide) in deck.slides().iter().enumerate() {
+            if !pages.contains_key(&format!("slide-{}.html", index + 1)) {
+                issues.push(Issue::error(
+                    "render",
+                    &format!("{TEMPLATES_DIR}/{}", slide.template),
+                    None,
+                    format!("Slide {} of deck '{name}' could not be rendered", index + 1),
+                ));
+            }
+        }
+
+        // Slides and overview; the print page repeats the slides
+        for (file, html) in &pages {
+            if file.ends_with(".html") && !file.contains('/') && file != "print.html" {
+                issues.extend(lint::check_html(
+                    &format!("{OUTPUT_DIR}/{lang}/{file}"),
+                    html,
+                ));
+            }
+        }
+        (deck.slides().to_vec(), issues)
+    }
+
+    /// Literal `asset()` paths that do not exist in `slides/assets`
+    fn lint_assets(&self, assets: &HashSet<String>, file: &str, content: &str) -> Vec<Issue> {
+        lint::asset_references(content)
+            .into_iter()
+            .filter(|(_, path)| {
+                let path = path.split(['?', '#']).next().unwrap_or_default();
+                // Zip sources leave out binary files such as images
+                !assets.contains(path) && !self.root.join(ASSETS_DIR).join(path).is_file()
+            })
+            .map(|(line, path)| {
+                Issue::error(
+                    "broken-asset",
+                    file,
+                    Some(line),
+                    format!("Asset '{path}' not found in {ASSETS_DIR}"),
+                )
+            })
+            .collect()
+    }
+
+    fn template_source(&self) -> Arc<dyn TemplateSource> {
+        match &self.source {
+            Some(source) => Arc::clone(source),
+            None => Arc::new(FileSource::new(&self.root)),
+        }
+    }
+}
+
+/// Seconds the frames are shown in total
+fn total_duration(frames: &[VideoFrame]) -> f32 {
+    frames.iter().map(|frame| frame.duration).sum()
+}
+
+/// Load presenter timings: seconds spent per slide number, e.g. `{"1": 12.5}`
+fn load_timings(path: &Path) -> Result<BTreeMap<usize, f32>> {
+    let content = fs::read_to_string(path).map_err(|e| Error::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| Error::VideoExport(format!("Invalid timings file {}: {e}", path.display())))
+}
+
+/// Output file of every recipient: the `file` column below the directory of
+/// `output_path`, else `<stem>-<slug>.pdf` next to it. Fails for names that
+/// give no file name, `file` values leaving the directory and recipients
+/// that would share a file.
+fn recipient_output_paths(
+    output_path: &str,
+    recipients: &[Recipient],
+) -> std::result::Result<Vec<String>, String> {
+    let path = Path::new(output_path);
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("presentation");
+
+    let mut taken: HashMap<String, &str> = HashMap::new();
+    let mut paths = Vec::new();
+    for recipient in recipients {
+        let name = recipient.name();
+        let file = match recipient.get("file") {
+            Some(file) => {
+                let relative = Path::new(file);
+                if !relative
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_)))
+                {
+                    return Err(format!(
+                        "file '{file}' of '{name}' must be a relative path inside the output directory"
+                    ));
+                }
+                dir.join(relative)
+            }
+            None => match recipient.slug() {
+                slug if slug.is_empty() => {
+                    return Err(format!(
+                        "name '{name}' gives no file name; add a 'file' column"
+                    ))
+                }
+                slug => dir.join(format!("{stem}-{slug}.pdf")),
+            },
+        };
+
+        let file = file.to_string_lossy().into_owned();
+        if let Some(other) = taken.insert(file.to_lowercase(), name) {
+            return Err(format!(
+                "'{other}' and '{name}' would both be written to {file}; add a 'file' column"
+            ));
+        }
+        paths.push(file);
+    }
+    Ok(paths)
+}
+
+fn exported_file(
+    path: &str,
+    recipient: Option<&Recipient>,
+    encryption: Option<&pdf::Encryption>,
+) -> ExportedFile {
+    ExportedFile {
+        path: path.to_string(),
+        recipient: recipient.map(|recipient| recipient.name().to_string()),
+        encryption: encryption.map(|encryption| EncryptionReport {
+            algorithm: encryption.algorithm.to_string(),
+            owner_password: encryption.owner_password.clone(),
+            user_password: encryption.user_password.clone(),
+        }),
+    }
+}
+
+fn password_entry(
+    output_path: &str,
+    recipient: Option<&Recipient>,
+    encryption: &pdf::Encryption,
+) -> serde_json::Value {
+    let mut entry = serde_json::json!({
+        "file": output_path,
+        "encryption": encryption.algorithm.to_string(),
+        "owner_password": encryption.owner_password,
+        "user_password": encryption.user_password,
+    });
+    if let Some(recipient) = recipient {
+        entry["recipient"] = recipient.name().into();
+    }
+    entry
+}
+
+fn document_info(manifest: Option<&Manifest>) -> pdf::DocumentInfo {
+    let Some(manifest) = manifest else {
+        return pdf::DocumentInfo {
+            title: "Presentation".to_string(),
+            ..Default::default()
+        };
+    };
+
+    let metadata = &manifest.metadata;
+    pdf::DocumentInfo {
+        title: metadata
+            .title
+            .clone()
+            .unwrap_or_else(|| manifest.deck.clone()),
+        author: metadata.author.clone(),
+        subject: metadata.subject.clone(),
+        keywords: metadata.keywords.clone(),
+        creation_date: metadata.date.clone(),
+        outline: outline_items(&manifest.sections, &manifest.slides, 1, manifest.total),
+        page_labels: Vec::new(),
+    }
+}
+
+/// Pages of a PDF with every fragment state: for each slide its fragment
+/// thumbnails, then the fully revealed slide, paired with the slide number
+fn fragment_pages(
+    thumbnails_dir: &Path,
+    manifest: &Manifest,
+    slide_count: usize,
+) -> Vec<(usize, pdf::PdfPage)> {
+    let titles = manifest.titles();
+    let mut pages = Vec::new();
+
+    for number in 1..=slide_count {
+        let mut bookmark = Some(
+            titles
+                .get(number - 1)
+                .filter(|title| !title.is_empty())
+                .cloned()
+                .unwrap_or_else(|| format!("Slide {number}")),
+        );
+        let fragments = manifest
+            .slides
+            .get(number - 1)
+            .map_or(0, |slide| slide.fragments);
+
+        for step in 0..fragments {
+            let page = pdf::PdfPage {
+                image: thumbnails_dir.join(chrome::fragment_file_name(number, step)),
+                bookmark: bookmark.take(),
+            };
+            pages.push((number, page));
+        }
+        let page = pdf::PdfPage {
+            image: thumbnails_dir.join(ThumbnailSize::Full.file_name(number)),
+            bookmark,
+        };
+        pages.push((number, page));
+    }
+
+    pages
+}
+
+/// Point outline items at the first page of their slide
+fn remap_outline(items: &mut [pdf::OutlineItem], first_pages: &[usize]) {
+    for item in items {
+        if let Some(page) = first_pages.get(item.page - 1) {
+            item.page = *page;
+        }
+        remap_outline(&mut item.children, first_pages);
+    }
+}
+
+/// Outline for the slides `first..=last`: sections become parent bookmarks,
+/// every slide gets its own bookmark
+fn outline_items(
+    sections: &[Section],
+    slides: &[ManifestSlide],
+    first: usize,
+    last: usize,
+) -> Vec<pdf::OutlineItem> {
+    let mut items = Vec::new();
+    let mut number = first;
+
+    while number <= last {
+        if let Some(section) = sections.iter().find(|s| s.first == number) {
+            items.push(pdf::OutlineItem {
+                title: section.title.clone(),
+                page: section.first,
+                children: outline_items(&section.sections, slides, section.first, section.last),
+            });
+            number = section.last + 1;
+            continue;
+        }
+
+        let title = slides
+            .get(number - 1)
+            .map(|slide| slide.title.clone())
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| format!("Slide {number}"));
+        items.push(pdf::OutlineItem {
+            title,
+            page: number,
+            children: Vec::new(),
+        });
+        number += 1;
+    }
+
+    items
 }
 
 fn extract_pptx_images(archive: &mut ZipArchive<File>, output_dir: &Path) -> Result<usize> {
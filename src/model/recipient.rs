@@ -0,0 +1,102 @@
+//! Recipient - a person receiving a personalised PDF export
+
+use crate::error::{Error, Result};
+use crate::util;
+use indexmap::IndexMap;
+use std::fs;
+use std::path::Path;
+
+/// One row of a recipients CSV file. Column names are lowercased;
+/// `name` is required, `password` and `file` are optional.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recipient {
+    pub fields: IndexMap<String, String>,
+}
+
+impl Recipient {
+    /// Load recipients from a CSV file with a header row
+    pub fn load_csv(path: &Path) -> Result<Vec<Self>> {
+        let content = fs::read_to_string(path).map_err(|e| Error::FileRead {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        Self::parse_csv(&content).map_err(|message| Error::RecipientsParse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn parse_csv(content: &str) -> std::result::Result<Vec<Self>, String> {
+        let mut rows = util::csv_records(content)?.into_iter();
+
+        let header: Vec<String> = rows
+            .next()
+            .ok_or("missing header row")?
+            .into_iter()
+            .map(|column| column.trim().to_lowercase())
+            .collect();
+        if !header.iter().any(|column| column == "name") {
+            return Err("missing 'name' column".to_string());
+        }
+
+        let mut recipients = Vec::new();
+        for (line, row) in rows.enumerate() {
+            if row.iter().all(|value| value.trim().is_empty()) {
+                continue;
+            }
+            if row.len() > header.len() {
+                return Err(format!(
+                    "row {} has {} columns, header has {}",
+                    line + 2,
+                    row.len(),
+                    header.len()
+                ));
+            }
+
+            let fields = header
+                .iter()
+                .cloned()
+                .zip(row.into_iter().map(|value| value.trim().to_string()))
+                .collect();
+            let recipient = Recipient { fields };
+            if recipient.name().is_empty() {
+                return Err(format!("row {} has no name", line + 2));
+            }
+            recipients.push(recipient);
+        }
+
+        Ok(recipients)
+    }
+
+    pub fn name(&self) -> &str {
+        self.get("name").unwrap_or_default()
+    }
+
+    /// Non-empty value of a column
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.fields
+            .get(column)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Replace `{column}` placeholders with this recipient's values
+    pub fn fill(&self, template: &str) -> String {
+        self.fields
+            .iter()
+            .fold(template.to_string(), |text, (column, value)| {
+                text.replace(&format!("{{{column}}}"), value)
+            })
+    }
+
+    /// File name friendly version of the name, e.g. "ACME Corp." -> "acme-corp"
+    pub fn slug(&self) -> String {
+        self.name()
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
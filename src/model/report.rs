@@ -0,0 +1,114 @@
+//! Reports - what a build or export produced, for callers of the library
+
+use crate::infrastructure::chrome::ThumbnailStats;
+use crate::services::lint::{Issue, Severity};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Result of building a deck
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BuildReport {
+    pub deck: String,
+    pub lang: String,
+    pub slides: usize,
+    /// Files rendered into the output directory: slide, presenter, remote,
+    /// overview and print pages and the manifest; copied assets not included
+    pub files: usize,
+    pub output_dir: PathBuf,
+    /// None when the build skipped thumbnails
+    pub thumbnails: Option<ThumbnailStats>,
+}
+
+/// Result of a PDF or video export
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExportReport {
+    pub files: Vec<ExportedFile>,
+    /// Slides in the export
+    pub slides: usize,
+    /// Length of a video in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    /// Passwords as written for `passwords_out`: an object for one PDF, an
+    /// array for recipients
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passwords: Option<serde_json::Value>,
+}
+
+/// A file written by an export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedFile {
+    /// Path relative to the project root
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionReport>,
+}
+
+/// Encryption of an exported PDF
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EncryptionReport {
+    pub algorithm: String,
+    pub owner_password: String,
+    /// Empty when the PDF opens without a password
+    pub user_password: String,
+}
+
+/// Result of linting a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LintReport {
+    /// Decks that were checked
+    pub decks: Vec<String>,
+    pub issues: Vec<Issue>,
+}
+
+impl LintReport {
+    pub fn errors(&self) -> usize {
+        count(&self.issues, Severity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        count(&self.issues, Severity::Warning)
+    }
+}
+
+/// Result of the accessibility check of a deck
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct A11yReport {
+    pub deck: String,
+    pub lang: String,
+    /// Whether text contrast was measured in a browser
+    pub contrast: bool,
+    pub slides: Vec<SlideAudit>,
+}
+
+/// Accessibility problems of one slide page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlideAudit {
+    pub number: usize,
+    pub template: String,
+    pub issues: Vec<Issue>,
+}
+
+impl A11yReport {
+    pub fn errors(&self) -> usize {
+        self.slides
+            .iter()
+            .map(|slide| count(&slide.issues, Severity::Error))
+            .sum()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.slides
+            .iter()
+            .map(|slide| count(&slide.issues, Severity::Warning))
+            .sum()
+    }
+}
+
+fn count(issues: &[Issue], severity: Severity) -> usize {
+    issues
+        .iter()
+        .filter(|issue| issue.severity == severity)
+        .count()
+}
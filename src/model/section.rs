@@ -0,0 +1,81 @@
+//! Section - a (possibly nested) group of slides within a deck
+
+use super::Slide;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// Reference from a slide to one of the sections it belongs to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionRef {
+    pub key: String,
+    /// Display title or translation key; falls back to `key` when absent
+    pub title: Option<String>,
+}
+
+/// A section of a loaded deck with the slide numbers it spans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    pub key: String,
+    pub title: String,
+    /// 1-based position among its sibling sections
+    pub index: usize,
+    pub depth: usize,
+    /// Slide number of the first slide in this section
+    pub first: usize,
+    /// Slide number of the last slide in this section
+    pub last: usize,
+    pub count: usize,
+    pub sections: Vec<Section>,
+}
+
+impl Section {
+    /// Build the section tree of a deck from the section paths of its slides.
+    /// Consecutive slides sharing a section key form one section.
+    pub fn tree(slides: &[Slide], translate: &dyn Fn(&str) -> String) -> Vec<Section> {
+        let numbered: Vec<(usize, &Slide)> = slides
+            .iter()
+            .enumerate()
+            .map(|(index, slide)| (index + 1, slide))
+            .collect();
+        build_level(&numbered, 0, translate)
+    }
+
+    pub fn contains(&self, number: usize) -> bool {
+        (self.first..=self.last).contains(&number)
+    }
+}
+
+fn build_level(
+    slides: &[(usize, &Slide)],
+    depth: usize,
+    translate: &dyn Fn(&str) -> String,
+) -> Vec<Section> {
+    let mut sections = Vec::new();
+
+    for (section_ref, group) in &slides
+        .iter()
+        .chunk_by(|(_, slide)| slide.sections.get(depth))
+    {
+        let Some(section_ref) = section_ref else {
+            continue;
+        };
+
+        let group: Vec<(usize, &Slide)> = group.copied().collect();
+        let first = group.first().map(|(n, _)| *n).unwrap_or_default();
+        let last = group.last().map(|(n, _)| *n).unwrap_or_default();
+        let title = section_ref.title.as_deref().unwrap_or(&section_ref.key);
+
+        sections.push(Section {
+            key: section_ref.key.clone(),
+            title: translate(title),
+            index: sections.len() + 1,
+            depth,
+            first,
+            last,
+            count: group.len(),
+            sections: build_level(&group, depth + 1, translate),
+        });
+    }
+
+    sections
+}
@@ -1,27 +1,71 @@
 //! Slide - represents a single slide template
 
+use super::SectionRef;
 use crate::error::Result;
 use crate::util::{get_template_extension, TEMPLATE_EXTENSIONS};
-use glob::glob;
+use glob::{MatchOptions, Pattern};
+use itertools::Itertools;
 use log::warn;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Slide {
     pub template: String,
-    pub section_key: Option<String>,
+    /// Section path from the outermost to the innermost section
+    pub sections: Vec<SectionRef>,
+    /// Evaluated `title` block, filled in by the renderer
+    pub title: Option<String>,
+    /// Narration clip, relative to `slides/assets`
+    pub audio: Option<String>,
+    /// Go to the next slide when the narration clip ends
+    #[serde(default)]
+    pub auto_advance: bool,
+    /// Number of fragment steps, counted by the renderer
+    #[serde(default)]
+    pub fragments: usize,
+    /// Seconds planned for presenting this slide
+    pub planned_duration: Option<f32>,
 }
 
 impl Slide {
+    /// Dotted key of the innermost section, e.g. "main.details"
+    pub fn section_key(&self) -> Option<String> {
+        (!self.sections.is_empty()).then(|| self.sections.iter().map(|s| &s.key).join("."))
+    }
+
     /// Load a collection of slides matching a glob pattern
-    /// Automatically expands .html patterns to include all template extensions
     pub(crate) fn load_collection(
         pattern: &str,
-        section_key: Option<String>,
-        templates_dir: &Path,
+        sections: &[SectionRef],
+        templates: &[String],
     ) -> Result<Vec<Slide>> {
-        let mut slides = Vec::new();
+        let slides: Vec<Slide> = Self::matching_templates(pattern, templates)
+            .into_iter()
+            .map(|template| Slide {
+                template: template.clone(),
+                sections: sections.to_vec(),
+                title: None,
+                audio: None,
+                auto_advance: false,
+                fragments: 0,
+                planned_duration: None,
+            })
+            .collect();
+
+        if slides.is_empty() {
+            warn!("Slide pattern '{pattern}' matches no templates");
+        }
+
+        Ok(slides)
+    }
+
+    /// Templates matching a glob pattern, sorted by name
+    /// Automatically expands .html patterns to include all template extensions
+    pub(crate) fn matching_templates<'a>(
+        pattern: &str,
+        templates: &'a [String],
+    ) -> Vec<&'a String> {
+        let mut matches = Vec::new();
         let mut seen_base_names = std::collections::HashSet::new();
 
         // Expand pattern to all supported extensions if it ends with .html
@@ -35,56 +79,41 @@ impl Slide {
             vec![pattern.to_string()]
         };
 
-        for pat in patterns {
-            // Normalize pattern separators for the current OS
-            let normalized_pat = pat.replace('/', std::path::MAIN_SEPARATOR_STR);
-            let full_pattern = templates_dir.join(&normalized_pat);
-            let Some(pattern_str) = full_pattern.to_str() else {
-                continue;
-            };
+        // Like a glob on the file system: `*` stays within a directory
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
 
-            let entries = match glob(pattern_str) {
-                Ok(e) => e,
-                Err(_) => continue,
+        for pat in patterns {
+            let pat = match Pattern::new(&pat) {
+                Ok(pat) => pat,
+                Err(e) => {
+                    warn!("Invalid slide pattern '{pattern}': {e}");
+                    continue;
+                }
             };
 
-            for entry in entries {
-                match entry {
-                    Ok(path) => {
-                        let path_str = path.to_str().unwrap_or("");
-                        let Some(ext) = get_template_extension(path_str) else {
-                            continue;
-                        };
-
-                        // Strip templates_dir prefix, keep relative path for layout keys
-                        let relative = path.strip_prefix(templates_dir).unwrap_or(&path);
-
-                        // Convert to forward slashes for cross-platform compatibility
-                        let Some(template) = relative.to_str().map(|s| s.replace('\\', "/")) else {
-                            continue;
-                        };
+            for template in templates {
+                if !pat.matches_with(template, options) {
+                    continue;
+                }
+                let Some(ext) = get_template_extension(template) else {
+                    continue;
+                };
 
-                        // Get base name without extension for deduplication
-                        let base_name = template.trim_end_matches(ext);
+                // Get base name without extension for deduplication
+                let base_name = template.trim_end_matches(ext);
 
-                        // Avoid duplicates (e.g., if both foo.html and foo.twig exist)
-                        if seen_base_names.insert(base_name.to_string()) {
-                            slides.push(Slide {
-                                template,
-                                section_key: section_key.clone(),
-                            });
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Glob error: {e}");
-                    }
+                // Avoid duplicates (e.g., if both foo.html and foo.twig exist)
+                if seen_base_names.insert(base_name.to_string()) {
+                    matches.push(template);
                 }
             }
         }
 
         // Sort by template name for consistent ordering
-        slides.sort_by(|a, b| a.template.cmp(&b.template));
-
-        Ok(slides)
+        matches.sort();
+        matches
     }
 }
@@ -0,0 +1,142 @@
+//! Progress reporting - long-running operations describe what they did as
+//! events, and the caller decides how (and whether) to show them
+
+use crate::infrastructure::video::VideoFormat;
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Something a build, export or server did. Serialized with the variant name
+/// in snake case as `event`, e.g. `{"event": "slides_loaded", "count": 3, ...}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    BuildStarted {
+        deck: String,
+        lang: String,
+    },
+    SlidesLoaded {
+        deck: String,
+        count: usize,
+    },
+    TemplatesLoaded {
+        count: usize,
+    },
+    SlideRendered {
+        number: usize,
+        template: String,
+    },
+    PagesRendered {
+        count: usize,
+    },
+    FilesWritten,
+    AssetsCopied,
+    /// Thumbnails are being captured, which may take a while
+    ThumbnailsStarted,
+    ThumbnailCaptured {
+        number: usize,
+    },
+    ThumbnailsGenerated {
+        total: usize,
+        cached: usize,
+    },
+    /// Thumbnails of the last build found for an export
+    ThumbnailsFound {
+        count: usize,
+    },
+    RecipientsLoaded {
+        count: usize,
+    },
+    PdfGenerated {
+        bytes: usize,
+    },
+    ApplyingWatermark,
+    Encrypting {
+        algorithm: String,
+    },
+    PasswordsWritten {
+        path: String,
+    },
+    /// Video encoding started; `encoder` is ffmpeg unless encoding a GIF
+    EncodingVideo {
+        format: VideoFormat,
+        encoder: Option<PathBuf>,
+        clips: usize,
+    },
+    ServerStarted {
+        url: String,
+        remote_pin: Option<String>,
+        session: Option<PathBuf>,
+    },
+    /// A device was paired as remote control; `pin` pairs the next one
+    RemotePaired {
+        pin: String,
+    },
+    /// `client` entered too many wrong pins and may try again in `seconds`
+    RemoteClientLockedOut {
+        client: String,
+        seconds: u64,
+    },
+    WatchStarted {
+        paths: Vec<String>,
+    },
+    ChangeDetected {
+        path: String,
+    },
+    Rebuilding,
+    /// A rebuild in watch mode failed; watching continues
+    BuildFailed {
+        message: String,
+    },
+}
+
+/// Receives progress events, e.g. to print them or update a progress bar.
+/// Closures taking a `&ProgressEvent` are handlers too.
+pub trait Progress: Send + Sync {
+    fn report(&self, event: &ProgressEvent);
+}
+
+impl<F> Progress for F
+where
+    F: Fn(&ProgressEvent) + Send + Sync,
+{
+    fn report(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Ignores all events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Silent;
+
+impl Progress for Silent {
+    fn report(&self, _event: &ProgressEvent) {}
+}
+
+/// Shared handle to a progress handler; silent by default
+#[derive(Clone)]
+pub struct Reporter(Arc<dyn Progress>);
+
+impl Reporter {
+    pub fn new(progress: impl Progress + 'static) -> Self {
+        Self(Arc::new(progress))
+    }
+
+    pub fn report(&self, event: ProgressEvent) {
+        self.0.report(&event);
+    }
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Self::new(Silent)
+    }
+}
+
+impl fmt::Debug for Reporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Reporter")
+    }
+}
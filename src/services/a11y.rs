@@ -0,0 +1,126 @@
+//! Accessibility module - Checks of rendered slide pages: alt text, heading
+//! order, page language and keyboard access
+
+use super::lint::{images_without_alt, start_tags, Issue};
+
+/// Elements that can get keyboard focus without `tabindex`
+const FOCUSABLE_ELEMENTS: &[&str] = &["button", "input", "select", "textarea", "summary"];
+
+/// Roles of controls that must be reachable by keyboard
+const CONTROL_ROLES: &[&str] = &[
+    "button", "checkbox", "link", "menuitem", "option", "radio", "slider", "switch", "tab",
+];
+
+/// All checks of a rendered page; `lang` is the language of the build
+pub fn check_page(file: &str, html: &str, lang: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for line in images_without_alt(html) {
+        issues.push(Issue::error(
+            "missing-alt",
+            file,
+            Some(line),
+            "Image without alt text".to_string(),
+        ));
+    }
+    issues.extend(check_lang(file, html, lang));
+    issues.extend(check_headings(file, html));
+    issues.extend(check_keyboard(file, html));
+    issues
+}
+
+/// The `lang` of `<html>` must be set and match the language of the build
+pub fn check_lang(file: &str, html: &str, lang: &str) -> Vec<Issue> {
+    let Some(tag) = start_tags(html).into_iter().find(|tag| tag.name == "html") else {
+        return vec![Issue::error(
+            "lang",
+            file,
+            None,
+            "Page has no <html> element with a lang attribute".to_string(),
+        )];
+    };
+
+    let line = tag.line;
+    match tag.attribute("lang").filter(|value| !value.is_empty()) {
+        None => vec![Issue::error(
+            "lang",
+            file,
+            Some(line),
+            "<html> has no lang attribute".to_string(),
+        )],
+        Some(value) if primary_subtag(value) != primary_subtag(lang) => vec![Issue::error(
+            "lang",
+            file,
+            Some(line),
+            format!("<html lang=\"{value}\"> does not match the language '{lang}' of the build"),
+        )],
+        Some(_) => Vec::new(),
+    }
+}
+
+/// "de-AT" -> "de"
+fn primary_subtag(lang: &str) -> String {
+    lang.split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Headings must not skip levels, e.g. an h4 directly after an h2
+pub fn check_headings(file: &str, html: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut previous: Option<u8> = None;
+
+    for tag in start_tags(html) {
+        let line = tag.line;
+        let level = match tag.name.as_bytes() {
+            [b'h', level @ b'1'..=b'6'] => level - b'0',
+            _ => continue,
+        };
+        if let Some(previous) = previous.filter(|&previous| level > previous + 1) {
+            issues.push(Issue::warning(
+                "heading-order",
+                file,
+                Some(line),
+                format!("<h{level}> follows <h{previous}>, skipping a heading level"),
+            ));
+        }
+        previous = Some(level);
+    }
+
+    issues
+}
+
+/// Controls that a mouse can use but a keyboard cannot reach: click handlers
+/// and control roles on elements without focus, e.g. links without `href`
+pub fn check_keyboard(file: &str, html: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for tag in start_tags(html) {
+        let (line, name) = (tag.line, tag.name.as_str());
+        let tabindex = tag
+            .attribute("tabindex")
+            .and_then(|v| v.parse::<i32>().ok());
+        let focusable = match tabindex {
+            Some(tabindex) => tabindex >= 0,
+            None => {
+                FOCUSABLE_ELEMENTS.contains(&name) || (name == "a" && tag.has_attribute("href"))
+            }
+        };
+        let role = tag.attribute("role").unwrap_or_default();
+        let control = tag.has_attribute("onclick") || CONTROL_ROLES.contains(&role);
+
+        if control && !focusable {
+            issues.push(Issue::error(
+                "keyboard",
+                file,
+                Some(line),
+                format!(
+                    "<{name}> acts as a control but cannot be reached by keyboard; \
+                     use a <button> or add tabindex=\"0\""
+                ),
+            ));
+        }
+    }
+
+    issues
+}
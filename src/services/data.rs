@@ -0,0 +1,146 @@
+//! Data module - YAML, JSON, TOML and CSV files of `slides/data` for charts
+//! and tables in templates
+
+use crate::error::{Error, Result};
+use crate::infrastructure::source::TemplateSource;
+use crate::util;
+use log::warn;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Extensions of data files, in the order that wins when two files share a name
+pub const DATA_EXTENSIONS: &[&str] = &["yaml", "yml", "json", "toml", "csv"];
+
+/// Parsed data files keyed by their path relative to `slides/data`, e.g. "sales.csv"
+pub type DataFiles = BTreeMap<String, Value>;
+
+/// Parse the data files of `dir` of a source
+pub fn load_source(source: &dyn TemplateSource, dir: &str) -> Result<DataFiles> {
+    let mut files = DataFiles::new();
+    for file in source.list(dir)? {
+        let Some(format) = data_format(&file) else {
+            continue;
+        };
+        let path = format!("{}/{file}", dir.trim_end_matches('/'));
+        let Some(content) = source.read(&path)? else {
+            continue;
+        };
+        let value = parse(format, &content).map_err(|message| Error::DataParse {
+            path: PathBuf::from(&path),
+            message,
+        })?;
+        files.insert(file, value);
+    }
+
+    for (name, file) in names(&files) {
+        for other in files
+            .keys()
+            .filter(|other| stem(other) == Some(name) && *other != file)
+        {
+            warn!("Data file '{other}' is shadowed by '{file}'; use load_data(\"{other}\")");
+        }
+    }
+    Ok(files)
+}
+
+/// Names of the top-level data files with the file each one stands for:
+/// "sales" -> "sales.csv". YAML wins over JSON, TOML and CSV.
+pub fn names(files: &DataFiles) -> BTreeMap<&str, &str> {
+    let mut names = BTreeMap::new();
+    for format in DATA_EXTENSIONS {
+        for file in files.keys() {
+            let Some(name) = stem(file) else {
+                continue;
+            };
+            if data_format(file) == Some(format) {
+                names.entry(name).or_insert(file.as_str());
+            }
+        }
+    }
+    names
+}
+
+/// Data file for `data.<name>`
+pub fn by_name<'a>(files: &'a DataFiles, name: &str) -> Option<&'a Value> {
+    names(files).get(name).and_then(|file| files.get(*file))
+}
+
+/// "sales.csv" -> "sales"; None for files in subdirectories
+fn stem(file: &str) -> Option<&str> {
+    if file.contains('/') {
+        return None;
+    }
+    file.rsplit_once('.').map(|(stem, _)| stem)
+}
+
+fn data_format(file: &str) -> Option<&'static str> {
+    let (_, extension) = file.rsplit_once('.')?;
+    DATA_EXTENSIONS
+        .iter()
+        .find(|ext| ext.eq_ignore_ascii_case(extension))
+        .copied()
+}
+
+/// Parse a data file of the given format (one of `DATA_EXTENSIONS`)
+pub fn parse(format: &str, content: &str) -> std::result::Result<Value, String> {
+    match format {
+        "yaml" | "yml" => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        "json" => serde_json::from_str(content).map_err(|e| e.to_string()),
+        "toml" => toml::from_str(content).map_err(|e| e.to_string()),
+        "csv" => parse_csv(content),
+        _ => Err(format!("Unsupported data format '{format}'")),
+    }
+}
+
+/// Rows of a CSV file with a header line as objects keyed by column name.
+/// Numbers become numbers, everything else stays text.
+pub fn parse_csv(content: &str) -> std::result::Result<Value, String> {
+    let mut records = util::csv_records(content)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Value::Array(Vec::new()));
+    };
+
+    let mut rows = Vec::new();
+    for (index, record) in records.enumerate() {
+        if record.len() == 1 && record[0].is_empty() {
+            continue;
+        }
+        if record.len() != header.len() {
+            return Err(format!(
+                "row {} has {} fields, the header has {}",
+                index + 2,
+                record.len(),
+                header.len()
+            ));
+        }
+        let row: Map<String, Value> = header
+            .iter()
+            .cloned()
+            .zip(record.into_iter().map(csv_value))
+            .collect();
+        rows.push(Value::Object(row));
+    }
+    Ok(Value::Array(rows))
+}
+
+/// "42" -> 42, "1.5" -> 1.5; leading zeros and other text stay strings
+fn csv_value(field: String) -> Value {
+    let digits = field.trim_start_matches('-');
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !leading_zero {
+        if let Ok(number) = field.parse::<i64>() {
+            return number.into();
+        }
+        if field.contains('.') {
+            if let Some(number) = field
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+            {
+                return Value::Number(number);
+            }
+        }
+    }
+    Value::String(field)
+}
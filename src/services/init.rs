@@ -9,9 +9,7 @@ static TEMPLATE_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/assets/templates/in
 
 /// Create all project files in the target directory
 pub fn create_project_files(target: &Path) -> Result<()> {
-    copy_dir_recursive(&TEMPLATE_DIR, target)?;
-    println!("   ✅ Created project files");
-    Ok(())
+    copy_dir_recursive(&TEMPLATE_DIR, target)
 }
 
 fn copy_dir_recursive(dir: &Dir, target: &Path) -> Result<()> {
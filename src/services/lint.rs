@@ -0,0 +1,582 @@
+//! Lint module - Static checks of decks.yaml, templates and rendered pages
+
+use crate::model::{
+    AudioConfig, DeckConfig, DeckMetadata, DetailedDeckConfig, SectionConfig, TimingConfig,
+    VideoConfig,
+};
+use crate::util::{get_template_extension, struct_fields};
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// Keys of the settings of a deck with metadata, taken from the config types
+fn settings_keys() -> [(&'static str, &'static [&'static str]); 4] {
+    [
+        ("metadata", struct_fields::<DeckMetadata>()),
+        ("video", struct_fields::<VideoConfig>()),
+        ("audio", struct_fields::<AudioConfig>()),
+        ("timing", struct_fields::<TimingConfig>()),
+    ]
+}
+
+/// Templates rendered by name rather than listed in a deck
+pub const SPECIAL_TEMPLATES: &[&str] = &[
+    "overview.html",
+    "presenter.html",
+    "print.html",
+    "remote.html",
+];
+
+/// Elements without content or end tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose end tag may be left out
+const OPTIONAL_END_ELEMENTS: &[&str] = &[
+    "p", "li", "dt", "dd", "option", "optgroup", "tr", "td", "th", "thead", "tbody", "tfoot",
+    "colgroup", "caption", "rt", "rp", "html", "head", "body",
+];
+
+/// Elements with raw text content, which is not parsed for tags
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A problem found by `slides lint`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Name of the check, e.g. "missing-alt"
+    pub rule: &'static str,
+    pub message: String,
+    /// Project file, or rendered page below `output/`
+    pub file: String,
+    pub line: Option<usize>,
+}
+
+impl Issue {
+    pub fn error(rule: &'static str, file: &str, line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            rule,
+            message,
+            file: file.to_string(),
+            line,
+        }
+    }
+
+    pub fn warning(rule: &'static str, file: &str, line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(rule, file, line, message)
+        }
+    }
+}
+
+/// "slides/templates/intro.html:3: warning [missing-alt] Image without alt text"
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}", self.file)?,
+            None => write!(f, "{}", self.file)?,
+        }
+        write!(f, ": {} [{}] {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// Check the structure of decks.yaml: slide lists, sections and deck keys
+pub fn check_decks_file(file: &str, content: &str) -> Vec<Issue> {
+    let data: Value = match serde_yaml::from_str(content) {
+        Ok(data) => data,
+        Err(e) => {
+            let line = e.location().map(|location| location.line());
+            return vec![Issue::error("decks-yaml", file, line, e.to_string())];
+        }
+    };
+
+    let Value::Mapping(decks) = data else {
+        return vec![Issue::error(
+            "decks-yaml",
+            file,
+            Some(1),
+            "Expected a mapping of deck names to slide lists".to_string(),
+        )];
+    };
+
+    let mut issues = Vec::new();
+    for (name, deck) in &decks {
+        let Some(name) = name.as_str() else {
+            issues.push(Issue::error(
+                "decks-yaml",
+                file,
+                None,
+                format!("Deck name {name:?} is not a string"),
+            ));
+            continue;
+        };
+        check_deck(file, content, name, deck, &mut issues);
+    }
+
+    // Types of metadata, video, audio and timing settings
+    if issues
+        .iter()
+        .all(|issue| issue.severity == Severity::Warning)
+    {
+        if let Err(e) = serde_yaml::from_str::<IndexMap<String, DeckConfig>>(content) {
+            let line = e.location().map(|location| location.line());
+            issues.push(Issue::error("decks-yaml", file, line, e.to_string()));
+        }
+    }
+
+    issues
+}
+
+fn check_deck(file: &str, content: &str, name: &str, deck: &Value, issues: &mut Vec<Issue>) {
+    let line = line_of(content, name);
+    match deck {
+        Value::Sequence(entries) => check_entries(file, content, name, entries, issues),
+        Value::Mapping(map) if map.contains_key("slides") && !is_lone_section(map) => {
+            let deck_keys = struct_fields::<DetailedDeckConfig>();
+            for key in map.keys() {
+                let key = key.as_str().unwrap_or_default();
+                if !deck_keys.contains(&key) {
+                    issues.push(Issue::warning(
+                        "decks-yaml",
+                        file,
+                        line_of(content, key).or(line),
+                        format!(
+                            "Unknown key '{key}' in deck '{name}' (use {})",
+                            deck_keys.join(", ")
+                        ),
+                    ));
+                }
+            }
+            for (setting, known) in settings_keys() {
+                if let Some(Value::Mapping(settings)) = map.get(setting) {
+                    check_keys(file, content, setting, settings, known, issues);
+                }
+            }
+            check_deck(file, content, name, &map["slides"], issues);
+        }
+        Value::Mapping(sections) => check_sections(file, content, name, sections, issues),
+        _ => issues.push(Issue::error(
+            "decks-yaml",
+            file,
+            line,
+            format!(
+                "Deck '{name}' must be a list of slide patterns, sections or a deck with `slides`"
+            ),
+        )),
+    }
+}
+
+/// `deck: {slides: [...]}`, a section named "slides" rather than a deck with
+/// settings, as `DeckConfig` reads it
+fn is_lone_section(map: &serde_yaml::Mapping) -> bool {
+    map.len() == 1
+        && serde_yaml::from_value::<IndexMap<String, SectionConfig>>(Value::Mapping(map.clone()))
+            .is_ok()
+}
+
+fn check_keys(
+    file: &str,
+    content: &str,
+    setting: &str,
+    settings: &serde_yaml::Mapping,
+    known: &[&str],
+    issues: &mut Vec<Issue>,
+) {
+    for key in settings.keys().filter_map(Value::as_str) {
+        if !known.contains(&key) {
+            issues.push(Issue::warning(
+                "decks-yaml",
+                file,
+                line_of(content, key),
+                format!(
+                    "Unknown key '{key}' in {setting} (use {})",
+                    known.join(", ")
+                ),
+            ));
+        }
+    }
+}
+
+fn check_entries(
+    file: &str,
+    content: &str,
+    context: &str,
+    entries: &[Value],
+    issues: &mut Vec<Issue>,
+) {
+    for entry in entries {
+        match entry {
+            Value::String(_) => {}
+            Value::Mapping(sections) => check_sections(file, content, context, sections, issues),
+            _ => issues.push(Issue::error(
+                "decks-yaml",
+                file,
+                line_of(content, context),
+                format!("Entries of '{context}' must be slide patterns or sections"),
+            )),
+        }
+    }
+}
+
+fn check_sections(
+    file: &str,
+    content: &str,
+    context: &str,
+    sections: &serde_yaml::Mapping,
+    issues: &mut Vec<Issue>,
+) {
+    for (key, section) in sections {
+        let key = key.as_str().unwrap_or(context);
+        match section {
+            Value::Sequence(entries) => check_entries(file, content, key, entries, issues),
+            Value::Mapping(map) if map.contains_key("slides") => {
+                if let Some(Value::Sequence(entries)) = map.get("slides") {
+                    check_entries(file, content, key, entries, issues);
+                }
+                if let Some(extra) = map
+                    .keys()
+                    .filter_map(Value::as_str)
+                    .find(|k| *k != "title" && *k != "slides")
+                {
+                    issues.push(Issue::warning(
+                        "decks-yaml",
+                        file,
+                        line_of(content, extra),
+                        format!("Unknown key '{extra}' in section '{key}' (use title, slides)"),
+                    ));
+                }
+            }
+            _ => issues.push(Issue::error(
+                "decks-yaml",
+                file,
+                line_of(content, key),
+                format!("Section '{key}' must be a list of slides or have `slides`"),
+            )),
+        }
+    }
+}
+
+/// First line mentioning `text` as a key or list item, 1-based
+pub fn line_of(content: &str, text: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start().trim_start_matches("- ");
+            let line = line.trim_start_matches(['"', '\'']);
+            line.starts_with(text)
+        })
+        .map(|index| index + 1)
+}
+
+/// Trimmed content of the `{% ... %}` tags of a template
+fn template_tags(content: &str) -> Vec<&str> {
+    let mut tags = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{%") {
+        let Some(end) = rest[start..].find("%}") else {
+            break;
+        };
+        tags.push(rest[start + 2..start + end].trim_matches(['-', '+', ' ', '\n', '\t', '\r']));
+        rest = &rest[start + end + 2..];
+    }
+
+    tags
+}
+
+/// 1-based line of a byte offset
+pub(crate) fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// First quoted string, e.g. `layouts/base.html` of `extends "layouts/base.html"`
+fn quoted(text: &str) -> Option<&str> {
+    let start = text.find(['"', '\''])?;
+    let quote = &text[start..start + 1];
+    let value = &text[start + 1..];
+    value.find(quote).map(|end| &value[..end])
+}
+
+pub fn has_block(content: &str, block: &str) -> bool {
+    template_tags(content).iter().any(|tag| {
+        let mut words = tag.split_whitespace();
+        words.next() == Some("block") && words.next() == Some(block)
+    })
+}
+
+/// Templates a template extends, includes or imports
+pub fn referenced_templates(content: &str) -> Vec<String> {
+    template_tags(content)
+        .iter()
+        .filter(|tag| {
+            let keyword = tag.split_whitespace().next().unwrap_or_default();
+            matches!(keyword, "extends" | "include" | "import" | "from")
+        })
+        .filter_map(|tag| quoted(tag))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Name under which a template is registered as `.html`, e.g. "a.twig" -> "a.html"
+pub fn html_name(template: &str) -> String {
+    match get_template_extension(template) {
+        Some(ext) => format!("{}.html", template.trim_end_matches(ext)),
+        None => template.to_string(),
+    }
+}
+
+/// Templates neither in a deck, nor special, nor used by another template
+pub fn unused_templates(templates: &[(String, String)], slides: &BTreeSet<String>) -> Vec<String> {
+    let mut used: HashSet<String> = slides.iter().map(|t| html_name(t)).collect();
+    used.extend(SPECIAL_TEMPLATES.iter().map(|t| t.to_string()));
+    for (_, content) in templates {
+        used.extend(referenced_templates(content).iter().map(|t| html_name(t)));
+    }
+
+    templates
+        .iter()
+        .map(|(name, _)| name)
+        .filter(|name| !used.contains(&html_name(name)))
+        .cloned()
+        .collect()
+}
+
+/// Literal paths passed to `asset()`, with their line
+pub fn asset_references(content: &str) -> Vec<(usize, String)> {
+    let mut references = Vec::new();
+    for (start, _) in content.match_indices("asset(") {
+        let preceded_by_name = content[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if preceded_by_name {
+            continue;
+        }
+        let argument = content[start + "asset(".len()..].trim_start();
+        if !argument.starts_with(['"', '\'']) {
+            continue;
+        }
+        if let Some(path) = quoted(argument) {
+            references.push((line_at(content, start), path.to_string()));
+        }
+    }
+    references
+}
+
+/// A start or end tag of an HTML page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tag<'a> {
+    pub line: usize,
+    /// Lowercase element name
+    pub name: String,
+    pub end: bool,
+    /// Written as `<name ... />`
+    pub self_closing: bool,
+    /// Lowercase attribute names with their unquoted values; empty without value
+    pub attributes: Vec<(String, &'a str)>,
+}
+
+impl Tag<'_> {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attribute(name).is_some()
+    }
+}
+
+/// Tags of an HTML page in document order. Comments, doctypes and the content
+/// of raw text elements such as `<script>` are skipped; `>` inside quoted
+/// attribute values does not end a tag.
+pub(crate) fn tags(html: &str) -> Vec<Tag<'_>> {
+    let mut tags = Vec::new();
+    let mut position = 0;
+
+    while let Some(found) = html[position..].find('<') {
+        let start = position + found;
+        let rest = &html[start..];
+
+        if rest.starts_with("<!--") {
+            position = start + rest.find("-->").map_or(rest.len(), |end| end + 3);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            position = start + rest.find('>').map_or(rest.len(), |end| end + 1);
+            continue;
+        }
+
+        let end = rest.starts_with("</");
+        let name_start = start + if end { 2 } else { 1 };
+        if !html[name_start..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            position = start + 1;
+            continue;
+        }
+        let name_end = html[name_start..]
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .map_or(html.len(), |end| name_start + end);
+        let name = html[name_start..name_end].to_ascii_lowercase();
+
+        let (attributes, self_closing, tag_end) = parse_attributes(html, name_end);
+        position = tag_end;
+        let raw_text = !end && !self_closing && RAW_TEXT_ELEMENTS.contains(&name.as_str());
+        tags.push(Tag {
+            line: line_at(html, start),
+            name,
+            end,
+            self_closing,
+            attributes: if end { Vec::new() } else { attributes },
+        });
+
+        // Raw text is not markup: continue at its end tag
+        if raw_text {
+            let close = format!("</{}", tags.last().expect("just pushed").name);
+            position = html[position..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or(html.len(), |end| position + end);
+        }
+    }
+
+    tags
+}
+
+/// Attributes from `position` up to the `>` that ends the tag, whether the tag
+/// is self-closing, and the offset after the tag
+fn parse_attributes(html: &str, mut position: usize) -> (Vec<(String, &str)>, bool, usize) {
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+
+    loop {
+        let rest = &html[position..];
+        let Some(c) = rest.chars().next() else {
+            return (attributes, self_closing, position);
+        };
+        if c == '>' {
+            return (attributes, self_closing, position + 1);
+        }
+        if c.is_whitespace() || c == '/' {
+            self_closing = c == '/';
+            position += c.len_utf8();
+            continue;
+        }
+        self_closing = false;
+
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_ascii_lowercase();
+        position += name_len;
+
+        let after_name = html[position..].trim_start();
+        let Some(value) = after_name.strip_prefix('=') else {
+            attributes.push((name, ""));
+            continue;
+        };
+        let value = value.trim_start();
+        let value_start = html.len() - value.len();
+        let (value, value_end) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => match value[1..].find(quote) {
+                Some(end) => (&value[1..1 + end], value_start + end + 2),
+                None => (&value[1..], html.len()),
+            },
+            _ => {
+                let end = value
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(value.len());
+                (&value[..end], value_start + end)
+            }
+        };
+        attributes.push((name, value));
+        position = value_end;
+    }
+}
+
+/// Start tags of an HTML page
+pub(crate) fn start_tags(html: &str) -> Vec<Tag<'_>> {
+    tags(html).into_iter().filter(|tag| !tag.end).collect()
+}
+
+/// Images without `alt` attribute, by line
+pub fn images_without_alt(html: &str) -> Vec<usize> {
+    start_tags(html)
+        .into_iter()
+        .filter(|tag| tag.name == "img" && !tag.has_attribute("alt"))
+        .map(|tag| tag.line)
+        .collect()
+}
+
+/// Tag nesting problems of an HTML page: stray end tags and unclosed elements
+pub fn check_html(file: &str, html: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut open: Vec<(String, usize)> = Vec::new();
+
+    for tag in tags(html) {
+        if tag.end {
+            let name = tag.name;
+            let Some(index) = open.iter().rposition(|(open, _)| *open == name) else {
+                issues.push(Issue::warning(
+                    "html",
+                    file,
+                    Some(tag.line),
+                    format!("Unexpected end tag </{name}>"),
+                ));
+                continue;
+            };
+            for (unclosed, line) in open.drain(index..).skip(1) {
+                if !OPTIONAL_END_ELEMENTS.contains(&unclosed.as_str()) {
+                    issues.push(Issue::warning(
+                        "html",
+                        file,
+                        Some(line),
+                        format!("<{unclosed}> is not closed before </{name}>"),
+                    ));
+                }
+            }
+            continue;
+        }
+
+        if VOID_ELEMENTS.contains(&tag.name.as_str()) || tag.self_closing {
+            continue;
+        }
+        open.push((tag.name, tag.line));
+    }
+
+    for (unclosed, line) in open {
+        if !OPTIONAL_END_ELEMENTS.contains(&unclosed.as_str()) {
+            issues.push(Issue::warning(
+                "html",
+                file,
+                Some(line),
+                format!("<{unclosed}> is never closed"),
+            ));
+        }
+    }
+
+    issues
+}
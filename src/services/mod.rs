@@ -1,5 +1,8 @@
 //! Application Services
 
+pub mod a11y;
+pub mod data;
 pub mod init;
+pub mod lint;
 pub mod render;
 pub mod translations;
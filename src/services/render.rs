@@ -1,16 +1,22 @@
 //! Render module - Template rendering for slides
 
-use crate::error::{Error, Result};
+use crate::error::Result;
+use crate::infrastructure::chrome::ThumbnailSize;
+use crate::infrastructure::source::{FileSource, TemplateSource};
 use crate::minijinja::filters::markdown_to_html;
 use crate::minijinja::setup_environment;
-use crate::model::Slide;
+pub use crate::minijinja::Globals;
+use crate::model::{Section, Slide};
+use crate::services::data::DataFiles;
+use crate::services::lint;
+use crate::services::translations::{text_direction, Locale};
 use crate::util::get_template_extension;
 use itertools::Itertools;
 use log::warn;
 use minijinja::{context, Environment, Value};
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy)]
 struct SlideNav {
@@ -50,56 +56,101 @@ impl SlideNav {
     }
 }
 
-pub fn load_layouts(dir: &Path) -> Result<HashMap<String, String>> {
-    let mut layouts = HashMap::new();
-    load_layouts_recursive(dir, dir, &mut layouts)?;
-    Ok(layouts)
+/// Locales for language switchers and `hreflang` links
+fn locales_context(locale: &Locale) -> Vec<Value> {
+    locale
+        .locales
+        .iter()
+        .map(|code| {
+            context! {
+                code => code,
+                dir => text_direction(code),
+                current => *code == locale.lang,
+            }
+        })
+        .collect()
 }
 
-fn load_layouts_recursive(
-    base_dir: &Path,
-    current_dir: &Path,
-    layouts: &mut HashMap<String, String>,
-) -> Result<()> {
-    let entries = fs::read_dir(current_dir).map_err(|e| Error::ReadDir {
-        path: current_dir.to_path_buf(),
-        source: e,
-    })?;
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if path.is_dir() {
-            load_layouts_recursive(base_dir, &path, layouts)?;
-            continue;
-        }
+/// Build the deck's section tree with display titles run through the translations
+pub fn section_tree(slides: &[Slide], translations: &HashMap<String, String>) -> Vec<Section> {
+    Section::tree(slides, &|title| {
+        translations
+            .get(title)
+            .cloned()
+            .unwrap_or_else(|| title.to_string())
+    })
+}
 
-        let Some(filename) = path.file_name().and_then(|s| s.to_str()) else {
-            continue;
-        };
-        let Some(ext) = get_template_extension(filename) else {
-            continue;
-        };
+/// Sections containing the given slide number, from the outermost to the innermost
+fn section_path(sections: &[Section], number: usize) -> Vec<&Section> {
+    let mut path = Vec::new();
+    let mut level = sections;
+    while let Some(section) = level.iter().find(|s| s.contains(number)) {
+        path.push(section);
+        level = &section.sections;
+    }
+    path
+}
 
-        let Ok(relative_path) = path.strip_prefix(base_dir) else {
-            continue;
-        };
-        let Some(relative_str) = relative_path.to_str() else {
-            continue;
-        };
+/// Section data for a slide: the innermost section plus the full `path`, or none
+fn section_context(sections: &[Section], number: usize) -> Value {
+    let path: Vec<Value> = section_path(sections, number)
+        .into_iter()
+        .map(|section| {
+            context! {
+                key => section.key.clone(),
+                title => section.title.clone(),
+                index => section.index,
+                depth => section.depth,
+                position => number - section.first + 1,
+                total => section.count,
+                first => section.first,
+                last => section.last,
+            }
+        })
+        .collect();
 
-        // Normalize to forward slashes for cross-platform compatibility
-        let normalized_path = relative_str.replace('\\', "/");
+    match path.last() {
+        Some(current) => context! { path => path.clone(), ..current.clone() },
+        None => Value::from(()),
+    }
+}
+
+pub fn load_layouts(dir: &Path) -> Result<HashMap<String, String>> {
+    load_source_layouts(&FileSource::new(dir), "")
+}
 
-        let content = fs::read_to_string(&path).map_err(|e| Error::FileRead {
-            path: path.clone(),
-            source: e,
-        })?;
+/// Load the templates below `dir` of a source, keyed by their path relative to
+/// it. Other text files are loaded too, for `source()`.
+pub fn load_source_layouts(
+    source: &dyn TemplateSource,
+    dir: &str,
+) -> Result<HashMap<String, String>> {
+    let mut layouts = HashMap::new();
 
-        register_template(layouts, &normalized_path, ext, content);
+    for name in source.list(dir)? {
+        let path = if dir.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{name}", dir.trim_end_matches('/'))
+        };
+
+        match get_template_extension(&name) {
+            Some(ext) => {
+                if let Some(content) = source.read(&path)? {
+                    register_template(&mut layouts, &name, ext, content);
+                }
+            }
+            // Included with source(), e.g. code samples; binary files are skipped
+            None => {
+                if let Ok(Some(content)) = source.read(&path) {
+                    layouts.insert(name, content);
+                }
+            }
+        }
     }
 
-    Ok(())
+    Ok(layouts)
 }
 
 /// Register template under original name and normalized .html name
@@ -125,15 +176,21 @@ fn try_render(env: &Environment, template: &str, ctx: &Value) -> Option<String>
         .ok()
 }
 
-fn extract_slide_notes(env: &Environment, template: &str, current: usize, total: usize) -> String {
+fn render_slide_block(
+    env: &Environment,
+    template: &str,
+    block: &str,
+    current: usize,
+    total: usize,
+) -> Option<String> {
     let ctx = context! {
         app => context! { total => total, first => 1, last => total },
         slide => context! { current => current },
     };
 
     let Ok(tmpl) = env.get_template(template) else {
-        warn!("Template '{}' not found for speaker notes", template);
-        return String::new();
+        warn!("Template '{}' not found for block '{}'", template, block);
+        return None;
     };
 
     let Ok(mut state) = tmpl.eval_to_state(&ctx) else {
@@ -141,172 +198,490 @@ fn extract_slide_notes(env: &Environment, template: &str, current: usize, total:
             "Failed to evaluate template '{}' for slide {}",
             template, current
         );
-        return String::new();
+        return None;
     };
 
-    state
-        .render_block("notes")
-        .ok()
+    state.render_block(block).ok()
+}
+
+fn extract_slide_notes(env: &Environment, template: &str, current: usize, total: usize) -> String {
+    render_slide_block(env, template, "notes", current, total)
         .map(|raw| markdown_to_html(&raw))
         .unwrap_or_default()
 }
 
-pub fn render_deck_pages(
-    slides: &[Slide],
-    layouts: &HashMap<String, String>,
-    translations: &HashMap<String, String>,
-) -> HashMap<String, String> {
-    let mut pages = HashMap::new();
+/// Titles of all slides: the stored title, or the evaluated `title` block with
+/// whitespace collapsed
+fn slide_titles(env: &Environment, slides: &[Slide]) -> Vec<String> {
     let total = slides.len();
-    let env = setup_environment(layouts, translations);
+    slides
+        .iter()
+        .enumerate()
+        .map(|(index, slide)| match &slide.title {
+            Some(title) => title.clone(),
+            None => render_slide_block(env, &slide.template, "title", index + 1, total)
+                .map(|raw| raw.split_whitespace().join(" "))
+                .unwrap_or_default(),
+        })
+        .collect()
+}
 
-    for (index, slide) in slides.iter().enumerate() {
-        let nav = SlideNav::new(index, total);
+/// Evaluate each slide's `title` block and store it on the slide
+pub fn assign_slide_titles(
+    slides: &mut [Slide],
+    layouts: &HashMap<String, String>,
+    translations: &HashMap<String, String>,
+) {
+    RenderContext::new(layouts.clone(), translations.clone()).assign_slide_titles(slides);
+}
 
-        let ctx = context! {
-            app => nav.app_context(),
-            slide => nav.to_context(),
-            // Legacy uppercase variables for backwards compatibility
-            CURRENT => nav.current,
-            TOTAL => nav.total,
-            PREV => nav.prev,
-            NEXT => nav.next,
-        };
+/// Count the fragment steps of each rendered slide page and store them on the slides
+pub fn assign_fragment_counts(slides: &mut [Slide], pages: &HashMap<String, String>) {
+    for (index, slide) in slides.iter_mut().enumerate() {
+        slide.fragments = pages
+            .get(&format!("slide-{}.html", index + 1))
+            .map_or(0, |html| count_fragments(html));
+    }
+}
 
-        let Ok(tmpl) = env.get_template(&slide.template) else {
-            warn!("Template '{}' not found", slide.template);
-            continue;
-        };
+/// Number of reveal steps in a page: elements with a `data-fragment` attribute
+/// share a step when they have the same number; unnumbered ones take their
+/// position among all fragments (the same rule as `slides.js`). Comments and
+/// scripts are not markup, so fragments in them do not count.
+pub fn count_fragments(html: &str) -> usize {
+    let steps: std::collections::BTreeSet<usize> = lint::start_tags(html)
+        .iter()
+        .filter_map(|tag| tag.attribute("data-fragment"))
+        .enumerate()
+        .map(|(index, value)| {
+            let digits: String = value
+                .trim_start()
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits
+                .parse::<usize>()
+                .ok()
+                .filter(|number| *number > 0)
+                .unwrap_or(index + 1)
+        })
+        .collect();
+    steps.len()
+}
 
-        match tmpl.render(&ctx) {
-            Ok(html) => {
-                pages.insert(format!("slide-{}.html", nav.current), html);
+/// Agenda entries for `toc()`: the section tree with slide titles, marking the
+/// sections and slide that contain `current`. Slides outside of sections are
+/// entries of their own, without `key`.
+fn toc_context(sections: &[Section], titles: &[String], current: Option<usize>) -> Vec<Value> {
+    let mut entries = Vec::new();
+    let mut number = 1;
+    while number <= titles.len() {
+        match sections.iter().find(|section| section.first == number) {
+            Some(section) => {
+                entries.push(toc_section(section, titles, current));
+                number = section.last + 1;
+            }
+            None => {
+                let slide = toc_slide(number, titles, current);
+                entries.push(context! {
+                    title => titles[number - 1].clone(),
+                    depth => 0,
+                    first => number,
+                    last => number,
+                    count => 1,
+                    active => current == Some(number),
+                    slides => vec![slide],
+                    sections => Vec::<Value>::new(),
+                });
+                number += 1;
             }
-            Err(e) => warn!("Error rendering slide {}: {}", nav.current, e),
         }
     }
+    entries
+}
+
+fn toc_section(section: &Section, titles: &[String], current: Option<usize>) -> Value {
+    let slides: Vec<Value> = (section.first..=section.last)
+        .filter(|number| !section.sections.iter().any(|s| s.contains(*number)))
+        .map(|number| toc_slide(number, titles, current))
+        .collect();
 
-    pages
+    context! {
+        key => section.key.clone(),
+        title => section.title.clone(),
+        index => section.index,
+        depth => section.depth,
+        first => section.first,
+        last => section.last,
+        count => section.count,
+        active => current.is_some_and(|number| section.contains(number)),
+        slides => slides,
+        sections => section
+            .sections
+            .iter()
+            .map(|section| toc_section(section, titles, current))
+            .collect::<Vec<_>>(),
+    }
 }
 
-pub fn render_overview(
-    layouts: &HashMap<String, String>,
-    translations: &HashMap<String, String>,
-    slides: &[Slide],
-) -> Option<String> {
-    if !layouts.contains_key("overview.html") {
-        warn!("No overview.html template found - skipping overview page");
-        return None;
+fn toc_slide(number: usize, titles: &[String], current: Option<usize>) -> Value {
+    context! {
+        number => number,
+        title => titles.get(number - 1).cloned().unwrap_or_default(),
+        active => current == Some(number),
     }
+}
 
-    let total_slides = slides.len();
-    let env = setup_environment(layouts, translations);
+/// Everything the pages of a deck are rendered with besides the slides:
+/// templates, translations, the language of the build and global variables
+#[derive(Debug, Clone, Default)]
+pub struct RenderContext {
+    pub layouts: HashMap<String, String>,
+    pub translations: HashMap<String, String>,
+    pub locale: Locale,
+    pub globals: Globals,
+    /// Files of `slides/data` for `data.<name>` and `load_data()`
+    pub data: Arc<DataFiles>,
+}
 
-    let sections: Vec<Value> = slides
-        .iter()
-        .enumerate()
-        .chunk_by(|(_, slide)| slide.section_key.clone())
-        .into_iter()
-        .map(|(key, group)| {
-            let slides: Vec<Value> = group
-                .map(|(index, slide)| {
-                    context! {
-                        number => index + 1,
-                        template => slide.template.clone(),
-                    }
-                })
-                .collect();
-            context! {
-                key => key.unwrap_or_default(),
-                slides => slides,
-            }
-        })
-        .collect();
+impl RenderContext {
+    pub fn new(layouts: HashMap<String, String>, translations: HashMap<String, String>) -> Self {
+        Self {
+            layouts,
+            translations,
+            ..Default::default()
+        }
+    }
 
-    // Build context
-    let ctx = context! {
-        app => context! {
-            total => total_slides,
-            first => 1,
-            last => total_slides,
-        },
-        sections => sections,
-    };
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
 
-    try_render(&env, "overview.html", &ctx)
-}
+    pub fn with_globals(mut self, globals: Globals) -> Self {
+        self.globals = globals;
+        self
+    }
 
-pub fn render_presenter_pages(
-    layouts: &HashMap<String, String>,
-    translations: &HashMap<String, String>,
-    slides: &[Slide],
-) -> HashMap<String, String> {
-    let mut pages = HashMap::new();
+    pub fn with_data(mut self, data: DataFiles) -> Self {
+        self.data = Arc::new(data);
+        self
+    }
 
-    if !layouts.contains_key("presenter.html") {
-        warn!("No presenter.html template found - skipping presenter pages");
-        return pages;
+    fn environment(&self) -> Environment<'static> {
+        setup_environment(&self.layouts, &self.translations, &self.globals, &self.data)
     }
 
-    let total = slides.len();
-    let env = setup_environment(layouts, translations);
+    /// Evaluate each slide's `title` block and store it on the slide
+    pub fn assign_slide_titles(&self, slides: &mut [Slide]) {
+        let titles = slide_titles(&self.environment(), slides);
+        for (slide, title) in slides.iter_mut().zip(titles) {
+            slide.title = Some(title);
+        }
+    }
 
-    for (index, slide) in slides.iter().enumerate() {
-        let nav = SlideNav::new(index, total);
-        let notes = extract_slide_notes(&env, &slide.template, nav.current, total);
-        let output_path = format!("presenter/slide-{}.html", nav.current);
+    /// Slide pages (slide-N.html)
+    pub fn deck_pages(&self, slides: &[Slide]) -> HashMap<String, String> {
+        let locale = &self.locale;
+        let mut pages = HashMap::new();
+        let total = slides.len();
+        let env = self.environment();
+        let sections = section_tree(slides, &self.translations);
+        let titles = slide_titles(&env, slides);
+
+        for (index, slide) in slides.iter().enumerate() {
+            let nav = SlideNav::new(index, total);
+
+            let ctx = context! {
+                app => context! {
+                    sections => sections.clone(),
+                    lang => locale.lang.clone(),
+                    dir => locale.dir(),
+                    locales => locales_context(locale),
+                    ..nav.app_context()
+                },
+                slide => context! {
+                    title => titles[index].clone(),
+                    section => section_context(&sections, nav.current),
+                    audio => slide.audio.clone(),
+                    autoAdvance => slide.auto_advance,
+                    ..nav.to_context()
+                },
+                _output_path => format!("slide-{}.html", nav.current),
+                _toc => toc_context(&sections, &titles, Some(nav.current)),
+                // Legacy uppercase variables for backwards compatibility
+                CURRENT => nav.current,
+                TOTAL => nav.total,
+                PREV => nav.prev,
+                NEXT => nav.next,
+            };
+
+            let Ok(tmpl) = env.get_template(&slide.template) else {
+                warn!("Template '{}' not found", slide.template);
+                continue;
+            };
+
+            match tmpl.render(&ctx) {
+                Ok(html) => {
+                    pages.insert(format!("slide-{}.html", nav.current), html);
+                }
+                Err(e) => warn!("Error rendering slide {}: {}", nav.current, e),
+            }
+        }
 
+        pages
+    }
+
+    /// Render the overview page; `thumbnails` tells whether slide thumbnails are
+    /// available, otherwise `slide.thumbnail` is none
+    pub fn overview(&self, slides: &[Slide], thumbnails: bool) -> Option<String> {
+        let locale = &self.locale;
+        if !self.layouts.contains_key("overview.html") {
+            warn!("No overview.html template found - skipping overview page");
+            return None;
+        }
+
+        let total_slides = slides.len();
+        let env = self.environment();
+        let tree = section_tree(slides, &self.translations);
+        let titles = slide_titles(&env, slides);
+
+        let sections: Vec<Value> = slides
+            .iter()
+            .enumerate()
+            .chunk_by(|(_, slide)| slide.section_key())
+            .into_iter()
+            .map(|(key, group)| {
+                let slides: Vec<(usize, &Slide)> = group.collect();
+                let first = slides
+                    .first()
+                    .map(|(index, _)| index + 1)
+                    .unwrap_or_default();
+                let path = section_path(&tree, first);
+                let slides: Vec<Value> = slides
+                    .into_iter()
+                    .map(|(index, slide)| {
+                        context! {
+                            number => index + 1,
+                            template => slide.template.clone(),
+                            title => titles[index].clone(),
+                            thumbnail => thumbnails.then(|| ThumbnailSize::Small.path(index + 1)),
+                        }
+                    })
+                    .collect();
+                context! {
+                    key => key.unwrap_or_default(),
+                    title => path.last().map(|section| section.title.clone()),
+                    depth => path.last().map(|section| section.depth),
+                    slides => slides,
+                }
+            })
+            .collect();
+
+        // Build context
         let ctx = context! {
-            app => nav.app_context(),
-            slide => nav.to_context(),
-            notes => notes,
-            _output_path => output_path,
+            app => context! {
+                total => total_slides,
+                first => 1,
+                last => total_slides,
+                sections => tree,
+                thumbnails => thumbnails,
+                lang => locale.lang.clone(),
+                dir => locale.dir(),
+                locales => locales_context(locale),
+            },
+            sections => sections,
+            _output_path => "overview.html",
+            _toc => toc_context(&tree, &titles, None),
         };
 
-        match try_render(&env, "presenter.html", &ctx) {
-            Some(html) => {
-                pages.insert(format!("slide-{}.html", nav.current), html);
+        try_render(&env, "overview.html", &ctx)
+    }
+
+    /// Presenter pages (presenter/slide-N.html)
+    pub fn presenter_pages(&self, slides: &[Slide]) -> HashMap<String, String> {
+        if !self.layouts.contains_key("presenter.html") {
+            warn!("No presenter.html template found - skipping presenter pages");
+            return HashMap::new();
+        }
+
+        self.speaker_pages(slides, "presenter")
+    }
+
+    /// Remote control pages (remote/slide-N.html) for `slides serve --remote`,
+    /// rendered from remote.html with the presenter context
+    pub fn remote_pages(&self, slides: &[Slide]) -> HashMap<String, String> {
+        if !self.layouts.contains_key("remote.html") {
+            return HashMap::new();
+        }
+
+        self.speaker_pages(slides, "remote")
+    }
+
+    /// Per-slide pages for the speaker, rendered from `<view>.html` into `<view>/`
+    fn speaker_pages(&self, slides: &[Slide], view: &str) -> HashMap<String, String> {
+        let locale = &self.locale;
+        let mut pages = HashMap::new();
+        let template = format!("{view}.html");
+
+        let total = slides.len();
+        let env = self.environment();
+        let sections = section_tree(slides, &self.translations);
+        let titles = slide_titles(&env, slides);
+
+        // Schedule: second of the talk at which each slide is planned to start
+        let starts: Vec<f32> = slides
+            .iter()
+            .scan(0.0, |elapsed, slide| {
+                let start = *elapsed;
+                *elapsed += slide.planned_duration.unwrap_or(0.0);
+                Some(start)
+            })
+            .collect();
+        let target_time = slides
+            .iter()
+            .any(|slide| slide.planned_duration.is_some())
+            .then(|| {
+                slides
+                    .iter()
+                    .filter_map(|slide| slide.planned_duration)
+                    .sum::<f32>()
+            });
+
+        let neighbour = |number: usize| {
+            context! {
+                number => number,
+                title => titles[number - 1].clone(),
+                fragments => slides[number - 1].fragments,
+                url => format!("../slide-{number}.html"),
+                thumbnail => format!("../{}", ThumbnailSize::Medium.path(number)),
+            }
+        };
+
+        for (index, slide) in slides.iter().enumerate() {
+            let nav = SlideNav::new(index, total);
+            let notes = extract_slide_notes(&env, &slide.template, nav.current, total);
+            let output_path = format!("{view}/slide-{}.html", nav.current);
+
+            let ctx = context! {
+                app => context! {
+                    sections => sections.clone(),
+                    targetTime => target_time,
+                    lang => locale.lang.clone(),
+                    dir => locale.dir(),
+                    locales => locales_context(locale),
+                    ..nav.app_context()
+                },
+                slide => context! {
+                    title => titles[index].clone(),
+                    section => section_context(&sections, nav.current),
+                    fragments => slide.fragments,
+                    url => format!("../slide-{}.html", nav.current),
+                    planned => slide.planned_duration,
+                    plannedStart => target_time.map(|_| starts[index]),
+                    plannedEnd => target_time.map(|_| starts[index] + slide.planned_duration.unwrap_or(0.0)),
+                    ..nav.to_context()
+                },
+                prev_slide => nav.prev.map(neighbour),
+                next_slide => nav.next.map(neighbour),
+                notes => notes,
+                _output_path => output_path,
+                _toc => toc_context(&sections, &titles, Some(nav.current)),
+            };
+
+            match try_render(&env, &template, &ctx) {
+                Some(html) => {
+                    pages.insert(format!("slide-{}.html", nav.current), html);
+                }
+                None => warn!("Error rendering {view} page for slide {}", nav.current),
             }
-            None => warn!("Error rendering presenter page for slide {}", nav.current),
         }
+
+        pages
     }
 
-    pages
+    /// Print page with all slides (print.html)
+    pub fn print(&self, slides: &[Slide]) -> Option<String> {
+        let locale = &self.locale;
+        if !self.layouts.contains_key("print.html") {
+            return None;
+        }
+
+        let total = slides.len();
+        let env = self.environment();
+        let titles = slide_titles(&env, slides);
+
+        let slides_ctx: Vec<Value> = slides
+            .iter()
+            .enumerate()
+            .map(|(index, slide)| {
+                context! {
+                    number => index + 1,
+                    template => slide.template.clone(),
+                    title => titles[index].clone(),
+                }
+            })
+            .collect();
+
+        let ctx = context! {
+            app => context! {
+                total => total,
+                first => 1,
+                last => total,
+                lang => locale.lang.clone(),
+                dir => locale.dir(),
+                locales => locales_context(locale),
+            },
+            slides => slides_ctx,
+            _output_path => "print.html",
+        };
+
+        try_render(&env, "print.html", &ctx)
+    }
 }
 
-pub fn render_print(
+pub fn render_deck_pages(
+    slides: &[Slide],
+    layouts: &HashMap<String, String>,
+    translations: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    RenderContext::new(layouts.clone(), translations.clone()).deck_pages(slides)
+}
+
+/// Render the overview page; `thumbnails` tells whether slide thumbnails are
+/// available, otherwise `slide.thumbnail` is none
+pub fn render_overview(
     layouts: &HashMap<String, String>,
     translations: &HashMap<String, String>,
     slides: &[Slide],
+    thumbnails: bool,
 ) -> Option<String> {
-    if !layouts.contains_key("print.html") {
-        return None;
-    }
-
-    let total = slides.len();
-    let env = setup_environment(layouts, translations);
+    RenderContext::new(layouts.clone(), translations.clone()).overview(slides, thumbnails)
+}
 
-    let slides_ctx: Vec<Value> = slides
-        .iter()
-        .enumerate()
-        .map(|(index, slide)| {
-            context! {
-                number => index + 1,
-                template => slide.template.clone(),
-            }
-        })
-        .collect();
+pub fn render_presenter_pages(
+    layouts: &HashMap<String, String>,
+    translations: &HashMap<String, String>,
+    slides: &[Slide],
+) -> HashMap<String, String> {
+    RenderContext::new(layouts.clone(), translations.clone()).presenter_pages(slides)
+}
 
-    let ctx = context! {
-        app => context! {
-            total => total,
-            first => 1,
-            last => total,
-        },
-        slides => slides_ctx,
-    };
+/// Remote control pages (remote/slide-N.html) for `slides serve --remote`
+pub fn render_remote_pages(
+    layouts: &HashMap<String, String>,
+    translations: &HashMap<String, String>,
+    slides: &[Slide],
+) -> HashMap<String, String> {
+    RenderContext::new(layouts.clone(), translations.clone()).remote_pages(slides)
+}
 
-    try_render(&env, "print.html", &ctx)
+pub fn render_print(
+    layouts: &HashMap<String, String>,
+    translations: &HashMap<String, String>,
+    slides: &[Slide],
+) -> Option<String> {
+    RenderContext::new(layouts.clone(), translations.clone()).print(slides)
 }
 
 #[cfg(test)]
@@ -1,26 +1,90 @@
 //! Translation module: Simple i18n for slides
 
 use crate::error::{Error, Result};
+use crate::infrastructure::source::{FileSource, TemplateSource};
+use serde::Serialize;
 use serde_yaml::Value;
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Primary language subtags written right to left
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "ha", "he", "khw", "ks", "ps", "sd", "ug", "ur", "yi",
+];
+
+/// Language of a build and the languages its project is translated into
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Locale {
+    pub lang: String,
+    /// Locale codes of the files in `slides/translations`, sorted
+    pub locales: Vec<String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new("en", Vec::new())
+    }
+}
+
+impl Locale {
+    pub fn new(lang: &str, locales: Vec<String>) -> Self {
+        Self {
+            lang: lang.to_string(),
+            locales,
+        }
+    }
+
+    /// "rtl" for right-to-left languages, otherwise "ltr"
+    pub fn dir(&self) -> &'static str {
+        text_direction(&self.lang)
+    }
+}
+
+/// "rtl" for right-to-left languages such as "ar" or "he-IL", otherwise "ltr"
+pub fn text_direction(lang: &str) -> &'static str {
+    let primary = lang.split(['-', '_']).next().unwrap_or_default();
+    if RTL_LANGUAGES.contains(&primary.to_ascii_lowercase().as_str()) {
+        "rtl"
+    } else {
+        "ltr"
+    }
+}
+
+/// Locales with a translation file in `dir` of a source, e.g. ["de", "en"]
+pub fn available_locales(source: &dyn TemplateSource, dir: &str) -> Result<Vec<String>> {
+    Ok(source
+        .list(dir)?
+        .iter()
+        .filter(|file| !file.contains('/'))
+        .filter_map(|file| file.strip_suffix(".yaml"))
+        .map(str::to_string)
+        .collect())
+}
 
 /// Load and flatten translations: { intro: { title: "Hello" } } -> { "intro.title": "Hello" }
 pub fn load(locale: &str, translations_dir: &Path) -> Result<HashMap<String, String>> {
-    let path = translations_dir.join(format!("{}.yaml", locale));
+    load_source(&FileSource::new(translations_dir), "", locale)
+}
 
-    if !path.exists() {
-        return Err(Error::TranslationNotFound(path));
-    }
+/// Load and flatten the translations of a locale from `dir` of a source
+pub fn load_source(
+    source: &dyn TemplateSource,
+    dir: &str,
+    locale: &str,
+) -> Result<HashMap<String, String>> {
+    let file = format!("{locale}.yaml");
+    let path = if dir.is_empty() {
+        file
+    } else {
+        format!("{}/{file}", dir.trim_end_matches('/'))
+    };
 
-    let content = fs::read_to_string(&path).map_err(|e| Error::FileRead {
-        path: path.clone(),
-        source: e,
-    })?;
+    let Some(content) = source.read(&path)? else {
+        return Err(Error::TranslationNotFound(PathBuf::from(path)));
+    };
 
     let data: Value = serde_yaml::from_str(&content).map_err(|e| Error::YamlParse {
-        path: path.clone(),
+        path: PathBuf::from(&path),
         message: e.to_string(),
     })?;
 
@@ -1,7 +1,7 @@
 //! Utility functions shared across modules
 
 use crate::error::{Error, Result};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -60,20 +60,160 @@ pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn write_pages(dir: &Path, pages: &HashMap<String, String>) -> Result<()> {
-    if pages.is_empty() {
-        return Ok(());
+/// Write files keyed by their path relative to `dir`, creating directories as needed
+pub fn write_files(dir: &Path, files: &BTreeMap<String, String>) -> Result<()> {
+    for (name, content) in files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::CreateDir {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        fs::write(&path, content).map_err(|e| Error::FileWrite { path, source: e })?;
     }
 
-    fs::create_dir_all(dir).map_err(|e| Error::CreateDir {
-        path: dir.to_path_buf(),
-        source: e,
-    })?;
+    Ok(())
+}
 
-    for (name, html) in pages {
-        let path = dir.join(name);
-        fs::write(&path, html).map_err(|e| Error::FileWrite { path, source: e })?;
+/// Parse a duration such as "90", "45s", "2m", "1m30s" or "1h5m" into seconds
+pub fn parse_duration(text: &str) -> Option<f32> {
+    let text = text.trim();
+    if let Ok(seconds) = text.parse::<f32>() {
+        return (seconds >= 0.0).then_some(seconds);
     }
 
-    Ok(())
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    for c in text.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' | 's' => {
+                let value: f32 = std::mem::take(&mut number).parse().ok()?;
+                seconds += value
+                    * match c {
+                        'h' => 3600.0,
+                        'm' => 60.0,
+                        _ => 1.0,
+                    };
+            }
+            c if c.is_whitespace() => {}
+            _ => return None,
+        }
+    }
+
+    (number.is_empty() && !text.is_empty()).then_some(seconds)
+}
+
+/// Year, month, day, hour, minute and second of a UTC time
+pub type DateTime = (i64, u32, u32, u32, u32, u32);
+
+/// Current time as "2026-10-19T08:30:00Z"
+pub fn utc_now() -> String {
+    let (year, month, day, hour, minute, second) = utc_now_parts();
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+/// Current time broken down into its date and time fields
+pub fn utc_now_parts() -> DateTime {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let time = (secs % 86_400) as u32;
+    (year, month, day, time / 3600, time % 3600 / 60, time % 60)
+}
+
+/// Convert days since 1970-01-01 to (year, month, day) in the Gregorian calendar
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Split CSV content into records of fields (RFC 4180): quoted fields may
+/// contain commas, quotes ("") and line breaks; records end with LF or CRLF
+pub fn csv_records(content: &str) -> std::result::Result<Vec<Vec<String>>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Field names of a struct as its derived `Deserialize` knows them, so key
+/// checks follow the struct instead of a copied list
+pub fn struct_fields<'de, T: serde::Deserialize<'de>>() -> &'static [&'static str] {
+    use serde::de::{self, Visitor};
+
+    struct Fields<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> de::Deserializer<'de> for Fields<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("fields recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(Fields(&mut fields));
+    fields
 }
@@ -2,8 +2,11 @@
 //!
 //! Tests the full build process using fixtures from tests/fixtures/files/
 
+use image::codecs::gif::GifDecoder;
+use image::AnimationDecoder;
 use serial_test::serial;
-use slides_rs::model::Project;
+use slides_rs::infrastructure::video::VideoFormat;
+use slides_rs::model::{Manifest, PdfExport, Project, VideoExport};
 use std::env;
 use std::fs;
 use std::path::Path;
@@ -55,17 +58,17 @@ fn test_full_build_workflow() {
 
         // Should have slides (fixtures have 3 slides)
         assert!(
-            Path::new("output/slide-1.html").exists(),
+            Path::new("output/en/slide-1.html").exists(),
             "slide-1.html should exist"
         );
         assert!(
-            Path::new("output/slide-3.html").exists(),
+            Path::new("output/en/slide-3.html").exists(),
             "slide-3.html should exist"
         );
 
         // Verify HTML content
         let slide1_content =
-            fs::read_to_string("output/slide-1.html").expect("Should read slide-1.html");
+            fs::read_to_string("output/en/slide-1.html").expect("Should read slide-1.html");
 
         assert!(
             slide1_content.contains("<!DOCTYPE html>") || slide1_content.contains("<html"),
@@ -83,7 +86,7 @@ fn test_build_generates_correct_slide_count() {
         deck.build_html().expect("build_html() should succeed");
 
         // Count generated slide files
-        let slide_count = fs::read_dir("output")
+        let slide_count = fs::read_dir("output/en")
             .expect("Should read output dir")
             .filter_map(|e| e.ok())
             .filter(|e| {
@@ -108,7 +111,7 @@ fn test_build_includes_navigation() {
         deck.build_html().expect("build_html() should succeed");
 
         // Middle slide (slide 2) should have both prev and next
-        let slide2 = fs::read_to_string("output/slide-2.html").unwrap();
+        let slide2 = fs::read_to_string("output/en/slide-2.html").unwrap();
         assert!(
             slide2.contains("slide-1.html"),
             "Slide 2 should link to previous"
@@ -130,14 +133,162 @@ fn test_build_html_skips_thumbnails() {
 
         // build_html() should NOT generate thumbnails
         assert!(
-            !Path::new("output/thumbnails").exists(),
+            !Path::new("output/en/thumbnails").exists(),
             "build_html() should skip thumbnail generation"
         );
 
         // But HTML should still be generated
         assert!(
-            Path::new("output/slide-1.html").exists(),
+            Path::new("output/en/slide-1.html").exists(),
             "HTML slides should still be generated"
         );
     });
 }
+
+#[test]
+#[serial]
+fn test_build_writes_manifest() {
+    with_fixtures(|| {
+        let project = Project::current().expect("Should open project");
+        let mut deck = project.deck("default", "en");
+        deck.build_html().expect("build_html() should succeed");
+
+        let manifest =
+            Manifest::load(Path::new("output/en")).expect("build_html() should write a manifest");
+
+        assert_eq!(manifest.deck, "default");
+        assert_eq!(manifest.total, 3);
+        assert_eq!(manifest.slides[0].number, 1);
+        assert_eq!(manifest.slides[0].template, "slides/01-intro.html");
+        assert_eq!(manifest.titles().len(), 3);
+    });
+}
+
+#[test]
+#[serial]
+fn test_export_for_recipients_workflow() {
+    let thumbnails = Path::new("tests/fixtures/thumbnails")
+        .canonicalize()
+        .expect("Thumbnail fixtures should exist");
+
+    with_fixtures(move || {
+        let project = Project::current().expect("Should open project");
+        project
+            .deck("default", "en")
+            .build_html()
+            .expect("build_html() should succeed");
+
+        fs::create_dir_all("output/en/thumbnails").unwrap();
+        for number in 1..=3 {
+            let name = format!("slide-{number}.png");
+            fs::copy(
+                thumbnails.join(&name),
+                Path::new("output/en/thumbnails").join(&name),
+            )
+            .unwrap();
+        }
+        fs::write(
+            "output/recipients.csv",
+            "name,password\nACME Corp.,acme-secret\nGlobex,\n",
+        )
+        .unwrap();
+
+        let export = PdfExport {
+            output_path: "output/presentation.pdf".to_string(),
+            passwords_out: Some("output/passwords.json".to_string()),
+            ..Default::default()
+        };
+        project
+            .export_pdf_for_recipients(&export, "output/recipients.csv")
+            .expect("Export for recipients should succeed");
+
+        let passwords: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string("output/passwords.json").unwrap()).unwrap();
+        let entries = passwords.as_array().expect("Passwords should be a list");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["recipient"], "ACME Corp.");
+        assert_eq!(entries[0]["file"], "output/presentation-acme-corp.pdf");
+        assert_eq!(entries[0]["user_password"], "acme-secret");
+        assert_eq!(entries[1]["file"], "output/presentation-globex.pdf");
+
+        let globex_password = entries[1]["user_password"].as_str().unwrap();
+        assert_eq!(globex_password.len(), 16, "Password should be generated");
+
+        for (file, password) in [
+            ("output/presentation-acme-corp.pdf", "acme-secret"),
+            ("output/presentation-globex.pdf", globex_password),
+        ] {
+            let mut document = lopdf::Document::load(file).expect("PDF should exist");
+            assert!(document.is_encrypted(), "{file} should be encrypted");
+            document
+                .decrypt(password)
+                .unwrap_or_else(|e| panic!("{file} should open with its password: {e}"));
+        }
+    });
+}
+
+#[test]
+#[serial]
+fn test_export_gif_workflow() {
+    let thumbnails = Path::new("tests/fixtures/thumbnails")
+        .canonicalize()
+        .expect("Thumbnail fixtures should exist");
+
+    with_fixtures(move || {
+        let project = Project::current().expect("Should open project");
+        project
+            .deck("default", "en")
+            .build_html()
+            .expect("build_html() should succeed");
+
+        fs::create_dir_all("output/en/thumbnails").unwrap();
+        for number in 1..=3 {
+            let name = format!("slide-{number}.png");
+            fs::copy(
+                thumbnails.join(&name),
+                Path::new("output/en/thumbnails").join(&name),
+            )
+            .unwrap();
+        }
+
+        project
+            .export_video(&VideoExport {
+                deck: "default".to_string(),
+                lang: "en".to_string(),
+                format: VideoFormat::Gif,
+                output_path: "output/presentation.gif".to_string(),
+                duration: Some(1.5),
+                timings: None,
+            })
+            .expect("GIF export should succeed");
+
+        let file = fs::File::open("output/presentation.gif").expect("GIF should exist");
+        let decoder = GifDecoder::new(std::io::BufReader::new(file)).unwrap();
+        let delays: Vec<(u32, u32)> = decoder
+            .into_frames()
+            .map(|frame| frame.unwrap().delay().numer_denom_ms())
+            .collect();
+        assert_eq!(delays, vec![(1500, 1); 3]);
+
+        // Recorded presenter timings win over the given duration
+        fs::write("output/timings.json", r#"{"2": 4.25}"#).unwrap();
+        project
+            .export_video(&VideoExport {
+                deck: "default".to_string(),
+                lang: "en".to_string(),
+                format: VideoFormat::Gif,
+                output_path: "output/timed.gif".to_string(),
+                duration: Some(1.5),
+                timings: Some("output/timings.json".into()),
+            })
+            .expect("GIF export with timings should succeed");
+
+        let file = fs::File::open("output/timed.gif").expect("GIF should exist");
+        let decoder = GifDecoder::new(std::io::BufReader::new(file)).unwrap();
+        let delays: Vec<(u32, u32)> = decoder
+            .into_frames()
+            .map(|frame| frame.unwrap().delay().numer_denom_ms())
+            .collect();
+        assert_eq!(delays, vec![(1500, 1), (4250, 1), (1500, 1)]);
+    });
+}
@@ -173,7 +173,7 @@ fn test_init_demo_deck_renders_correctly() {
 
     // Verify all 6 demo slides were generated
     for i in 1..=6 {
-        let slide_path = format!("output/slide-{}.html", i);
+        let slide_path = format!("output/en/slide-{}.html", i);
         assert!(
             Path::new(&slide_path).exists(),
             "Demo slide {} should exist",
@@ -183,16 +183,16 @@ fn test_init_demo_deck_renders_correctly() {
 
     // Verify overview page
     assert!(
-        Path::new("output/overview.html").exists(),
+        Path::new("output/en/overview.html").exists(),
         "Overview page should exist"
     );
     // Verify presenter pages (now in presenter/ subdirectory)
     assert!(
-        Path::new("output/presenter").is_dir(),
+        Path::new("output/en/presenter").is_dir(),
         "Presenter directory should exist"
     );
     for i in 1..=6 {
-        let presenter_path = format!("output/presenter/slide-{}.html", i);
+        let presenter_path = format!("output/en/presenter/slide-{}.html", i);
         assert!(
             Path::new(&presenter_path).exists(),
             "Presenter page for slide {} should exist",
@@ -202,12 +202,12 @@ fn test_init_demo_deck_renders_correctly() {
 
     // Verify CSS was copied
     assert!(
-        Path::new("output/assets/css/style.css").exists(),
+        Path::new("output/en/assets/css/style.css").exists(),
         "CSS should be copied to output"
     );
 
     // Check first slide content includes translated text
-    let slide1 = fs::read_to_string("output/slide-1.html").expect("Should read slide 1");
+    let slide1 = fs::read_to_string("output/en/slide-1.html").expect("Should read slide 1");
     assert!(
         slide1.contains("Welcome to Slides RS"),
         "Slide 1 should contain welcome text"
@@ -249,11 +249,11 @@ fn test_sectioned_deck_config() {
     );
 
     // Should have 4 slides
-    assert!(Path::new("output/slide-1.html").exists());
-    assert!(Path::new("output/slide-2.html").exists());
-    assert!(Path::new("output/slide-3.html").exists());
-    assert!(Path::new("output/slide-4.html").exists());
-    assert!(!Path::new("output/slide-5.html").exists());
+    assert!(Path::new("output/en/slide-1.html").exists());
+    assert!(Path::new("output/en/slide-2.html").exists());
+    assert!(Path::new("output/en/slide-3.html").exists());
+    assert!(Path::new("output/en/slide-4.html").exists());
+    assert!(!Path::new("output/en/slide-5.html").exists());
 
     env::set_current_dir(&original_dir).unwrap();
 }
@@ -279,12 +279,21 @@ fn test_german_translations() {
     );
 
     // Check first slide has German text
-    let slide1 = fs::read_to_string("output/slide-1.html").expect("Should read slide 1");
+    let slide1 = fs::read_to_string("output/de/slide-1.html").expect("Should read slide 1");
     assert!(
         slide1.contains("Willkommen bei Slides RS"),
         "Slide 1 should contain German welcome text"
     );
 
+    // Each language has its own build, and the language links between them resolve
+    project
+        .deck("default", "en")
+        .build_html()
+        .expect("English build should succeed");
+    assert!(Path::new("output/de/slide-1.html").exists());
+    assert!(slide1.contains(r#"hreflang="en" href="../en/slide-1.html""#));
+    assert!(Path::new("output/de").join("../en/slide-1.html").exists());
+
     env::set_current_dir(&original_dir).unwrap();
 }
 
@@ -302,7 +311,7 @@ fn test_overview_page_rendering() {
     deck.build_html().expect("Build should succeed");
 
     // Check overview page exists and has content
-    let overview = fs::read_to_string("output/overview.html").expect("Should read overview");
+    let overview = fs::read_to_string("output/en/overview.html").expect("Should read overview");
     assert!(
         overview.contains("slide-1.html"),
         "Overview should link to slide 1"
@@ -330,13 +339,13 @@ fn test_presenter_page_rendering() {
 
     // Check presenter directory exists
     assert!(
-        Path::new("output/presenter").is_dir(),
+        Path::new("output/en/presenter").is_dir(),
         "Presenter directory should exist"
     );
 
     // Check first presenter page exists and has notes
     let presenter =
-        fs::read_to_string("output/presenter/slide-1.html").expect("Should read presenter");
+        fs::read_to_string("output/en/presenter/slide-1.html").expect("Should read presenter");
     assert!(
         presenter.contains("Speaker Notes") || presenter.contains("notes"),
         "Presenter should have notes section"
@@ -344,7 +353,7 @@ fn test_presenter_page_rendering() {
 
     // Check navigation works (slide 2 should have prev/next)
     let presenter2 =
-        fs::read_to_string("output/presenter/slide-2.html").expect("Should read presenter 2");
+        fs::read_to_string("output/en/presenter/slide-2.html").expect("Should read presenter 2");
     assert!(
         presenter2.contains("slide-1.html"),
         "Presenter page 2 should link to slide 1"
@@ -0,0 +1,85 @@
+//! Workflow test for using slides as a library: open a project by path,
+//! build it and collect progress without touching the working directory
+
+use slides_rs::model::{BuildOptions, Project};
+use slides_rs::progress::ProgressEvent;
+use slides_rs::Error;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+#[test]
+fn test_build_project_by_path_with_progress() {
+    let temp_dir = TempDir::new().unwrap();
+    Project::init_at(temp_dir.path()).expect("init should succeed");
+    fs::write(
+        temp_dir.path().join("slides/templates/intro.html"),
+        "<h1>Intro</h1>",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("decks.yaml"),
+        "default:\n  - intro.html\n",
+    )
+    .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let collected = Arc::clone(&events);
+    let project = Project::open(temp_dir.path())
+        .unwrap()
+        .with_progress(move |event: &ProgressEvent| collected.lock().unwrap().push(event.clone()));
+
+    let report = project
+        .build("default", &BuildOptions::default().with_thumbnails(false))
+        .expect("build should succeed");
+
+    assert_eq!(report.deck, "default");
+    assert_eq!(report.lang, "en");
+    assert_eq!(report.slides, 1);
+    // Slide, presenter, remote, overview and print pages and the manifest
+    let written = fs::read_dir(&report.output_dir)
+        .unwrap()
+        .chain(fs::read_dir(report.output_dir.join("presenter")).unwrap())
+        .chain(fs::read_dir(report.output_dir.join("remote")).unwrap())
+        .filter(|entry| entry.as_ref().unwrap().path().is_file())
+        .count();
+    assert!(report.files > report.slides);
+    assert_eq!(report.files, written);
+    assert_eq!(report.thumbnails, None);
+    assert_eq!(report.output_dir, temp_dir.path().join("output").join("en"));
+    assert!(report.output_dir.join("slide-1.html").exists());
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        events.first(),
+        Some(&ProgressEvent::BuildStarted {
+            deck: "default".to_string(),
+            lang: "en".to_string()
+        })
+    );
+    assert!(events.contains(&ProgressEvent::SlidesLoaded {
+        deck: "default".to_string(),
+        count: 1
+    }));
+    assert!(events.contains(&ProgressEvent::FilesWritten));
+    assert!(!events.contains(&ProgressEvent::ThumbnailsStarted));
+    assert!(events.contains(&ProgressEvent::SlideRendered {
+        number: 1,
+        template: "intro.html".to_string()
+    }));
+
+    // Events serialize with their name as `event`, e.g. for `--message-format json`
+    assert_eq!(
+        serde_json::to_value(&events[0]).unwrap(),
+        serde_json::json!({ "event": "build_started", "deck": "default", "lang": "en" })
+    );
+}
+
+#[test]
+fn test_open_missing_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing");
+
+    let err = Project::open(&missing).unwrap_err();
+    assert!(matches!(err, Error::ProjectNotFound(path) if path == missing));
+}
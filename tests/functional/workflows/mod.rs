@@ -4,3 +4,4 @@
 
 mod build_test;
 mod init_build_test;
+mod library_api_test;
@@ -3,10 +3,17 @@
 //! Tests module interactions without CLI
 
 mod integration {
+    pub mod a11y;
+    pub mod data;
     pub mod error;
     pub mod export;
     pub mod import;
     pub mod io;
+    pub mod lint;
     pub mod parser;
     pub mod rendering;
+    pub mod server;
+    pub mod source;
+    pub mod thumbnails;
+    pub mod video;
 }
@@ -0,0 +1,141 @@
+//! Accessibility check integration tests
+//!
+//! Tests the checks of rendered slide pages and `Project::check_a11y` on a
+//! built project; contrast needs a browser and is left out
+
+use slides_rs::model::{A11yOptions, Project};
+use slides_rs::services::a11y;
+use slides_rs::services::lint::{Issue, Severity};
+use std::fs;
+use tempfile::TempDir;
+
+fn messages(issues: Vec<Issue>) -> Vec<(&'static str, Option<usize>)> {
+    issues
+        .into_iter()
+        .map(|issue| (issue.rule, issue.line))
+        .collect()
+}
+
+#[test]
+fn test_check_lang() {
+    let page = |html: &str, lang: &str| messages(a11y::check_lang("page.html", html, lang));
+
+    assert_eq!(
+        page("<html lang=\"de-AT\"><body></body></html>", "de"),
+        vec![]
+    );
+    assert_eq!(page("<html lang=en>", "en_US"), vec![]);
+    assert_eq!(page("<html lang=\"en\">", "de"), vec![("lang", Some(1))]);
+    assert_eq!(page("\n<html>", "en"), vec![("lang", Some(2))]);
+    assert_eq!(page("<html lang=\"\">", "en"), vec![("lang", Some(1))]);
+    assert_eq!(page("<p>Fragment</p>", "en"), vec![("lang", None)]);
+}
+
+#[test]
+fn test_check_headings() {
+    let html =
+        "<h1>Title</h1>\n<h2>Part</h2>\n<h4>Skipped</h4>\n<h2>Back</h2>\n<h3>Fine</h3>\n<header>";
+    let issues = a11y::check_headings("page.html", html);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].line, Some(3));
+    assert_eq!(
+        issues[0].message,
+        "<h4> follows <h2>, skipping a heading level"
+    );
+}
+
+#[test]
+fn test_check_keyboard() {
+    let html = "<button onclick=\"next()\">Next</button>\n\
+                <div onclick=\"next()\">Next</div>\n\
+                <span role=\"button\" tabindex=\"0\">Ok</span>\n\
+                <span role=\"button\">Missing</span>\n\
+                <a href=\"#2\" onclick=\"go(2)\">Two</a>\n\
+                <a onclick=\"go(3)\">Three</a>\n\
+                <button tabindex=\"-1\" onclick=\"hide()\">Hidden</button>\n\
+                <a name=\"anchor\"></a>";
+
+    assert_eq!(
+        messages(a11y::check_keyboard("page.html", html)),
+        vec![
+            ("keyboard", Some(2)),
+            ("keyboard", Some(4)),
+            ("keyboard", Some(6)),
+            ("keyboard", Some(7)),
+        ]
+    );
+}
+
+#[test]
+fn test_checks_skip_comments_and_scripts() {
+    let html = "<html lang=\"en\"><body>\n\
+                <!-- <h4>Draft</h4> <div onclick=\"go()\"></div> -->\n\
+                <script>const html = '<h5>x</h5><span role=\"button\">';</script>\n\
+                <h1 title=\"see > below\">Title</h1>\n\
+                <h2>Sub</h2>\n\
+                </body></html>";
+
+    assert!(a11y::check_page("page.html", html, "en").is_empty());
+}
+
+#[test]
+fn test_check_a11y_per_slide() {
+    let temp_dir = TempDir::new().unwrap();
+    let templates = temp_dir.path().join("slides/templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(
+        temp_dir.path().join("decks.yaml"),
+        "default:\n  - bad.html\n  - good.html\n",
+    )
+    .unwrap();
+    fs::write(
+        templates.join("good.html"),
+        "<html lang=\"en\"><body><h1>Good</h1><img src=\"a.png\" alt=\"A\"></body></html>",
+    )
+    .unwrap();
+    fs::write(
+        templates.join("bad.html"),
+        "<html lang=\"en\">\n<body>\n<h1>Bad</h1>\n<h3>Skip</h3>\n<img src=\"a.png\">\n\
+         <div onclick=\"next()\">Next</div>\n</body>\n</html>",
+    )
+    .unwrap();
+
+    let project = Project::open(temp_dir.path()).unwrap();
+    let report = project
+        .check_a11y("default", &A11yOptions::default().with_contrast(false))
+        .expect("check should succeed");
+
+    assert!(!report.contrast);
+    assert_eq!(report.slides.len(), 2);
+    assert_eq!(report.slides[0].template, "bad.html");
+    assert_eq!(
+        messages(report.slides[0].issues.clone()),
+        vec![
+            ("missing-alt", Some(5)),
+            ("heading-order", Some(4)),
+            ("keyboard", Some(6)),
+        ]
+    );
+    assert!(report.slides[0]
+        .issues
+        .iter()
+        .all(|issue| issue.file == "output/en/slide-1.html"));
+    assert_eq!(report.slides[1].template, "good.html");
+    assert!(report.slides[1].issues.is_empty());
+    assert_eq!((report.errors(), report.warnings()), (2, 1));
+
+    // The page language follows the build language
+    let report = project
+        .check_a11y(
+            "default",
+            &A11yOptions::default().with_lang("de").with_contrast(false),
+        )
+        .unwrap();
+    assert_eq!(report.lang, "de");
+    assert!(report
+        .slides
+        .iter()
+        .all(|slide| slide.issues.iter().any(|issue| issue.rule == "lang")));
+}
@@ -0,0 +1,101 @@
+//! Data file integration tests
+//!
+//! Tests parsing YAML, JSON, TOML and CSV files from `slides/data` and using
+//! them in templates through `data.<name>` and `load_data()`
+
+use serde_json::json;
+use slides_rs::infrastructure::source::MemorySource;
+use slides_rs::model::Project;
+use slides_rs::services::data::{parse, parse_csv};
+use slides_rs::{Error, ErrorCategory};
+
+fn project(template: &str) -> MemorySource {
+    MemorySource::new()
+        .with_file("decks.yaml", "default:\n  - chart.html\n")
+        .with_file("slides/templates/chart.html", template)
+        .with_file(
+            "slides/data/sales.csv",
+            "quarter,revenue,region\nQ1,1200,\"North, East\"\nQ2,1350.5,South\n",
+        )
+        .with_file("slides/data/team.yaml", "- name: Ada\n  role: Lead\n")
+        .with_file("slides/data/config.toml", "[chart]\ncolor = \"blue\"\n")
+        .with_file(
+            "slides/data/config.json",
+            "{\"chart\": {\"color\": \"red\"}}",
+        )
+        .with_file("slides/data/archive/2025.json", "[1, 2, 3]")
+        .with_file("slides/data/README.md", "not data")
+}
+
+fn render(source: MemorySource) -> slides_rs::Result<String> {
+    let mut files = Project::from_source(source)
+        .deck("default", "en")
+        .render()?;
+    Ok(files.remove("slide-1.html").unwrap())
+}
+
+#[test]
+fn test_parse_csv() {
+    let rows = parse_csv(
+        "\u{feff}name,count,code,note\r\nA,42,007,\"said \"\"hi\"\"\"\r\n\
+         B,-1.5,0.5,\"two\nlines\"\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(
+        rows,
+        json!([
+            {"name": "A", "count": 42, "code": "007", "note": "said \"hi\""},
+            {"name": "B", "count": -1.5, "code": 0.5, "note": "two\nlines"},
+        ])
+    );
+
+    assert_eq!(parse_csv("").unwrap(), json!([]));
+    assert_eq!(parse_csv("a,b").unwrap(), json!([]));
+    assert!(parse_csv("a,b\n1\n").unwrap_err().contains("row 2"));
+    assert!(parse_csv("a\n\"open\n").is_err());
+}
+
+#[test]
+fn test_parse_formats() {
+    assert_eq!(
+        parse("toml", "a = 1\n[b]\nc = [\"x\"]").unwrap(),
+        json!({"a": 1, "b": {"c": ["x"]}})
+    );
+    assert_eq!(
+        parse("yml", "a: [1, two]").unwrap(),
+        json!({"a": [1, "two"]})
+    );
+    assert!(parse("json", "{").is_err());
+}
+
+#[test]
+fn test_data_in_templates() {
+    let html = render(project(
+        "{% for row in data.sales %}{{ row.quarter }}={{ row.revenue + 0 }};{% endfor %}\
+         {{ data.sales[0].region }}|{{ data.team[0].name }}|{{ data.config.chart.color }}|\
+         {{ load_data('config.json').chart.color }}|{{ load_data('archive/2025.json') | sum }}|\
+         {{ data.README is defined }}",
+    ))
+    .unwrap();
+
+    // YAML before JSON before TOML before CSV when two files share a name
+    assert_eq!(html, "Q1=1200;Q2=1350.5;North, East|Ada|red|red|6|false");
+
+    let html = render(project("{{ load_data('config.toml').chart.color }}")).unwrap();
+    assert_eq!(html, "blue");
+}
+
+#[test]
+fn test_data_errors() {
+    // Like other template errors, an unknown file fails only its page
+    let files = Project::from_source(project("{{ load_data('missing.csv') }}"))
+        .deck("default", "en")
+        .render()
+        .unwrap();
+    assert!(!files.contains_key("slide-1.html"));
+
+    let err = render(project("").with_file("slides/data/broken.yaml", "a: [")).unwrap_err();
+    assert!(matches!(err, Error::DataParse { .. }), "{err}");
+    assert!(err.to_string().contains("slides/data/broken.yaml"));
+    assert_eq!(err.category(), ErrorCategory::Config);
+}
@@ -1,6 +1,6 @@
 //! Tests for centralized error handling
 
-use slides_rs::Error;
+use slides_rs::{Error, ErrorCategory};
 use std::path::PathBuf;
 
 #[test]
@@ -24,14 +24,20 @@ fn test_error_display_messages() {
     let err = Error::DirExists(PathBuf::from("/tmp/existing"));
     assert_eq!(err.to_string(), "Directory '/tmp/existing' already exists");
 
+    let err = Error::ProjectNotFound(PathBuf::from("/tmp/missing"));
+    assert_eq!(err.to_string(), "Project directory not found: /tmp/missing");
+
     let err = Error::ThumbnailsNotFound;
     assert_eq!(
         err.to_string(),
         "Thumbnails not found. Run 'slides build' first."
     );
 
-    let err = Error::NoThumbnails;
-    assert_eq!(err.to_string(), "No thumbnails found in output/thumbnails/");
+    let err = Error::NoThumbnails(PathBuf::from("output/en/thumbnails"));
+    assert_eq!(
+        err.to_string(),
+        "No thumbnails found in output/en/thumbnails"
+    );
 
     let err = Error::TemplateNotFound("missing.html".to_string());
     assert_eq!(err.to_string(), "Template 'missing.html' not found");
@@ -85,3 +91,74 @@ fn test_template_render_error_includes_template_name() {
     assert!(msg.contains("intro.html"));
     assert!(msg.contains("undefined variable"));
 }
+
+#[test]
+fn test_error_categories_and_exit_codes() {
+    let cases = [
+        (
+            Error::DeckNotFound("talk".to_string()),
+            ErrorCategory::Config,
+            3,
+        ),
+        (
+            Error::TemplateNotFound("a.html".to_string()),
+            ErrorCategory::Template,
+            4,
+        ),
+        (
+            Error::BrowserNotFound(String::new()),
+            ErrorCategory::Browser,
+            5,
+        ),
+        (
+            Error::NotFound(PathBuf::from("missing.png")),
+            ErrorCategory::Io,
+            6,
+        ),
+        (Error::DirNotEmpty, ErrorCategory::Project, 7),
+        (
+            Error::NoThumbnails(PathBuf::from("output/en/thumbnails")),
+            ErrorCategory::Export,
+            8,
+        ),
+        (
+            Error::StaleBuild {
+                path: PathBuf::from("output/en"),
+                message: "is of deck 'talk', not 'default'".to_string(),
+            },
+            ErrorCategory::Export,
+            8,
+        ),
+        (
+            Error::ManifestSerialize(String::new()),
+            ErrorCategory::Io,
+            6,
+        ),
+        (
+            Error::LintFailed {
+                errors: 1,
+                warnings: 0,
+            },
+            ErrorCategory::Lint,
+            11,
+        ),
+        (
+            Error::A11yFailed {
+                errors: 0,
+                warnings: 2,
+            },
+            ErrorCategory::Lint,
+            11,
+        ),
+    ];
+
+    for (err, category, exit_code) in cases {
+        assert_eq!(err.category(), category, "{err}");
+        assert_eq!(category.exit_code(), exit_code);
+    }
+
+    assert_eq!(
+        serde_json::to_value(ErrorCategory::Io).unwrap(),
+        serde_json::json!("io")
+    );
+}
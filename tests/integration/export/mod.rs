@@ -3,7 +3,13 @@
 //! Tests the PDF generation from thumbnails
 
 use serial_test::serial;
-use slides_rs::infrastructure::pdf;
+use slides_rs::infrastructure::pdf::{
+    self, EncryptionAlgorithm, ImageCompression, PageSize, PdfOptions, Scaling, Watermark,
+    WatermarkPosition,
+};
+use slides_rs::model::{PdfExport, Project};
+use slides_rs::Error;
+use std::fs;
 use std::path::Path;
 
 const THUMBNAILS_PATH: &str = "tests/fixtures/thumbnails";
@@ -25,7 +31,7 @@ fn test_generate_pdf_from_thumbnails() {
     );
 
     // Generate PDF from 3 thumbnail slides
-    let result = pdf::generate_from_thumbnails(thumbnails_dir, 3);
+    let result = pdf::generate_from_thumbnails(thumbnails_dir, 3, &[], &PdfOptions::default());
 
     assert!(
         result.is_ok(),
@@ -48,18 +54,347 @@ fn test_generate_pdf_from_thumbnails() {
     );
 }
 
+/// Test that slide titles end up as PDF bookmarks
+#[test]
+#[serial]
+fn test_pdf_bookmarks_from_titles() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+    let titles = vec!["Welcome".to_string(), String::new()];
+
+    let pdf_bytes =
+        pdf::generate_from_thumbnails(thumbnails_dir, 2, &titles, &PdfOptions::default())
+            .expect("PDF generation should succeed");
+    let document = lopdf::Document::load_mem(&pdf_bytes).expect("Should parse generated PDF");
+
+    let outline_titles: Vec<String> = document
+        .objects
+        .values()
+        .filter_map(|object| object.as_dict().ok())
+        .filter(|dict| dict.has(b"Parent") && dict.has(b"Dest"))
+        .filter_map(|dict| dict.get(b"Title").ok())
+        .filter_map(|title| title.as_str().ok())
+        .map(|title| String::from_utf8_lossy(title).into_owned())
+        .collect();
+
+    assert!(outline_titles.contains(&"Welcome".to_string()));
+    assert!(outline_titles.contains(&"Slide 2".to_string()));
+}
+
+/// Test document information, nested outline and page labels
+#[test]
+#[serial]
+fn test_apply_document_info() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 3, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
+
+    let item = |title: &str, page: usize, children: Vec<pdf::OutlineItem>| pdf::OutlineItem {
+        title: title.to_string(),
+        page,
+        children,
+    };
+    let info = pdf::DocumentInfo {
+        title: "Rust in Production".to_string(),
+        author: Some("Jane Doe".to_string()),
+        subject: Some("Konferenz-Vortrag über Rust".to_string()),
+        keywords: vec!["rust".to_string(), "talk".to_string()],
+        creation_date: Some("2026-10-19".to_string()),
+        outline: vec![
+            item("Intro", 1, vec![]),
+            item(
+                "Main",
+                2,
+                vec![item("Topic A", 2, vec![]), item("Topic B", 3, vec![])],
+            ),
+        ],
+        page_labels: Vec::new(),
+    };
+
+    let result = pdf::apply_document_info(pdf_bytes, &info).expect("Should apply document info");
+    let document = lopdf::Document::load_mem(&result).expect("Should parse PDF");
+
+    let info_dict = document
+        .trailer
+        .get(b"Info")
+        .and_then(|info| info.as_reference())
+        .and_then(|id| document.get_dictionary(id))
+        .expect("PDF should have an info dictionary");
+    let text = |key: &[u8]| lopdf::decode_text_string(info_dict.get(key).unwrap()).unwrap();
+
+    assert_eq!(text(b"Title"), "Rust in Production");
+    assert_eq!(text(b"Author"), "Jane Doe");
+    assert_eq!(text(b"Subject"), "Konferenz-Vortrag über Rust");
+    assert_eq!(text(b"Keywords"), "rust, talk");
+    assert_eq!(text(b"CreationDate"), "D:20261019000000Z");
+
+    let catalog = document.catalog().expect("PDF should have a catalog");
+    assert!(catalog.has(b"PageLabels"), "PDF should have page labels");
+
+    let outlines = catalog
+        .get(b"Outlines")
+        .and_then(|o| o.as_reference())
+        .and_then(|id| document.get_dictionary(id))
+        .expect("PDF should have an outline");
+    assert_eq!(outlines.get(b"Count").unwrap().as_i64().unwrap(), 2);
+
+    let bookmark_titles: Vec<String> = document
+        .objects
+        .values()
+        .filter_map(|object| object.as_dict().ok())
+        .filter(|dict| dict.has(b"Parent") && dict.has(b"Title"))
+        .filter_map(|dict| lopdf::decode_text_string(dict.get(b"Title").ok()?).ok())
+        .collect();
+    assert_eq!(
+        bookmark_titles.len(),
+        4,
+        "Old flat bookmarks should be replaced"
+    );
+    assert!(bookmark_titles.contains(&"Topic B".to_string()));
+}
+
+/// Test that only YYYY-MM-DD[THH:MM:SS] dates are taken as creation date
+#[test]
+#[serial]
+fn test_pdf_creation_date_formats() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 1, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
+    let creation_date = |date: &str| {
+        let info = pdf::DocumentInfo {
+            creation_date: Some(date.to_string()),
+            ..Default::default()
+        };
+        let result = pdf::apply_document_info(pdf_bytes.clone(), &info).unwrap();
+        let document = lopdf::Document::load_mem(&result).unwrap();
+        let info_dict = document
+            .trailer
+            .get(b"Info")
+            .and_then(|info| info.as_reference())
+            .and_then(|id| document.get_dictionary(id))
+            .unwrap();
+        lopdf::decode_text_string(info_dict.get(b"CreationDate").unwrap()).unwrap()
+    };
+
+    assert_eq!(creation_date("2026-10-19T08:30:15"), "D:20261019083015Z");
+    assert_eq!(creation_date("2026-10-19 08:30Z"), "D:20261019083000Z");
+
+    // Other formats fall back to the current time instead of a garbled date
+    let year = &slides_rs::util::utc_now()[..4];
+    for date in ["19.10.2026", "2026-13-01", "20261019", "2026-10-19T25:00"] {
+        let created = creation_date(date);
+        assert!(
+            created.starts_with(&format!("D:{year}")),
+            "{date}: {created}"
+        );
+    }
+}
+
+/// Test page size parsing for the export options
+/// Test a PDF with several pages per slide, e.g. one per fragment state
+#[test]
+#[serial]
+fn test_pdf_fragment_pages() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+    let page = |slide: usize, bookmark: Option<&str>| pdf::PdfPage {
+        image: thumbnails_dir.join(format!("slide-{slide}.png")),
+        bookmark: bookmark.map(str::to_string),
+    };
+    let pages = vec![page(1, Some("Intro")), page(1, None), page(2, Some("End"))];
+
+    let pdf_bytes = pdf::generate_from_images(&pages, &PdfOptions::default())
+        .expect("PDF generation should succeed");
+    let info = pdf::DocumentInfo {
+        title: "Fragments".to_string(),
+        page_labels: vec![1, 1, 2],
+        ..Default::default()
+    };
+    let result = pdf::apply_document_info(pdf_bytes, &info).expect("Should apply document info");
+    let document = lopdf::Document::load_mem(&result).expect("Should parse PDF");
+
+    assert_eq!(document.get_pages().len(), 3);
+
+    let catalog = document.catalog().expect("PDF should have a catalog");
+    let labels = catalog
+        .get(b"PageLabels")
+        .and_then(|labels| labels.as_dict())
+        .and_then(|labels| labels.get(b"Nums"))
+        .and_then(|nums| nums.as_array())
+        .expect("PDF should have page labels");
+    let ranges: Vec<(i64, i64)> = labels
+        .chunks(2)
+        .map(|range| {
+            let start = range[0].as_i64().unwrap();
+            let label = range[1].as_dict().unwrap().get(b"St").unwrap();
+            (start, label.as_i64().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![(0, 1), (1, 1)],
+        "The fragment page repeats the slide number and slide 2 continues it"
+    );
+}
+
+#[test]
+fn test_page_size_from_str() {
+    assert_eq!("16:9".parse(), Ok(PageSize::Widescreen));
+    assert_eq!("4:3".parse(), Ok(PageSize::Standard));
+    assert_eq!("A4".parse(), Ok(PageSize::A4Landscape));
+    assert_eq!(
+        "280x157.5".parse(),
+        Ok(PageSize::Custom {
+            width_mm: 280.0,
+            height_mm: 157.5
+        })
+    );
+    assert!("big".parse::<PageSize>().is_err());
+    assert!("0x100".parse::<PageSize>().is_err());
+}
+
+fn first_page(document: &lopdf::Document) -> lopdf::ObjectId {
+    *document
+        .get_pages()
+        .get(&1)
+        .expect("PDF should have a page")
+}
+
+/// Test that the page size option sets the media box
+#[test]
+#[serial]
+fn test_pdf_page_size() {
+    let options = PdfOptions {
+        page_size: PageSize::Standard,
+        ..Default::default()
+    };
+    let pdf_bytes = pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 1, &[], &options)
+        .expect("PDF generation should succeed");
+    let document = lopdf::Document::load_mem(&pdf_bytes).expect("Should parse PDF");
+
+    let page = document.get_dictionary(first_page(&document)).unwrap();
+    let media_box: Vec<f32> = page
+        .get(b"MediaBox")
+        .and_then(|b| b.as_array())
+        .unwrap()
+        .iter()
+        .map(|v| v.as_float().unwrap())
+        .collect();
+
+    // 254 x 190.5 mm = 720 x 540 pt
+    assert!(
+        (media_box[2] - 720.0).abs() < 1.0,
+        "width: {}",
+        media_box[2]
+    );
+    assert!(
+        (media_box[3] - 540.0).abs() < 1.0,
+        "height: {}",
+        media_box[3]
+    );
+}
+
+/// Test that fitting a 4:3 slide onto a 16:9 page letterboxes it, while a 4:3 page does not
+#[test]
+#[serial]
+fn test_pdf_fit_letterboxing() {
+    let has_letterbox = |page_size: PageSize| {
+        let options = PdfOptions {
+            page_size,
+            scaling: Scaling::Fit,
+            ..Default::default()
+        };
+        let pdf_bytes = pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 1, &[], &options)
+            .expect("PDF generation should succeed");
+        let document = lopdf::Document::load_mem(&pdf_bytes).expect("Should parse PDF");
+        let content = document.get_page_content(first_page(&document)).unwrap();
+        String::from_utf8_lossy(&content).contains(" re")
+    };
+
+    assert!(has_letterbox(PageSize::Widescreen));
+    assert!(!has_letterbox(PageSize::Standard));
+}
+
+/// Test that image compression shrinks the PDF and JPEG uses the DCT filter
+#[test]
+#[serial]
+fn test_pdf_image_compression() {
+    let generate = |compression: ImageCompression| {
+        let options = PdfOptions {
+            compression,
+            ..Default::default()
+        };
+        pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 3, &[], &options)
+            .expect("PDF generation should succeed")
+    };
+
+    let raw = generate(ImageCompression::None);
+    let flate = generate(ImageCompression::Flate);
+    let jpeg = generate(ImageCompression::Jpeg(60));
+
+    assert!(flate.len() < raw.len(), "Flate should be smaller than raw");
+    assert!(jpeg.len() < raw.len(), "JPEG should be smaller than raw");
+
+    let document = lopdf::Document::load_mem(&jpeg).expect("Should parse JPEG PDF");
+    let has_dct = document.objects.values().any(|object| {
+        object.as_stream().is_ok_and(|stream| {
+            stream
+                .dict
+                .get(b"Filter")
+                .is_ok_and(|filter| format!("{filter:?}").contains("DCTDecode"))
+        })
+    });
+    assert!(has_dct, "JPEG images should use the DCTDecode filter");
+}
+
+/// Test that `ImageCompression::None` leaves every stream uncompressed, also
+/// after a watermark added compressed ones
+#[test]
+#[serial]
+fn test_pdf_without_compression() {
+    let options = PdfOptions {
+        compression: ImageCompression::None,
+        ..Default::default()
+    };
+    let pdf_bytes = pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 1, &[], &options)
+        .expect("PDF generation should succeed");
+    let watermark = Watermark::image(Path::new(THUMBNAILS_PATH).join("slide-1.png"));
+    let stamped = pdf::apply_watermark(pdf_bytes, &watermark).unwrap();
+    let compressed_streams = |bytes: &[u8]| {
+        let document = lopdf::Document::load_mem(bytes).expect("Should parse PDF");
+        document
+            .objects
+            .values()
+            .filter_map(|object| object.as_stream().ok())
+            .filter(|stream| stream.dict.has(b"Filter"))
+            .count()
+    };
+    assert!(compressed_streams(&stamped) > 0);
+
+    let uncompressed = pdf::apply_compression(stamped, ImageCompression::None).unwrap();
+    assert_eq!(compressed_streams(&uncompressed), 0);
+    let compressed = pdf::apply_compression(uncompressed, ImageCompression::Flate).unwrap();
+    assert!(compressed_streams(&compressed) > 0);
+}
+
+fn encryption(user_password: &str, owner_password: &str) -> pdf::Encryption {
+    pdf::Encryption {
+        user_password: user_password.to_string(),
+        owner_password: owner_password.to_string(),
+        ..Default::default()
+    }
+}
+
 /// Test PDF encryption with password
 #[test]
 #[serial]
 fn test_pdf_encryption() {
     let thumbnails_dir = Path::new(THUMBNAILS_PATH);
 
-    let pdf_bytes =
-        pdf::generate_from_thumbnails(thumbnails_dir, 1).expect("PDF generation should succeed");
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 1, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
 
     // Encrypt with password
     let password = "test-password-123";
-    let result = pdf::encrypt(pdf_bytes, password, false, false);
+    let result = pdf::encrypt(pdf_bytes, &encryption(password, "owner-password-456"));
 
     assert!(
         result.is_ok(),
@@ -67,8 +402,7 @@ fn test_pdf_encryption() {
         result.err()
     );
 
-    let (encrypted_bytes, success) = result.unwrap();
-    assert!(success, "Encryption should report success");
+    let encrypted_bytes = result.unwrap();
     assert!(
         encrypted_bytes.starts_with(b"%PDF"),
         "Encrypted output should still be valid PDF"
@@ -81,10 +415,16 @@ fn test_pdf_encryption() {
 fn test_pdf_encryption_no_print() {
     let thumbnails_dir = Path::new(THUMBNAILS_PATH);
 
-    let pdf_bytes =
-        pdf::generate_from_thumbnails(thumbnails_dir, 1).expect("PDF generation should succeed");
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 1, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
 
-    let result = pdf::encrypt(pdf_bytes, "password", true, false);
+    let result = pdf::encrypt(
+        pdf_bytes,
+        &pdf::Encryption {
+            no_print: true,
+            ..encryption("password", "owner")
+        },
+    );
 
     assert!(
         result.is_ok(),
@@ -98,14 +438,118 @@ fn test_pdf_encryption_no_print() {
 fn test_pdf_encryption_no_copy() {
     let thumbnails_dir = Path::new(THUMBNAILS_PATH);
 
-    let pdf_bytes =
-        pdf::generate_from_thumbnails(thumbnails_dir, 1).expect("PDF generation should succeed");
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 1, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
 
-    let result = pdf::encrypt(pdf_bytes, "password", false, true);
+    let result = pdf::encrypt(
+        pdf_bytes,
+        &pdf::Encryption {
+            no_copy: true,
+            ..encryption("password", "owner")
+        },
+    );
 
     assert!(result.is_ok(), "PDF encryption with no-copy should succeed");
 }
 
+/// Test that every algorithm encrypts with distinct user and owner passwords
+#[test]
+#[serial]
+fn test_pdf_encryption_algorithms() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+
+    for algorithm in [
+        EncryptionAlgorithm::Aes256,
+        EncryptionAlgorithm::Aes128,
+        EncryptionAlgorithm::Rc4,
+    ] {
+        let pdf_bytes =
+            pdf::generate_from_thumbnails(thumbnails_dir, 1, &[], &PdfOptions::default())
+                .expect("PDF generation should succeed");
+        let encrypted = pdf::encrypt(
+            pdf_bytes,
+            &pdf::Encryption {
+                algorithm,
+                ..encryption("user-secret", "owner-secret")
+            },
+        )
+        .unwrap_or_else(|e| panic!("{algorithm} encryption should succeed: {e}"));
+
+        let document = lopdf::Document::load_mem(&encrypted).expect("Should parse PDF");
+        assert!(
+            document.is_encrypted(),
+            "{algorithm} PDF should be encrypted"
+        );
+
+        for password in ["user-secret", "owner-secret"] {
+            let mut document = lopdf::Document::load_mem(&encrypted).unwrap();
+            assert!(
+                document.decrypt(password).is_ok(),
+                "{algorithm} PDF should open with '{password}'"
+            );
+        }
+
+        let mut document = lopdf::Document::load_mem(&encrypted).unwrap();
+        assert!(
+            document.decrypt("wrong").is_err(),
+            "{algorithm} PDF should reject a wrong password"
+        );
+    }
+}
+
+/// Test that an owner password equal to the user password is rejected
+#[test]
+#[serial]
+fn test_pdf_encryption_rejects_equal_passwords() {
+    let pdf_bytes =
+        pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 1, &[], &PdfOptions::default())
+            .expect("PDF generation should succeed");
+
+    let same = encryption("secret", "secret");
+    assert!(!same.has_distinct_passwords());
+    assert!(matches!(
+        pdf::encrypt(pdf_bytes, &same),
+        Err(Error::PdfEncryption(message)) if message.contains("must differ")
+    ));
+    assert!(encryption("", "secret").has_distinct_passwords());
+}
+
+/// Test resolving requested passwords
+#[test]
+fn test_protection_resolve() {
+    let protection = pdf::Protection {
+        no_print: true,
+        ..Default::default()
+    };
+    assert!(protection.is_requested(), "Restrictions require encryption");
+    let encryption = protection.resolve();
+    assert_eq!(encryption.algorithm, EncryptionAlgorithm::Aes256);
+    assert!(
+        encryption.user_password.is_empty(),
+        "No user password requested"
+    );
+    assert_eq!(
+        encryption.owner_password.len(),
+        16,
+        "Owner password is generated"
+    );
+
+    let protection = pdf::Protection {
+        user_password: Some(pdf::AUTO_PASSWORD.to_string()),
+        owner_password: Some("owner".to_string()),
+        ..Default::default()
+    };
+    let encryption = protection.resolve();
+    assert_eq!(
+        encryption.user_password.len(),
+        16,
+        "User password is generated"
+    );
+    assert_eq!(encryption.owner_password, "owner");
+
+    assert!(!pdf::Protection::default().is_requested());
+}
+
 /// Test secure password generation
 #[test]
 fn test_generate_secure_password() {
@@ -132,10 +576,250 @@ fn test_generate_secure_password() {
 fn test_pdf_error_missing_thumbnail() {
     let nonexistent = Path::new("tests/fixtures/nonexistent");
 
-    let result = pdf::generate_from_thumbnails(nonexistent, 1);
+    let result = pdf::generate_from_thumbnails(nonexistent, 1, &[], &PdfOptions::default());
 
     assert!(
         result.is_err(),
         "Should fail when thumbnails directory doesn't exist"
     );
 }
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Object id of a named page resource, e.g. `XObject` `X0`
+fn page_resource(
+    document: &lopdf::Document,
+    page_id: lopdf::ObjectId,
+    category: &[u8],
+    name: &[u8],
+) -> lopdf::ObjectId {
+    let (inline, referenced) = document.get_page_resources(page_id).unwrap();
+    inline
+        .into_iter()
+        .chain(
+            referenced
+                .into_iter()
+                .filter_map(|id| document.get_dictionary(id).ok()),
+        )
+        .find_map(|resources| {
+            let entries = resources.get(category).ok()?;
+            let entries = match entries {
+                lopdf::Object::Reference(id) => document.get_dictionary(*id).ok()?,
+                entries => entries.as_dict().ok()?,
+            };
+            entries.get(name).ok()?.as_reference().ok()
+        })
+        .expect("Page resource should exist")
+}
+
+/// Test that a text watermark is stamped onto every page
+#[test]
+#[serial]
+fn test_pdf_text_watermark() {
+    let pdf_bytes =
+        pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 2, &[], &PdfOptions::default())
+            .expect("PDF generation should succeed");
+
+    let watermark = Watermark {
+        position: WatermarkPosition::BottomRight,
+        rotation: 30.0,
+        ..Watermark::text("Confidential – prepared for ACME")
+    };
+    let watermarked =
+        pdf::apply_watermark(pdf_bytes, &watermark).expect("Watermarking should succeed");
+
+    let document = lopdf::Document::load_mem(&watermarked).expect("Should parse PDF");
+    for (number, page_id) in document.get_pages() {
+        let content = document.get_page_content(page_id).unwrap();
+        assert!(
+            contains(&content, b"/GSWatermark gs"),
+            "Page {number} should use the watermark opacity"
+        );
+        assert!(
+            contains(&content, b"(Confidential \x96 prepared for ACME) Tj"),
+            "Page {number} should show the watermark text in WinAnsi encoding"
+        );
+        assert!(
+            !contains(&content, b"EMCQ"),
+            "Page {number} content streams should stay separated"
+        );
+
+        let state_id = page_resource(&document, page_id, b"ExtGState", b"GSWatermark");
+        let state = document.get_dictionary(state_id).unwrap();
+        assert_eq!(state.get(b"ca").unwrap().as_float().unwrap(), 0.3);
+    }
+}
+
+/// Test that an image watermark is embedded once with its transparency
+#[test]
+#[serial]
+fn test_pdf_image_watermark() {
+    let thumbnails_dir = Path::new(THUMBNAILS_PATH);
+    let pdf_bytes = pdf::generate_from_thumbnails(thumbnails_dir, 2, &[], &PdfOptions::default())
+        .expect("PDF generation should succeed");
+
+    let watermark = Watermark {
+        position: WatermarkPosition::TopLeft,
+        opacity: 0.5,
+        ..Watermark::image(thumbnails_dir.join("slide-1.png"))
+    };
+    let watermarked =
+        pdf::apply_watermark(pdf_bytes, &watermark).expect("Watermarking should succeed");
+
+    let document = lopdf::Document::load_mem(&watermarked).expect("Should parse PDF");
+    let mut images = std::collections::HashSet::new();
+    for page_id in document.get_pages().into_values() {
+        let content = document.get_page_content(page_id).unwrap();
+        assert!(contains(&content, b"/XWatermark Do"));
+
+        let image_id = page_resource(&document, page_id, b"XObject", b"XWatermark");
+        let image = document.get_object(image_id).unwrap().as_stream().unwrap();
+        assert!(image.dict.has(b"SMask"), "Transparency should be kept");
+        images.insert(image_id);
+    }
+    assert_eq!(images.len(), 1, "All pages should share one image object");
+}
+
+/// Test that a missing watermark image is reported
+#[test]
+#[serial]
+fn test_pdf_image_watermark_missing() {
+    let pdf_bytes =
+        pdf::generate_from_thumbnails(Path::new(THUMBNAILS_PATH), 1, &[], &PdfOptions::default())
+            .expect("PDF generation should succeed");
+
+    let result = pdf::apply_watermark(pdf_bytes, &Watermark::image("missing/logo.png"));
+    assert!(result.is_err(), "Missing image should fail");
+}
+
+/// Test parsing watermark positions
+#[test]
+fn test_watermark_position_from_str() {
+    assert_eq!(
+        "center".parse::<WatermarkPosition>(),
+        Ok(WatermarkPosition::Center)
+    );
+    assert_eq!(
+        "Bottom-Right".parse::<WatermarkPosition>(),
+        Ok(WatermarkPosition::BottomRight)
+    );
+    assert_eq!(
+        "top_left".parse::<WatermarkPosition>(),
+        Ok(WatermarkPosition::TopLeft)
+    );
+    assert!("middle".parse::<WatermarkPosition>().is_err());
+}
+
+/// Test that recipients sharing a file or without a file name stop the
+/// export before any PDF is written
+#[test]
+fn test_recipient_output_paths_are_checked() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let project = Project::open(dir.path()).unwrap();
+    let export = PdfExport {
+        output_path: "out/presentation.pdf".to_string(),
+        ..Default::default()
+    };
+    let error = |csv: &str| {
+        fs::write(dir.path().join("recipients.csv"), csv).unwrap();
+        match project.export_pdf_for_recipients(&export, "recipients.csv") {
+            Err(Error::RecipientsParse { message, .. }) => message,
+            other => panic!("{csv}: expected a recipients error, got {other:?}"),
+        }
+    };
+
+    assert!(error("name\nAda\nada\n").contains("out/presentation-ada.pdf"));
+    assert!(error("name,file\nAda,ada.pdf\nBob,ADA.pdf\n").contains("'Ada' and 'Bob'"));
+    assert!(error("name\n!!!\n").contains("no file name"));
+    for file in ["../ada.pdf", "/tmp/ada.pdf"] {
+        let message = error(&format!("name,file\nAda,{file}\n"));
+        assert!(message.contains("inside the output directory"), "{message}");
+    }
+
+    let protected = PdfExport {
+        protection: pdf::Protection {
+            owner_password: Some("secret".to_string()),
+            ..Default::default()
+        },
+        ..export.clone()
+    };
+    fs::write(
+        dir.path().join("recipients.csv"),
+        "name,password\nAda,other\nBob,secret\n",
+    )
+    .unwrap();
+    match project.export_pdf_for_recipients(&protected, "recipients.csv") {
+        Err(Error::RecipientsParse { message, .. }) => {
+            assert!(message.contains("'Bob' is the owner password"), "{message}")
+        }
+        other => panic!("expected a recipients error, got {other:?}"),
+    }
+
+    // Valid paths get as far as the missing thumbnails
+    fs::write(
+        dir.path().join("recipients.csv"),
+        "name,file\nAda,ada/deck.pdf\nBob,\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        project.export_pdf_for_recipients(&export, "recipients.csv"),
+        Err(Error::ThumbnailsNotFound)
+    ));
+}
+
+/// Test that an export fails on the manifest of another build instead of
+/// using its titles and bookmarks
+#[test]
+#[serial]
+fn test_export_checks_build_manifest() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output_dir = dir.path().join("output/en");
+    fs::create_dir_all(output_dir.join("thumbnails")).unwrap();
+    for number in 1..=3 {
+        let name = format!("slide-{number}.png");
+        fs::copy(
+            Path::new(THUMBNAILS_PATH).join(&name),
+            output_dir.join("thumbnails").join(&name),
+        )
+        .unwrap();
+    }
+    let project = Project::open(dir.path()).unwrap();
+    let export = PdfExport {
+        output_path: "presentation.pdf".to_string(),
+        ..Default::default()
+    };
+    let manifest = |deck: &str, lang: &str, total: usize| {
+        let json = format!(
+            r#"{{"deck":"{deck}","lang":"{lang}","total":{total},"slides":[],"sections":[]}}"#
+        );
+        fs::write(output_dir.join("manifest.json"), json).unwrap();
+    };
+
+    for (deck, lang, total, message) in [
+        ("talk", "en", 3, "is of deck 'talk', not 'default'"),
+        ("default", "de", 3, "is in 'de', not 'en'"),
+        ("default", "en", 5, "has 5 slides but 3 thumbnails"),
+    ] {
+        manifest(deck, lang, total);
+        let error = project.export_pdf(&export).unwrap_err();
+        assert!(matches!(error, Error::StaleBuild { .. }), "{error}");
+        assert_eq!(
+            error.to_string(),
+            format!("The build in 'output/en' {message}. Run 'slides build' first.")
+        );
+    }
+
+    fs::write(output_dir.join("manifest.json"), "{").unwrap();
+    assert!(matches!(
+        project.export_pdf(&export),
+        Err(Error::ManifestParse { .. })
+    ));
+
+    manifest("default", "en", 3);
+    project.export_pdf(&export).expect("Export should succeed");
+    assert!(dir.path().join("presentation.pdf").exists());
+}
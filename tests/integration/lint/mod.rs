@@ -0,0 +1,203 @@
+//! Lint integration tests
+//!
+//! Tests the checks of `slides lint` on projects held in memory and the HTML
+//! and decks.yaml checks on their own
+
+use slides_rs::infrastructure::source::MemorySource;
+use slides_rs::model::Project;
+use slides_rs::services::lint::{self, Issue, Severity};
+use slides_rs::Error;
+
+const BASE: &str =
+    "<!DOCTYPE html><html><head><title>{% block title %}{% endblock %}</title></head>\
+                    <body>{% block body %}{% endblock %}</body></html>";
+
+fn project(decks: &str) -> MemorySource {
+    MemorySource::new()
+        .with_file("decks.yaml", decks)
+        .with_file("slides/templates/layouts/base.html.twig", BASE)
+        .with_file(
+            "slides/templates/intro.html",
+            "{% extends \"layouts/base.html.twig\" %}\n\
+             {% block body %}<h1>Intro</h1>{% endblock %}\n\
+             {% block notes %}Say hello{% endblock %}",
+        )
+        .with_file("slides/assets/logo.png", "")
+}
+
+fn rules(issues: &[Issue]) -> Vec<&str> {
+    issues.iter().map(|issue| issue.rule).collect()
+}
+
+#[test]
+fn test_lint_clean_project() {
+    let report = Project::from_source(project("default:\n  - intro.html\n"))
+        .lint(None, "en")
+        .unwrap();
+
+    assert_eq!(report.decks, vec!["default"]);
+    assert_eq!(report.issues, vec![]);
+}
+
+#[test]
+fn test_lint_finds_template_problems() {
+    let source = project("default:\n  - intro.html\n  - missing-*.html\n  - broken.html\n")
+        .with_file(
+            "slides/templates/broken.html",
+            "{% extends \"layouts/base.html.twig\" %}\n\
+             {% block body %}\n\
+             <div>\n\
+             <img src=\"{{ asset('logo.png') }}\" alt=\"Logo\">\n\
+             <img src=\"{{ asset('missing.png') }}\">\n\
+             <span>open\n\
+             </div>\n\
+             {% endblock %}",
+        )
+        .with_file("slides/templates/orphan.html", "<p>Unused</p>");
+
+    let report = Project::from_source(source).lint(None, "en").unwrap();
+    let issue = |rule: &str| {
+        report
+            .issues
+            .iter()
+            .find(|issue| issue.rule == rule)
+            .unwrap_or_else(|| panic!("no {rule} issue in {:?}", report.issues))
+    };
+
+    let pattern = issue("unmatched-pattern");
+    assert_eq!(
+        (pattern.file.as_str(), pattern.line),
+        ("decks.yaml", Some(3))
+    );
+
+    let notes = issue("missing-notes");
+    assert_eq!(notes.file, "slides/templates/broken.html");
+
+    let asset = issue("broken-asset");
+    assert_eq!(asset.severity, Severity::Error);
+    assert_eq!(
+        asset.to_string(),
+        "slides/templates/broken.html:5: error [broken-asset] Asset 'missing.png' not found in slides/assets"
+    );
+
+    let alt = issue("missing-alt");
+    assert_eq!(
+        (alt.file.as_str(), alt.line),
+        ("slides/templates/broken.html", Some(5))
+    );
+
+    let html = issue("html");
+    assert_eq!(html.file, "output/en/slide-2.html");
+    assert!(html.message.contains("<span>"), "{}", html.message);
+
+    let unused = issue("unused-template");
+    assert_eq!(unused.file, "slides/templates/orphan.html");
+
+    assert_eq!(report.errors(), 1);
+    assert_eq!(report.warnings(), 5);
+}
+
+#[test]
+fn test_lint_single_deck_skips_unused_templates() {
+    let source = project("default:\n  - intro.html\nother:\n  - extra.html\n").with_file(
+        "slides/templates/extra.html",
+        "<p>Extra</p>{% block notes %}{% endblock %}",
+    );
+
+    let report = Project::from_source(source)
+        .lint(Some("default"), "en")
+        .unwrap();
+    assert_eq!(report.decks, vec!["default"]);
+    assert!(!rules(&report.issues).contains(&"unused-template"));
+
+    let err = Project::from_source(project("default:\n  - intro.html\n"))
+        .lint(Some("missing"), "en")
+        .unwrap_err();
+    assert!(matches!(err, Error::DeckNotFound(name) if name == "missing"));
+}
+
+#[test]
+fn test_lint_decks_file_schema() {
+    let content = "talk:\n  metadata:\n    titel: Talk\n  slides:\n    - intro.html\nbroken: 3\n";
+    let issues = lint::check_decks_file("decks.yaml", content);
+
+    assert_eq!(
+        issues
+            .iter()
+            .map(|issue| (issue.severity, issue.line))
+            .collect::<Vec<_>>(),
+        vec![(Severity::Warning, Some(3)), (Severity::Error, Some(6))]
+    );
+    assert!(issues[0].message.contains("'titel'"));
+
+    // Keys follow the config types: every field is known, others are listed
+    let content = "talk:\n  globals: {}\n  env: []\n  timing:\n    target: 5m\n  \
+                   metadata:\n    venue: Hall\n  slides:\n    - intro.html\n";
+    let issues = lint::check_decks_file("decks.yaml", content);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].line, Some(7));
+    assert!(issues[0].message.contains("'venue'"));
+    assert!(issues[0]
+        .message
+        .contains("title, author, subject, keywords, date"));
+
+    let issues = lint::check_decks_file("decks.yaml", "talk:\n  - [unclosed\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Error);
+    assert!(issues[0].line.is_some());
+
+    // Invalid decks.yaml stops the lint before rendering
+    let report = Project::from_source(project("default: 3\n"))
+        .lint(None, "en")
+        .unwrap();
+    assert_eq!(rules(&report.issues), vec!["decks-yaml"]);
+    assert!(report.decks.is_empty());
+}
+
+#[test]
+fn test_check_html() {
+    let html = "<!DOCTYPE html>\n<html>\n<body>\n<ul><li>One<li>Two</ul>\n\
+                <p>Text<br><img src=\"a.png\" alt=\"\"></p>\n\
+                <script>if (a < b) { document.write('<div>') }</script>\n\
+                <!-- <section> -->\n\
+                <div><em>Unclosed</div>\n\
+                </span>\n\
+                <section>\n\
+                </body>\n</html>";
+
+    let issues: Vec<(Option<usize>, String)> = lint::check_html("page.html", html)
+        .into_iter()
+        .map(|issue| (issue.line, issue.message))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (Some(8), "<em> is not closed before </div>".to_string()),
+            (Some(9), "Unexpected end tag </span>".to_string()),
+            (
+                Some(10),
+                "<section> is not closed before </body>".to_string()
+            ),
+        ]
+    );
+
+    assert_eq!(
+        lint::images_without_alt(
+            "<img src=\"a.png\">\n<img alt=\"A\" src=\"b.png\">\n<img data-alt=\"x\">"
+        ),
+        vec![1, 3]
+    );
+
+    // Comments, scripts, quoted ">" and attribute names inside values
+    assert_eq!(
+        lint::images_without_alt(
+            "<!-- <img src=\"old.png\"> -->\n\
+             <script>el.innerHTML = '<img src=\"x.png\">';</script>\n\
+             <img alt=\"a > b\" src=\"c.png\">\n\
+             <img title=\"x alt y\" src=\"d.png\">\n\
+             <IMG SRC=e.png ALT=E>"
+        ),
+        vec![4]
+    );
+    assert!(lint::check_html("page.html", "<p title=\"a > b\">Text</p>").is_empty());
+}
@@ -1,3 +1,316 @@
 //! Parser integration tests
 //!
 //! Tests slide parsing, deck loading, and YAML parsing
+
+use slides_rs::model::{DeckConfig, Recipient, Seconds};
+use slides_rs::util::parse_duration;
+use std::path::Path;
+
+const FIXTURES_PATH: &str = "tests/fixtures/default/slides/templates";
+
+fn parse(yaml: &str) -> DeckConfig {
+    serde_yaml::from_str(yaml).expect("Should parse deck config")
+}
+
+/// Test that flat decks produce slides without sections
+#[test]
+fn test_flat_deck_has_no_sections() {
+    let config = parse("- slides/*.html");
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+
+    assert_eq!(slides.len(), 3);
+    assert!(slides.iter().all(|s| s.sections.is_empty()));
+    assert_eq!(slides[0].section_key(), None);
+}
+
+/// Test that the existing one-level section format keeps working
+#[test]
+fn test_sectioned_deck() {
+    let config = parse(
+        r#"
+intro:
+  - slides/01-intro.html
+main:
+  - slides/02-features.html
+  - slides/03-end.html
+"#,
+    );
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+
+    assert_eq!(slides.len(), 3);
+    assert_eq!(slides[0].section_key().as_deref(), Some("intro"));
+    assert_eq!(slides[2].section_key().as_deref(), Some("main"));
+    assert_eq!(slides[1].sections[0].title, None);
+}
+
+/// Test nested sections and section titles
+#[test]
+fn test_nested_sections_with_titles() {
+    let config = parse(
+        r#"
+intro:
+  title: sections.intro
+  slides:
+    - slides/01-intro.html
+main:
+  - slides/02-features.html
+  - details:
+      title: In Detail
+      slides:
+        - slides/03-end.html
+"#,
+    );
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+
+    assert_eq!(slides.len(), 3);
+    assert_eq!(
+        slides[0].sections[0].title.as_deref(),
+        Some("sections.intro")
+    );
+    assert_eq!(slides[1].section_key().as_deref(), Some("main"));
+    assert_eq!(slides[2].section_key().as_deref(), Some("main.details"));
+    assert_eq!(slides[2].sections[1].title.as_deref(), Some("In Detail"));
+}
+
+/// Test decks with metadata and a sectioned slide list
+#[test]
+fn test_deck_with_metadata() {
+    let config = parse(
+        r#"
+metadata:
+  title: Rust in Production
+  author: Jane Doe
+  keywords: [rust, talk]
+  date: "2026-10-19"
+slides:
+  intro:
+    - slides/01-intro.html
+"#,
+    );
+
+    let metadata = config.metadata();
+    assert_eq!(metadata.title.as_deref(), Some("Rust in Production"));
+    assert_eq!(metadata.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(metadata.keywords, vec!["rust", "talk"]);
+    assert_eq!(metadata.date.as_deref(), Some("2026-10-19"));
+
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+    assert_eq!(slides.len(), 1);
+    assert_eq!(slides[0].section_key().as_deref(), Some("intro"));
+}
+
+/// Test that a sectioned deck is not mistaken for a deck with metadata
+#[test]
+fn test_sectioned_deck_with_slides_section() {
+    let config = parse(
+        r#"
+slides:
+  - slides/01-intro.html
+outro:
+  - slides/03-end.html
+"#,
+    );
+
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+    assert_eq!(slides.len(), 2);
+    assert_eq!(slides[0].section_key().as_deref(), Some("slides"));
+    assert!(config.metadata().title.is_none());
+}
+
+/// Test that a deck whose only section is named `slides` keeps that section
+#[test]
+fn test_sectioned_deck_with_only_slides_section() {
+    let config = parse(
+        r#"
+slides:
+  - slides/01-intro.html
+  - slides/03-end.html
+"#,
+    );
+    assert!(matches!(config, DeckConfig::Sectioned(_)));
+
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+    assert_eq!(slides.len(), 2);
+    assert!(slides
+        .iter()
+        .all(|slide| slide.section_key().as_deref() == Some("slides")));
+
+    // With settings next to it, `slides` is the deck's slide list
+    let config = parse("metadata:\n  title: Talk\nslides:\n  - slides/01-intro.html\n");
+    assert!(matches!(config, DeckConfig::Detailed(_)));
+    let slides = config
+        .load_slides(Path::new(FIXTURES_PATH))
+        .expect("Should load slides");
+    assert_eq!(slides[0].section_key(), None);
+}
+
+/// Test per-slide video durations by template pattern
+#[test]
+fn test_deck_video_durations() {
+    let config = parse(
+        r#"
+video:
+  duration: 4
+  durations:
+    slides/01-intro.html: 8
+    "slides/0*.html": 6.5
+slides:
+  - slides/*.html
+"#,
+    );
+
+    let video = config.video();
+    assert_eq!(video.duration, Some(4.0));
+    assert_eq!(video.slide_duration("slides/01-intro.html"), Some(8.0));
+    assert_eq!(video.slide_duration("slides/01-intro.html.twig"), Some(8.0));
+    assert_eq!(video.slide_duration("slides/02-content.html"), Some(6.5));
+    assert_eq!(video.slide_duration("other/end.html"), None);
+
+    assert_eq!(parse("- slides/*.html").video(), Default::default());
+}
+
+/// Test narration clips by template pattern
+#[test]
+fn test_deck_audio_clips() {
+    let config = parse(
+        r#"
+audio:
+  auto_advance: true
+  clips:
+    slides/01-intro.html: audio/intro.mp3
+    "slides/0*.html": audio/generic.ogg
+slides:
+  - slides/*.html
+"#,
+    );
+
+    let audio = config.audio();
+    assert!(audio.auto_advance);
+    assert_eq!(
+        audio.slide_clip("slides/01-intro.html.twig"),
+        Some("audio/intro.mp3")
+    );
+    assert_eq!(
+        audio.slide_clip("slides/02-content.html"),
+        Some("audio/generic.ogg")
+    );
+    assert_eq!(audio.slide_clip("other/end.html"), None);
+
+    assert_eq!(parse("- slides/*.html").audio(), Default::default());
+}
+
+#[test]
+fn test_deck_timing_plan() {
+    let config = parse(
+        r#"
+timing:
+  target: 10m
+  durations:
+    slides/01-intro.html: 1m30s
+    "slides/03-*.html": 90
+slides:
+  - slides/*.html
+"#,
+    );
+
+    let timing = config.timing();
+    assert_eq!(timing.target, Some(Seconds(600.0)));
+    assert_eq!(
+        timing.plan(&[
+            "slides/01-intro.html",
+            "slides/02-content.html",
+            "slides/03-demo.html",
+            "slides/04-end.html",
+        ]),
+        vec![Some(90.0), Some(210.0), Some(90.0), Some(210.0)]
+    );
+
+    // Without a target only matched slides have a plan
+    let config = parse("timing:\n  durations:\n    \"*\": 2m\nslides:\n  - slides/*.html\n");
+    assert_eq!(config.timing().plan(&["slides/a.html"]), vec![Some(120.0)]);
+    assert_eq!(
+        parse("- slides/*.html").timing().plan(&["a.html"]),
+        vec![None]
+    );
+
+    let invalid =
+        serde_yaml::from_str::<DeckConfig>("timing:\n  target: soon\nslides:\n  - slides/*.html\n");
+    assert!(invalid.unwrap_err().to_string().contains("soon"));
+}
+
+/// Test errors in the settings of a deck name the setting
+#[test]
+fn test_deck_settings_errors_name_the_key() {
+    let err = serde_yaml::from_str::<DeckConfig>("titel: Talk\nslides:\n  - slides/*.html\n")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown field `titel`"), "{err}");
+
+    let err = serde_yaml::from_str::<DeckConfig>(
+        "metadata:\n  title: Talk\nvideo:\n  duraton: 5\nslides:\n  - slides/*.html\n",
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("unknown field `duraton`"), "{err}");
+
+    let err = serde_yaml::from_str::<DeckConfig>("42")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("a deck must be a list of slide patterns"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("90"), Some(90.0));
+    assert_eq!(parse_duration("45s"), Some(45.0));
+    assert_eq!(parse_duration("2m"), Some(120.0));
+    assert_eq!(parse_duration("1m30s"), Some(90.0));
+    assert_eq!(parse_duration("1h 5m"), Some(3900.0));
+    assert_eq!(parse_duration("1m30"), None);
+    assert_eq!(parse_duration("soon"), None);
+    assert_eq!(parse_duration(""), None);
+}
+
+#[test]
+fn test_recipients_csv() {
+    let csv = "\u{feff}Name,Email,Password\r\n\
+               ACME Corp.,buyer@acme.example,\r\n\
+               \"Doe, Jane\",jane@example.com,\"se\"\"cret\"\r\n\
+               \r\n";
+    let recipients = Recipient::parse_csv(csv).expect("CSV should parse");
+
+    assert_eq!(recipients.len(), 2);
+    assert_eq!(recipients[0].name(), "ACME Corp.");
+    assert_eq!(recipients[0].slug(), "acme-corp");
+    assert_eq!(recipients[0].get("password"), None);
+    assert_eq!(recipients[1].name(), "Doe, Jane");
+    assert_eq!(recipients[1].get("password"), Some("se\"cret"));
+    assert_eq!(
+        recipients[1].fill("Prepared for {name} <{email}>"),
+        "Prepared for Doe, Jane <jane@example.com>"
+    );
+}
+
+#[test]
+fn test_recipients_csv_requires_name() {
+    let error = Recipient::parse_csv("email\nbuyer@acme.example\n").unwrap_err();
+    assert!(error.contains("name"), "Unexpected error: {error}");
+
+    let error = Recipient::parse_csv("name,email\n,buyer@acme.example\n").unwrap_err();
+    assert!(error.contains("row 2"), "Unexpected error: {error}");
+}
@@ -4,11 +4,16 @@
 //! Uses minimal fixtures from tests/fixtures/minimal/
 
 use serial_test::serial;
-use slides_rs::model::Slide;
+use slides_rs::infrastructure::git::{self, GitInfo};
+use slides_rs::infrastructure::source::MemorySource;
+use slides_rs::model::{Project, SectionRef, Slide};
 use slides_rs::services::render::{
-    load_layouts, render_deck_pages, render_overview, render_presenter_pages,
+    assign_fragment_counts, count_fragments, load_layouts, render_deck_pages, render_overview,
+    render_presenter_pages, render_remote_pages, Globals, RenderContext,
 };
+use slides_rs::services::translations::{available_locales, text_direction, Locale};
 use std::collections::HashMap;
+use std::fs;
 use std::path::Path;
 
 const FIXTURES_PATH: &str = "tests/fixtures/minimal/slides";
@@ -40,7 +45,7 @@ fn test_render_single_slide() {
 
     let slides = vec![Slide {
         template: "templates/slides/01-intro.html".into(),
-        section_key: None,
+        ..Default::default()
     }];
     let translations: HashMap<String, String> = HashMap::new();
 
@@ -67,15 +72,15 @@ fn test_navigation_links() {
     let slides = vec![
         Slide {
             template: "templates/slides/01-intro.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "templates/slides/02-content.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "templates/slides/03-end.html".into(),
-            section_key: None,
+            ..Default::default()
         },
     ];
     let translations: HashMap<String, String> = HashMap::new();
@@ -126,15 +131,15 @@ fn test_slide_numbers() {
     let slides = vec![
         Slide {
             template: "templates/slides/01-intro.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "templates/slides/02-content.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "templates/slides/03-end.html".into(),
-            section_key: None,
+            ..Default::default()
         },
     ];
     let translations: HashMap<String, String> = HashMap::new();
@@ -174,16 +179,20 @@ fn test_render_overview_with_template() {
     let slides = vec![
         Slide {
             template: "slides/01-intro.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "slides/02-content.html".into(),
-            section_key: Some("main".into()),
+            sections: vec![SectionRef {
+                key: "main".into(),
+                title: None,
+            }],
+            ..Default::default()
         },
     ];
     let translations: HashMap<String, String> = HashMap::new();
 
-    let result = render_overview(&layouts, &translations, &slides);
+    let result = render_overview(&layouts, &translations, &slides, true);
 
     assert!(
         result.is_some(),
@@ -196,6 +205,38 @@ fn test_render_overview_with_template() {
     );
 }
 
+/// Test overview without thumbnails (e.g. built with --no-thumbnails)
+#[test]
+#[serial]
+fn test_render_overview_without_thumbnails() {
+    let layouts = HashMap::from([(
+        "overview.html".to_string(),
+        "{{ app.thumbnails }}|{% for section in sections %}{% for slide in section.slides %}\
+         {{ slide.thumbnail | default('none') }};{% endfor %}{% endfor %}"
+            .to_string(),
+    )]);
+    let slides = vec![
+        Slide {
+            template: "a.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "b.html".into(),
+            ..Default::default()
+        },
+    ];
+    let translations: HashMap<String, String> = HashMap::new();
+
+    let html = render_overview(&layouts, &translations, &slides, true).unwrap();
+    assert_eq!(
+        html,
+        "true|thumbnails/small/slide-1.jpg;thumbnails/small/slide-2.jpg;"
+    );
+
+    let html = render_overview(&layouts, &translations, &slides, false).unwrap();
+    assert_eq!(html, "false|none;none;");
+}
+
 /// Test overview returns None when template missing
 #[test]
 #[serial]
@@ -203,11 +244,11 @@ fn test_render_overview_without_template() {
     let layouts: HashMap<String, String> = HashMap::new(); // No templates
     let slides = vec![Slide {
         template: "test.html".into(),
-        section_key: None,
+        ..Default::default()
     }];
     let translations: HashMap<String, String> = HashMap::new();
 
-    let result = render_overview(&layouts, &translations, &slides);
+    let result = render_overview(&layouts, &translations, &slides, true);
 
     assert!(
         result.is_none(),
@@ -225,11 +266,11 @@ fn test_render_presenter_pages_with_template() {
     let slides = vec![
         Slide {
             template: "slides/01-intro.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "slides/02-features.html".into(),
-            section_key: None,
+            ..Default::default()
         },
     ];
     let translations: HashMap<String, String> = HashMap::new();
@@ -260,7 +301,7 @@ fn test_render_presenter_pages_without_template() {
     let layouts: HashMap<String, String> = HashMap::new(); // No templates
     let slides = vec![Slide {
         template: "test.html".into(),
-        section_key: None,
+        ..Default::default()
     }];
     let translations: HashMap<String, String> = HashMap::new();
 
@@ -282,7 +323,7 @@ fn test_presenter_single_slide_deck() {
     // Only one slide
     let slides = vec![Slide {
         template: "slides/01-intro.html".into(),
-        section_key: None,
+        ..Default::default()
     }];
     let translations: HashMap<String, String> = HashMap::new();
 
@@ -323,11 +364,11 @@ fn test_asset_paths_in_presenter_pages() {
     let slides = vec![
         Slide {
             template: "slides/01-intro.html".into(),
-            section_key: None,
+            ..Default::default()
         },
         Slide {
             template: "slides/02-features.html".into(),
-            section_key: None,
+            ..Default::default()
         },
     ];
     let translations: HashMap<String, String> = HashMap::new();
@@ -347,3 +388,691 @@ fn test_asset_paths_in_presenter_pages() {
         "Presenter page should not use assets/ without ../ prefix"
     );
 }
+
+/// Test section metadata in the slide context for nested sections
+#[test]
+#[serial]
+fn test_section_context_for_nested_sections() {
+    let section = |key: &str, title: Option<&str>| SectionRef {
+        key: key.into(),
+        title: title.map(Into::into),
+    };
+
+    let mut layouts = HashMap::new();
+    layouts.insert(
+        "slide.html".to_string(),
+        "{% for s in slide.section.path %}{{ s.title }}>{% endfor %}\
+         {{ slide.section.position }}/{{ slide.section.total }}#{{ slide.section.index }}\
+         |{% for s in app.sections %}{{ s.title }}:{{ s.count }},{% endfor %}"
+            .to_string(),
+    );
+    let slide = |sections: Vec<SectionRef>| Slide {
+        template: "slide.html".into(),
+        sections,
+        ..Default::default()
+    };
+
+    let slides = vec![
+        slide(vec![section("intro", Some("sections.intro"))]),
+        slide(vec![section("main", None)]),
+        slide(vec![
+            section("main", None),
+            section("details", Some("In Detail")),
+        ]),
+        slide(vec![
+            section("main", None),
+            section("details", Some("In Detail")),
+        ]),
+    ];
+    let mut translations = HashMap::new();
+    translations.insert("sections.intro".to_string(), "Introduction".to_string());
+
+    let pages = render_deck_pages(&slides, &layouts, &translations);
+
+    assert_eq!(
+        pages["slide-1.html"],
+        "Introduction>1/1#1|Introduction:1,main:3,"
+    );
+    assert_eq!(pages["slide-2.html"], "main>1/3#2|Introduction:1,main:3,");
+    assert_eq!(
+        pages["slide-4.html"],
+        "main>In Detail>2/2#1|Introduction:1,main:3,"
+    );
+}
+
+/// Test overview sections report the same 0-based depth as the section tree
+#[test]
+#[serial]
+fn test_overview_section_depth() {
+    let section = |key: &str| SectionRef {
+        key: key.into(),
+        title: None,
+    };
+    let mut layouts = HashMap::new();
+    layouts.insert(
+        "overview.html".to_string(),
+        "{% for s in sections %}{{ s.key }}:{{ s.depth }};{% endfor %}\
+         |{% for s in app.sections %}{{ s.key }}:{{ s.depth }};{% endfor %}"
+            .to_string(),
+    );
+    let slide = |sections: Vec<SectionRef>| Slide {
+        template: "slide.html".into(),
+        sections,
+        ..Default::default()
+    };
+    let slides = vec![
+        slide(vec![]),
+        slide(vec![section("main")]),
+        slide(vec![section("main"), section("details")]),
+    ];
+
+    let html = render_overview(&layouts, &HashMap::new(), &slides, false).unwrap();
+    assert_eq!(html, ":none;main:0;main.details:1;|main:0;");
+}
+
+/// Test toc() lists sections with slide titles and marks the current section
+#[test]
+#[serial]
+fn test_toc_function() {
+    let mut layouts = HashMap::new();
+    layouts.insert(
+        "base.html".to_string(),
+        "{% for s in toc() %}[{% if s.active %}*{% endif %}{{ s.title }}:\
+         {% for item in s.slides %}{{ item.number }}={{ item.title }}{% if item.active %}!{% endif %};{% endfor %}]\
+         {% endfor %}"
+            .to_string(),
+    );
+    layouts.insert(
+        "agenda.html".to_string(),
+        r#"{% extends "base.html" %}{% block title %}Agenda{% endblock %}"#.to_string(),
+    );
+    layouts.insert(
+        "topic.html".to_string(),
+        r#"{% extends "base.html" %}{% block title %}
+  Topic {{ slide.current }}
+{% endblock %}"#
+            .to_string(),
+    );
+
+    let section = |key: &str| SectionRef {
+        key: key.into(),
+        title: None,
+    };
+    let slides = vec![
+        Slide {
+            template: "agenda.html".into(),
+            sections: vec![section("intro")],
+            ..Default::default()
+        },
+        Slide {
+            template: "topic.html".into(),
+            sections: vec![section("main")],
+            ..Default::default()
+        },
+        Slide {
+            template: "topic.html".into(),
+            sections: vec![section("main")],
+            ..Default::default()
+        },
+    ];
+
+    let pages = render_deck_pages(&slides, &layouts, &HashMap::new());
+
+    assert_eq!(
+        pages["slide-1.html"],
+        "[*intro:1=Agenda!;][main:2=Topic 2;3=Topic 3;]"
+    );
+    assert_eq!(
+        pages["slide-3.html"],
+        "[intro:1=Agenda;][*main:2=Topic 2;3=Topic 3!;]"
+    );
+
+    // Slides outside of sections are entries of their own
+    let mut slides = slides;
+    slides[0].sections.clear();
+    let pages = render_deck_pages(&slides, &layouts, &HashMap::new());
+    assert_eq!(
+        pages["slide-1.html"],
+        "[*Agenda:1=Agenda!;][main:2=Topic 2;3=Topic 3;]"
+    );
+}
+
+/// Test toc() lists every slide of a flat deck
+#[test]
+#[serial]
+fn test_toc_function_for_flat_deck() {
+    let layouts = HashMap::from([(
+        "slide.html".to_string(),
+        "{% block title %}Slide {{ slide.current }}{% endblock %}|\
+         {% for s in toc() %}{{ s.key | default('-') }}:{{ s.title }}:{{ s.first }}\
+         {% if s.active %}*{% endif %};{% endfor %}"
+            .to_string(),
+    )]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        };
+        3
+    ];
+
+    let pages = render_deck_pages(&slides, &layouts, &HashMap::new());
+    assert_eq!(
+        pages["slide-2.html"],
+        "Slide 2|-:Slide 1:1;-:Slide 2:2*;-:Slide 3:3;"
+    );
+}
+
+/// Test slide titles are exposed to slides, presenter pages and the overview
+#[test]
+#[serial]
+fn test_slide_titles_in_contexts() {
+    let mut layouts = HashMap::new();
+    layouts.insert(
+        "slide.html".to_string(),
+        "<title>{% block title %}Slide {{ slide.current }}{% endblock %}</title>[{{ slide.title }}]"
+            .to_string(),
+    );
+    layouts.insert(
+        "presenter.html".to_string(),
+        "{{ slide.title }} -> {{ next_slide.title | default('end') }}".to_string(),
+    );
+    layouts.insert(
+        "overview.html".to_string(),
+        "{% for s in sections %}{% for item in s.slides %}{{ item.title }};{% endfor %}{% endfor %}"
+            .to_string(),
+    );
+
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            title: Some("Stored Title".into()),
+            ..Default::default()
+        },
+    ];
+    let translations: HashMap<String, String> = HashMap::new();
+
+    let pages = render_deck_pages(&slides, &layouts, &translations);
+    assert_eq!(pages["slide-1.html"], "<title>Slide 1</title>[Slide 1]");
+    assert!(pages["slide-2.html"].ends_with("[Stored Title]"));
+
+    let presenter = render_presenter_pages(&layouts, &translations, &slides);
+    assert_eq!(presenter["slide-1.html"], "Slide 1 -> Stored Title");
+    assert_eq!(presenter["slide-2.html"], "Stored Title -> end");
+
+    let overview = render_overview(&layouts, &translations, &slides, true).unwrap();
+    assert_eq!(overview, "Slide 1;Stored Title;");
+}
+
+/// Test thumbnail() returns variant paths relative to the rendered page
+#[test]
+#[serial]
+fn test_thumbnail_function() {
+    let template = "{{ thumbnail(slide.current) }}|{{ thumbnail(slide.current, 'small') }}|\
+                    {{ thumbnail(slide.next, 'medium') }}|{{ thumbnail(1, '2x') }}";
+    let layouts = HashMap::from([
+        ("slide.html".to_string(), template.to_string()),
+        ("presenter.html".to_string(), template.to_string()),
+        (
+            "broken.html".to_string(),
+            "{{ thumbnail(1, 'huge') }}".to_string(),
+        ),
+    ]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+    let translations = HashMap::new();
+
+    let pages = render_deck_pages(&slides, &layouts, &translations);
+    assert_eq!(
+        pages["slide-1.html"],
+        "thumbnails/slide-1.png|thumbnails/small/slide-1.jpg|\
+         thumbnails/medium/slide-2.jpg|thumbnails/2x/slide-1.png"
+    );
+
+    let presenter = render_presenter_pages(&layouts, &translations, &slides);
+    assert!(
+        presenter["slide-1.html"].starts_with("../thumbnails/slide-1.png|"),
+        "Presenter pages link one directory up"
+    );
+
+    let broken = vec![Slide {
+        template: "broken.html".into(),
+        ..Default::default()
+    }];
+    let pages = render_deck_pages(&broken, &layouts, &translations);
+    assert!(
+        !pages.contains_key("slide-1.html"),
+        "Unknown sizes should fail rendering"
+    );
+}
+
+/// Test poll() and questions() render audience widgets
+#[test]
+#[serial]
+fn test_poll_and_questions_functions() {
+    let layouts = HashMap::from([
+        (
+            "poll.html".to_string(),
+            r#"{{ poll("q1", options=["Rust", "<Go>"], question="Favourite?") }}"#.to_string(),
+        ),
+        (
+            "ask.html".to_string(),
+            r#"{{ questions(submit="Senden") }}"#.to_string(),
+        ),
+        (
+            "bad-id.html".to_string(),
+            r#"{{ poll("q 1", options=["A"]) }}"#.to_string(),
+        ),
+        (
+            "no-options.html".to_string(),
+            r#"{{ poll("q2", options=[]) }}"#.to_string(),
+        ),
+    ]);
+    let slides: Vec<Slide> = ["poll.html", "ask.html", "bad-id.html", "no-options.html"]
+        .into_iter()
+        .map(|template| Slide {
+            template: template.into(),
+            ..Default::default()
+        })
+        .collect();
+
+    let pages = render_deck_pages(&slides, &layouts, &HashMap::new());
+    let poll = &pages["slide-1.html"];
+    assert!(
+        poll.starts_with(r#"<div class="poll" data-poll="q1">"#),
+        "{poll}"
+    );
+    assert!(
+        poll.contains(r#"<p class="poll-question">Favourite?</p>"#),
+        "{poll}"
+    );
+    assert!(poll.contains(r#"data-option="Rust""#), "{poll}");
+    assert!(poll.contains(r#"data-option="&lt;Go&gt;""#), "{poll}");
+
+    let ask = &pages["slide-2.html"];
+    assert!(
+        ask.starts_with("<form class=\"ask\" data-questions>"),
+        "{ask}"
+    );
+    assert!(ask.contains(">Senden</button>"), "{ask}");
+
+    assert!(
+        !pages.contains_key("slide-3.html"),
+        "Invalid ids should fail"
+    );
+    assert!(!pages.contains_key("slide-4.html"), "Polls need options");
+}
+
+#[test]
+#[serial]
+fn test_slide_audio_context() {
+    let layouts = HashMap::from([(
+        "slide.html".to_string(),
+        "{% if slide.audio %}{{ asset(slide.audio) }}|{{ slide.autoAdvance }}{% else %}silent{% endif %}"
+            .to_string(),
+    )]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            audio: Some("audio/intro.mp3".into()),
+            auto_advance: true,
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+
+    let pages = render_deck_pages(&slides, &layouts, &HashMap::new());
+    assert_eq!(pages["slide-1.html"], "assets/audio/intro.mp3|true");
+    assert_eq!(pages["slide-2.html"], "silent");
+}
+
+#[test]
+fn test_count_fragments() {
+    assert_eq!(count_fragments("<h1>No fragments</h1>"), 0);
+    assert_eq!(
+        count_fragments(
+            r#"<ul><li data-fragment>One</li><li class="x" data-fragment="">Two</li></ul>
+               <p>Text about data-fragment</p><div data-fragments="9">Other attribute</div>"#
+        ),
+        2
+    );
+    // Equal numbers share a step; unnumbered fragments use their position
+    assert_eq!(
+        count_fragments(
+            r#"<p data-fragment="1">A</p><p data-fragment='1'>B</p><p
+               data-fragment>C</p><p data-fragment="5">D</p>"#
+        ),
+        3
+    );
+    // Comments, scripts and attribute values are not markup
+    assert_eq!(
+        count_fragments(
+            r#"<!-- <li data-fragment>Hidden</li> -->
+               <script>const item = '<div data-fragment>';</script>
+               <p title="<b data-fragment>">Text</p><p data-fragment>Shown</p>"#
+        ),
+        1
+    );
+}
+
+#[test]
+#[serial]
+fn test_fragment_filter_and_presenter_context() {
+    let layouts = HashMap::from([
+        (
+            "slide.html".to_string(),
+            "{{ 'First' | fragment }}{{ 'Last' | fragment(4) }}".to_string(),
+        ),
+        (
+            "presenter.html".to_string(),
+            "{{ slide.fragments }}|{{ next_slide.fragments }}".to_string(),
+        ),
+    ]);
+    let mut slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+    let translations = HashMap::new();
+
+    let pages = render_deck_pages(&slides, &layouts, &translations);
+    assert_eq!(
+        pages["slide-1.html"],
+        r#"<span class="fragment" data-fragment>First</span><span class="fragment" data-fragment="4">Last</span>"#
+    );
+
+    assign_fragment_counts(&mut slides, &pages);
+    assert_eq!(slides[0].fragments, 2);
+
+    let presenter = render_presenter_pages(&layouts, &translations, &slides);
+    assert_eq!(presenter["slide-1.html"], "2|2");
+}
+
+#[test]
+#[serial]
+fn test_presenter_slide_urls() {
+    let layouts = HashMap::from([
+        ("slide.html".to_string(), String::new()),
+        (
+            "presenter.html".to_string(),
+            "{{ slide.url }}|{{ next_slide.url | default('none') }}".to_string(),
+        ),
+    ]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+
+    let presenter = render_presenter_pages(&layouts, &HashMap::new(), &slides);
+    assert_eq!(presenter["slide-1.html"], "../slide-1.html|../slide-2.html");
+    assert_eq!(presenter["slide-2.html"], "../slide-2.html|none");
+}
+
+#[test]
+fn test_remote_pages() {
+    let mut layouts = HashMap::from([("slide.html".to_string(), String::new())]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+    assert!(render_remote_pages(&layouts, &HashMap::new(), &slides).is_empty());
+
+    layouts.insert(
+        "remote.html".to_string(),
+        "{{ asset('js/remote.js') }}|{{ slide.current }}|{{ next_slide.url | default('none') }}"
+            .to_string(),
+    );
+    let remote = render_remote_pages(&layouts, &HashMap::new(), &slides);
+    assert_eq!(
+        remote["slide-1.html"],
+        "../assets/js/remote.js|1|../slide-2.html"
+    );
+    assert_eq!(remote["slide-2.html"], "../assets/js/remote.js|2|none");
+}
+
+#[test]
+fn test_presenter_schedule_and_neighbours() {
+    let layouts = HashMap::from([
+        ("slide.html".to_string(), String::new()),
+        (
+            "presenter.html".to_string(),
+            "{{ app.targetTime }}|{{ slide.planned }}|{{ slide.plannedStart }}-{{ slide.plannedEnd }}|\
+             {{ prev_slide.thumbnail | default('none') }}|{{ next_slide.thumbnail | default('none') }}"
+                .to_string(),
+        ),
+    ]);
+    let slide = |planned: Option<f32>| Slide {
+        template: "slide.html".into(),
+        planned_duration: planned,
+        ..Default::default()
+    };
+    let slides = vec![slide(Some(60.0)), slide(None), slide(Some(30.0))];
+
+    let presenter = render_presenter_pages(&layouts, &HashMap::new(), &slides);
+    assert_eq!(
+        presenter["slide-1.html"],
+        "90.0|60.0|0.0-60.0|none|../thumbnails/medium/slide-2.jpg"
+    );
+    assert_eq!(
+        presenter["slide-2.html"],
+        "90.0|none|60.0-60.0|../thumbnails/medium/slide-1.jpg|../thumbnails/medium/slide-3.jpg"
+    );
+    assert_eq!(
+        presenter["slide-3.html"],
+        "90.0|30.0|60.0-90.0|../thumbnails/medium/slide-2.jpg|none"
+    );
+
+    // No plan, no schedule
+    let presenter = render_presenter_pages(&layouts, &HashMap::new(), &slides[1..2]);
+    assert_eq!(presenter["slide-1.html"], "none|none|none-none|none|none");
+}
+
+#[test]
+fn test_locale_context_and_urls() {
+    let layouts = HashMap::from([
+        (
+            "slide.html".to_string(),
+            "{{ app.lang }}|{{ app.dir }}|\
+             {% for locale in app.locales %}{{ locale.code }}:{{ locale.dir }}:{{ locale.current }},{% endfor %}|\
+             {{ locale_url('ar') }}"
+                .to_string(),
+        ),
+        (
+            "presenter.html".to_string(),
+            "{{ app.lang }}|{{ locale_url('en') }}".to_string(),
+        ),
+        (
+            "overview.html".to_string(),
+            "{{ app.dir }}|{{ locale_url('en') }}".to_string(),
+        ),
+    ]);
+    let slides = vec![
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+        Slide {
+            template: "slide.html".into(),
+            ..Default::default()
+        },
+    ];
+    let locale = Locale::new("ar", vec!["ar".to_string(), "en".to_string()]);
+
+    let context = RenderContext::new(layouts, HashMap::new()).with_locale(locale);
+
+    let pages = context.deck_pages(&slides);
+    assert_eq!(
+        pages["slide-2.html"],
+        "ar|rtl|ar:rtl:true,en:ltr:false,|../ar/slide-2.html"
+    );
+
+    let presenter = context.presenter_pages(&slides);
+    assert_eq!(
+        presenter["slide-1.html"],
+        "ar|../../en/presenter/slide-1.html"
+    );
+
+    let overview = context.overview(&slides, false).unwrap();
+    assert_eq!(overview, "rtl|../en/overview.html");
+}
+
+#[test]
+fn test_text_direction_and_available_locales() {
+    assert_eq!(text_direction("he-IL"), "rtl");
+    assert_eq!(text_direction("fa_IR"), "rtl");
+    assert_eq!(text_direction("de"), "ltr");
+    assert_eq!(Locale::default().dir(), "ltr");
+
+    let source = MemorySource::new()
+        .with_file("slides/translations/en.yaml", "a: b")
+        .with_file("slides/translations/de.yaml", "a: c")
+        .with_file("slides/translations/README.md", "")
+        .with_file("slides/translations/old/fr.yaml", "a: d");
+    assert_eq!(
+        available_locales(&source, "slides/translations").unwrap(),
+        vec!["de", "en"]
+    );
+    assert!(available_locales(&source, "missing").unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_deck_build_env_and_user_globals() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(
+        root.join("decks.yaml"),
+        "talk:\n  metadata:\n    title: talk.title\n  globals:\n    company: ACME\n    \
+         colors: [red, blue]\n    build: ignored\n  env: [SLIDES_TEST_STAGE, SLIDES_TEST_UNSET]\n  \
+         slides:\n    intro:\n      - intro.html\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("slides/templates")).unwrap();
+    fs::write(
+        root.join("slides/templates/intro.html"),
+        "{{ deck.name }}|{{ deck.metadata.title }}|{{ deck.sections[0].key }}|\
+         {{ company }}|{{ colors | join(',') }}|{{ build.version }}|{{ build.short_commit }}|\
+         {{ build.branch }}|{{ build.timestamp[:4] }}|{{ env.SLIDES_TEST_STAGE }}|\
+         {{ env.SLIDES_TEST_UNSET is defined }}",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("slides/translations")).unwrap();
+    fs::write(
+        root.join("slides/translations/en.yaml"),
+        "talk:\n  title: The Talk\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join(".git/refs/heads")).unwrap();
+    fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    fs::write(
+        root.join(".git/refs/heads/main"),
+        "0123456789abcdef0123456789abcdef01234567\n",
+    )
+    .unwrap();
+
+    std::env::set_var("SLIDES_TEST_STAGE", "staging");
+    let files = Project::open(root)
+        .unwrap()
+        .deck("talk", "en")
+        .render()
+        .unwrap();
+    std::env::remove_var("SLIDES_TEST_STAGE");
+
+    let year = &slides_rs::util::utc_now()[..4];
+    assert_eq!(
+        files["slide-1.html"],
+        format!(
+            "talk|The Talk|intro|ACME|red,blue|{}|0123456|main|{year}|staging|false",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
+
+#[test]
+#[serial]
+fn test_dump_lists_present_variables() {
+    let layouts = HashMap::from([("slide.html".to_string(), "{{ dump() }}".to_string())]);
+    let slides = vec![Slide {
+        template: "slide.html".into(),
+        ..Default::default()
+    }];
+    let globals = Globals::from([("company".to_string(), serde_json::json!("ACME"))]);
+
+    let pages = RenderContext::new(layouts, HashMap::new())
+        .with_globals(globals)
+        .deck_pages(&slides);
+    let dump = &pages["slide-1.html"];
+    for name in [
+        "&quot;app&quot;",
+        "&quot;slide&quot;",
+        "&quot;company&quot;",
+    ] {
+        assert!(dump.contains(name), "{name} missing in {dump}");
+    }
+    // Functions and private variables are not data
+    assert!(!dump.contains("&quot;asset&quot;"));
+    assert!(!dump.contains("_output_path"));
+}
+
+#[test]
+fn test_git_info() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = dir.path().join(".git");
+    fs::create_dir_all(&git).unwrap();
+    // No HEAD yet
+    assert_eq!(git::read(dir.path()), None);
+
+    // Branch in packed refs, read from a subdirectory of the repository
+    fs::write(git.join("HEAD"), "ref: refs/heads/release/1.0\n").unwrap();
+    fs::write(
+        git.join("packed-refs"),
+        "# pack-refs with: peeled\nabc123 refs/heads/main\ndef456 refs/heads/release/1.0\n^ff00\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("talks/rust")).unwrap();
+    assert_eq!(
+        git::read(&dir.path().join("talks/rust")),
+        Some(GitInfo {
+            commit: "def456".into(),
+            branch: Some("release/1.0".into()),
+        })
+    );
+
+    // Detached HEAD
+    fs::write(git.join("HEAD"), "0123456789abcdef\n").unwrap();
+    let info = git::read(dir.path()).unwrap();
+    assert_eq!(info.branch, None);
+    assert_eq!(info.short_commit(), "0123456");
+}
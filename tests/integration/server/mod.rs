@@ -0,0 +1,482 @@
+//! Dev server integration tests
+//!
+//! Tests static file serving and presenter/audience sync over HTTP
+
+use slides_rs::infrastructure::live::{LiveSession, SessionData};
+use slides_rs::infrastructure::server::{
+    is_presenter_machine, lan_ip, page_access, DevServer, PageAccess, Pairing, RemoteAccess,
+    SyncHub, LIVE_EVENTS_PATH, POLLS_PATH, QUESTIONS_PATH, REMOTE_PAIR_PATH, SYNC_EVENTS_PATH,
+    SYNC_STATE_PATH,
+};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Serve a temporary output directory on a free port
+fn start() -> (TempDir, SocketAddr) {
+    let (dir, server) = bind();
+    let addr = server.local_addr().expect("Should have an address");
+    std::thread::spawn(move || server.run());
+    (dir, addr)
+}
+
+fn bind() -> (TempDir, DevServer) {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("slide-1.html"), "<h1>Hello</h1>").unwrap();
+    fs::create_dir_all(dir.path().join("assets/css")).unwrap();
+    fs::write(dir.path().join("assets/css/my style.css"), "h1 {}").unwrap();
+    fs::create_dir_all(dir.path().join("remote")).unwrap();
+    fs::write(dir.path().join("remote/slide-1.html"), "Secret notes").unwrap();
+    fs::create_dir_all(dir.path().join("presenter")).unwrap();
+    fs::write(dir.path().join("presenter/slide-1.html"), "Speaker notes").unwrap();
+
+    let server = DevServer::bind(dir.path(), "127.0.0.1:0").expect("Should bind");
+    (dir, server)
+}
+
+fn connect(addr: SocketAddr) -> TcpStream {
+    let stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    stream
+}
+
+/// Send a request and return the status line, headers and body
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (String, String) {
+    request_with_headers(addr, method, path, "", body)
+}
+
+fn request_with_headers(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    headers: &str,
+    body: &str,
+) -> (String, String) {
+    let mut stream = connect(addr);
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{headers}\
+         Content-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    (head.to_string(), body.to_string())
+}
+
+/// Open an event stream and return a function reading the next event's data
+fn subscribe(addr: SocketAddr, path: &str) -> impl FnMut() -> String {
+    let mut stream = connect(addr);
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut events = BufReader::new(stream);
+    move || loop {
+        let mut line = String::new();
+        events
+            .read_line(&mut line)
+            .expect("Should receive an event");
+        if let Some(data) = line.strip_prefix("data: ") {
+            return data.trim().to_string();
+        }
+    }
+}
+
+#[test]
+fn test_serves_output_files() {
+    let (_dir, addr) = start();
+
+    let (head, body) = request(addr, "GET", "/slide-1.html", "");
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert!(head.contains("text/html"), "{head}");
+    assert_eq!(body, "<h1>Hello</h1>");
+
+    let (head, _) = request(addr, "GET", "/assets/css/my%20style.css?v=2", "");
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert!(head.contains("text/css"), "{head}");
+
+    let (head, _) = request(addr, "GET", "/", "");
+    assert!(head.starts_with("HTTP/1.1 302"), "{head}");
+    assert!(head.contains("/slide-1.html"), "{head}");
+}
+
+#[test]
+fn test_rejects_paths_outside_output() {
+    let (_dir, addr) = start();
+
+    for path in [
+        "/missing.html",
+        "/../Cargo.toml",
+        "/assets/%2e%2e/%2e%2e/Cargo.toml",
+    ] {
+        let (head, _) = request(addr, "GET", path, "");
+        assert!(head.starts_with("HTTP/1.1 404"), "{path}: {head}");
+    }
+}
+
+#[test]
+fn test_sync_state_is_streamed() {
+    let (_dir, addr) = start();
+
+    let (head, _) = request(addr, "POST", SYNC_STATE_PATH, "not json");
+    assert!(head.starts_with("HTTP/1.1 400"), "{head}");
+
+    let (head, _) = request(
+        addr,
+        "POST",
+        SYNC_STATE_PATH,
+        r#"{"slide": 2, "fragment": 0}"#,
+    );
+    assert!(head.starts_with("HTTP/1.1 204"), "{head}");
+
+    // New subscribers get the latest state first, then every published one
+    let mut next_event = subscribe(addr, SYNC_EVENTS_PATH);
+
+    assert_eq!(next_event(), r#"{"fragment":0,"slide":2}"#);
+    request(
+        addr,
+        "POST",
+        SYNC_STATE_PATH,
+        r#"{"slide": 3, "fragment": "last"}"#,
+    );
+    assert_eq!(next_event(), r#"{"fragment":"last","slide":3}"#);
+}
+
+#[test]
+fn test_sync_hub() {
+    let hub = SyncHub::new();
+    assert_eq!(hub.state(), None);
+
+    let early = hub.subscribe();
+    hub.publish("first".to_string());
+    let late = hub.subscribe();
+    hub.publish("second".to_string());
+
+    assert_eq!(
+        early.try_iter().collect::<Vec<_>>(),
+        vec!["first", "second"]
+    );
+    assert_eq!(late.try_iter().collect::<Vec<_>>(), vec!["first", "second"]);
+    assert_eq!(hub.state().as_deref(), Some("second"));
+}
+
+#[test]
+fn test_remote_pages_need_remote_mode() {
+    let (_dir, addr) = start();
+
+    let (head, _) = request(addr, "GET", "/remote/slide-1.html", "");
+    assert!(head.starts_with("HTTP/1.1 404"), "{head}");
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, "pin=000000");
+    assert!(head.starts_with("HTTP/1.1 405"), "{head}");
+}
+
+#[test]
+fn test_remote_pairing_with_pin() {
+    let (_dir, server) = bind();
+    let server = server.with_remote();
+    let remote = server.remote().expect("Should have remote access");
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+
+    // Unpaired devices get the pin form, however the path is written
+    for path in [
+        "/remote/slide-1.html",
+        "/%72emote/slide-1.html",
+        "//remote/slide-1.html",
+        "/REMOTE/slide-1.html",
+    ] {
+        let (head, body) = request(addr, "GET", path, "");
+        assert!(head.starts_with("HTTP/1.1 200"), "{path}: {head}");
+        assert!(body.contains(REMOTE_PAIR_PATH), "{path}: {body}");
+        assert!(!body.contains("Secret notes"), "{path}: {body}");
+    }
+
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, "pin=wrong");
+    assert!(head.starts_with("HTTP/1.1 401"), "{head}");
+
+    let pin = remote.pin();
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, &format!("pin={pin}"));
+    assert!(head.starts_with("HTTP/1.1 303"), "{head}");
+    assert!(head.contains("Location: /remote/slide-1.html"), "{head}");
+    let cookie = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Set-Cookie: "))
+        .and_then(|value| value.split(';').next())
+        .expect("Should set a cookie")
+        .to_string();
+
+    let (head, body) = request_with_headers(
+        addr,
+        "GET",
+        "/remote/slide-1.html",
+        &format!("Cookie: theme=dark; {cookie}\r\n"),
+        "",
+    );
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert_eq!(body, "Secret notes");
+
+    let (head, _) = request_with_headers(
+        addr,
+        "GET",
+        "/remote/",
+        &format!("Cookie: {cookie}\r\n"),
+        "",
+    );
+    assert!(head.starts_with("HTTP/1.1 302"), "{head}");
+
+    // Navigation from this machine needs no pairing
+    let (head, _) = request(addr, "POST", SYNC_STATE_PATH, r#"{"slide": 2}"#);
+    assert!(head.starts_with("HTTP/1.1 204"), "{head}");
+}
+
+#[test]
+fn test_serves_locale_builds_below_base_path() {
+    let dir = TempDir::new().unwrap();
+    for lang in ["en", "de"] {
+        fs::create_dir_all(dir.path().join(lang).join("remote")).unwrap();
+        fs::write(dir.path().join(lang).join("slide-1.html"), lang).unwrap();
+        fs::write(dir.path().join(lang).join("remote/slide-1.html"), "Notes").unwrap();
+    }
+    let server = DevServer::bind(dir.path(), "127.0.0.1:0")
+        .expect("Should bind")
+        .with_base_path("en")
+        .with_remote();
+    let remote = server.remote().expect("Should have remote access");
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+
+    for path in ["/", "/en/"] {
+        let (head, _) = request(addr, "GET", path, "");
+        assert!(head.starts_with("HTTP/1.1 302"), "{path}: {head}");
+        assert!(
+            head.contains("Location: /en/slide-1.html"),
+            "{path}: {head}"
+        );
+    }
+    let pin = remote.pin();
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, &format!("pin={pin}"));
+    assert!(head.contains("Location: /en/remote/slide-1.html"), "{head}");
+
+    // locale_url() links lead to the other builds
+    let (head, body) = request(addr, "GET", "/de/slide-1.html", "");
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert_eq!(body, "de");
+
+    // Remote pages of every build need pairing
+    for path in ["/en/remote/slide-1.html", "/de/remote/slide-1.html"] {
+        let (_, body) = request(addr, "GET", path, "");
+        assert!(body.contains(REMOTE_PAIR_PATH), "{path}: {body}");
+        assert!(!body.contains("Notes"), "{path}: {body}");
+    }
+}
+
+#[test]
+fn test_only_presenter_machine_navigates() {
+    let lan = IpAddr::from([192, 168, 1, 10]);
+    assert!(is_presenter_machine(IpAddr::from([127, 0, 0, 1]), None));
+    assert!(is_presenter_machine("::1".parse().unwrap(), Some(lan)));
+    assert!(is_presenter_machine(lan, Some(lan)));
+    assert!(!is_presenter_machine(
+        IpAddr::from([192, 168, 1, 20]),
+        Some(lan)
+    ));
+    assert!(!is_presenter_machine(lan, None));
+
+    // Served on all interfaces, this machine still navigates through its LAN address
+    let dir = TempDir::new().unwrap();
+    let server = DevServer::bind(dir.path(), "0.0.0.0:0").expect("Should bind");
+    let port = server.local_addr().unwrap().port();
+    std::thread::spawn(move || server.run());
+    let Some(ip) = lan_ip() else {
+        return;
+    };
+    let (head, _) = request(
+        SocketAddr::new(ip, port),
+        "POST",
+        SYNC_STATE_PATH,
+        r#"{"slide": 2}"#,
+    );
+    assert!(head.starts_with("HTTP/1.1 204"), "{head}");
+}
+
+#[test]
+fn test_presenter_pages_need_presenter_machine() {
+    let (_dir, addr) = start();
+    let (head, body) = request(addr, "GET", "/presenter/slide-1.html", "");
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert_eq!(body, "Speaker notes");
+
+    // LAN clients without pairing, with and without --remote
+    for path in [
+        "/en/presenter/slide-1.html",
+        "/en/%70resenter/slide-1.html",
+        "//PRESENTER/slide-1.html",
+    ] {
+        assert_eq!(page_access(path, true, false, false), PageAccess::Pair);
+        assert_eq!(
+            page_access(path, false, false, false),
+            PageAccess::Forbidden
+        );
+        assert_eq!(page_access(path, true, false, true), PageAccess::Allowed);
+        assert_eq!(page_access(path, false, true, false), PageAccess::Allowed);
+    }
+    assert_eq!(
+        page_access("/en/slide-1.html", false, false, false),
+        PageAccess::Allowed
+    );
+    assert_eq!(
+        page_access("/en/remote/slide-1.html", true, true, false),
+        PageAccess::Pair
+    );
+}
+
+#[test]
+fn test_remote_pin_is_one_time() {
+    let remote = RemoteAccess::new();
+    let phone = IpAddr::from([192, 168, 1, 20]);
+    let attacker = IpAddr::from([192, 168, 1, 66]);
+
+    let pin = remote.pin();
+    assert_eq!(pin.len(), 6);
+    let Pairing::Paired(token) = remote.pair(phone, &pin) else {
+        panic!("Should pair");
+    };
+    assert!(remote.is_paired(&token));
+    assert!(!remote.is_paired("guess"));
+    assert_ne!(remote.pin(), pin);
+
+    // Too many wrong pins lock out the client, not the pin
+    let pin = remote.pin();
+    let wrong = if pin == "000000" { "111111" } else { "000000" };
+    for _ in 0..4 {
+        assert_eq!(remote.pair(attacker, wrong), Pairing::WrongPin);
+    }
+    assert!(matches!(
+        remote.pair(attacker, wrong),
+        Pairing::LockedOut(wait) if wait == Duration::from_secs(30)
+    ));
+    assert!(matches!(remote.pair(attacker, &pin), Pairing::LockedOut(_)));
+    assert_eq!(remote.pin(), pin);
+    assert!(matches!(remote.pair(phone, &pin), Pairing::Paired(_)));
+}
+
+#[test]
+fn test_remote_pair_lockout_over_http() {
+    let (_dir, server) = bind();
+    let server = server.with_remote();
+    let remote = server.remote().unwrap();
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+
+    let wrong = if remote.pin() == "000000" {
+        "111111"
+    } else {
+        "000000"
+    };
+    for _ in 0..4 {
+        let (head, body) = request(addr, "POST", REMOTE_PAIR_PATH, &format!("pin={wrong}"));
+        assert!(head.starts_with("HTTP/1.1 401"), "{head}");
+        assert!(body.contains("Wrong pin"), "{body}");
+    }
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, &format!("pin={wrong}"));
+    assert!(head.starts_with("HTTP/1.1 429"), "{head}");
+    assert!(head.contains("Retry-After: 30"), "{head}");
+
+    let pin = remote.pin();
+    let (head, _) = request(addr, "POST", REMOTE_PAIR_PATH, &format!("pin={pin}"));
+    assert!(head.starts_with("HTTP/1.1 429"), "{head}");
+}
+
+#[test]
+fn test_poll_votes_and_questions() {
+    let (dir, server) = bind();
+    let session = dir.path().join("sessions/talk.json");
+    let server = server.with_live_session(LiveSession::new("talk", Some(session.clone())));
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+    let poll = format!("{POLLS_PATH}q1");
+
+    let (head, body) = request(addr, "GET", &poll, "");
+    assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+    assert_eq!(body, r#"{"poll":"q1","votes":{},"total":0}"#);
+
+    let mut next_event = subscribe(addr, LIVE_EVENTS_PATH);
+    request(addr, "POST", &poll, r#"{"voter": "a", "option": "Rust"}"#);
+    assert_eq!(
+        next_event(),
+        r#"{"results":{"poll":"q1","total":1,"votes":{"Rust":1}},"type":"poll"}"#
+    );
+
+    // Voting again replaces the earlier vote
+    request(addr, "POST", &poll, r#"{"voter": "b", "option": "Rust"}"#);
+    let (_, body) = request(addr, "POST", &poll, r#"{"voter": "a", "option": "Go"}"#);
+    assert_eq!(body, r#"{"poll":"q1","votes":{"Go":1,"Rust":1},"total":2}"#);
+
+    for (path, vote) in [
+        (poll.as_str(), r#"{"voter": "a"}"#),
+        (poll.as_str(), r#"{"voter": "a b", "option": "Go"}"#),
+        ("/_slides/polls/q%201", r#"{"voter": "a", "option": "Go"}"#),
+    ] {
+        let (head, _) = request(addr, "POST", path, vote);
+        assert!(head.starts_with("HTTP/1.1 400"), "{vote}: {head}");
+    }
+
+    let (head, _) = request(addr, "POST", QUESTIONS_PATH, r#"{"text": "  "}"#);
+    assert!(head.starts_with("HTTP/1.1 400"), "{head}");
+    let (head, body) = request(
+        addr,
+        "POST",
+        QUESTIONS_PATH,
+        r#"{"text": "Why Rust?", "name": "Ada"}"#,
+    );
+    assert!(head.starts_with("HTTP/1.1 201"), "{head}");
+    assert!(body.contains(r#""id":1"#), "{body}");
+    request(addr, "POST", QUESTIONS_PATH, r#"{"text": "And Go?"}"#);
+
+    let (_, body) = request(addr, "GET", QUESTIONS_PATH, "");
+    assert!(
+        body.contains("Why Rust?") && body.contains("And Go?"),
+        "{body}"
+    );
+
+    let answered = format!("{QUESTIONS_PATH}/1/answered");
+    let (head, _) = request(addr, "POST", &answered, "");
+    assert!(head.starts_with("HTTP/1.1 204"), "{head}");
+    let (head, _) = request(addr, "POST", &format!("{QUESTIONS_PATH}/9/answered"), "");
+    assert!(head.starts_with("HTTP/1.1 404"), "{head}");
+
+    // Everything is saved for later analysis
+    let saved: SessionData =
+        serde_json::from_str(&fs::read_to_string(&session).expect("Should save the session"))
+            .unwrap();
+    assert_eq!(saved.deck, "talk");
+    assert_eq!(saved.polls["q1"]["a"], "Go");
+    assert_eq!(saved.polls["q1"]["b"], "Rust");
+    assert_eq!(saved.questions.len(), 2);
+    assert!(saved.questions[0].answered);
+    assert_eq!(saved.questions[0].name.as_deref(), Some("Ada"));
+    assert!(!saved.questions[1].answered);
+}
+
+#[test]
+fn test_live_session_limits() {
+    let live = LiveSession::new("talk", None);
+
+    assert!(live.vote("q1", "voter", &"x".repeat(201)).is_none());
+    assert!(live.vote(&"q".repeat(65), "voter", "A").is_none());
+    assert!(live.ask(&"?".repeat(501), None).is_none());
+    assert!(live.ask("Fine?", Some(&"n".repeat(81))).is_none());
+
+    let question = live.ask("  Fine?  ", Some(" ")).expect("Should accept");
+    assert_eq!(question.text, "Fine?");
+    assert_eq!(question.name, None);
+    assert!(!live.answer(2));
+    assert!(live.answer(1));
+    assert!(live.data().started.ends_with('Z'));
+    assert_eq!(live.path(), None);
+}
@@ -0,0 +1,145 @@
+//! Template source integration tests
+//!
+//! Tests reading projects from disk, memory and zip archives, and rendering
+//! a deck from memory without a project directory
+
+use serial_test::serial;
+use slides_rs::infrastructure::source::{FileSource, MemorySource, TemplateSource, ZipSource};
+use slides_rs::model::Project;
+use slides_rs::Error;
+use std::env;
+use std::fs;
+use std::io::{Cursor, Write};
+use tempfile::TempDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+fn memory_project() -> MemorySource {
+    MemorySource::new()
+        .with_file("decks.yaml", "default:\n  - \"*.html\"\n")
+        .with_file(
+            "slides/templates/layouts/base.html.twig",
+            "<main>{% block content %}{% endblock %}</main>",
+        )
+        .with_file(
+            "slides/templates/01-intro.html",
+            "{% extends \"layouts/base.html\" %}{% block title %}{{ 'intro.title' | trans }}{% endblock %}\
+             {% block content %}<h1>{{ 'intro.title' | trans }}</h1>{% endblock %}",
+        )
+        .with_file(
+            "slides/templates/02-code.html",
+            "{% extends \"layouts/base.html\" %}{% block content %}<pre>{{ source('code/hello.rs') }}</pre>{% endblock %}",
+        )
+        .with_file("slides/templates/code/hello.rs", "fn main() {}")
+        .with_file("slides/translations/en.yaml", "intro:\n  title: Hello from memory\n")
+}
+
+#[test]
+fn test_memory_source_paths() {
+    let source = MemorySource::new()
+        .with_file("./slides\\templates/a.html", "a")
+        .with_file("slides/templates/nested/b.html", "b")
+        .with_file("decks.yaml", "default: []");
+
+    assert_eq!(
+        source.read("slides/templates/a.html").unwrap().as_deref(),
+        Some("a")
+    );
+    assert_eq!(source.read("missing.html").unwrap(), None);
+    assert_eq!(
+        source.list("slides/templates/").unwrap(),
+        vec!["a.html", "nested/b.html"]
+    );
+    assert!(source.list("slides/translations").unwrap().is_empty());
+}
+
+#[test]
+fn test_file_source_lists_fixtures() {
+    let source = FileSource::new("tests/fixtures/default");
+
+    let templates = source.list("slides/templates").unwrap();
+    assert!(templates.contains(&"slides/01-intro.html".to_string()));
+    assert!(templates.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(source.read("decks.yaml").unwrap().is_some());
+    assert!(source.list("missing").unwrap().is_empty());
+}
+
+#[test]
+fn test_zip_source() {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("decks.yaml", FileOptions::default())
+        .unwrap();
+    writer.write_all(b"default:\n  - \"*.html\"\n").unwrap();
+    writer
+        .start_file("slides/templates/intro.html", FileOptions::default())
+        .unwrap();
+    writer.write_all(b"<h1>Zipped</h1>").unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+
+    let source = ZipSource::from_bytes(bytes).unwrap();
+    assert_eq!(source.list("slides/templates").unwrap(), vec!["intro.html"]);
+
+    let files = Project::from_source(source)
+        .deck("default", "en")
+        .render()
+        .expect("Should render the zipped deck");
+    assert!(files["slide-1.html"].contains("<h1>Zipped</h1>"));
+
+    let err = ZipSource::from_bytes(b"not a zip".to_vec()).unwrap_err();
+    assert!(matches!(err, Error::ZipError(_)));
+}
+
+#[test]
+fn test_render_deck_from_memory() {
+    let files = Project::from_source(memory_project())
+        .deck("default", "en")
+        .render()
+        .expect("Should render from memory");
+
+    // Without presenter or overview templates, only slides and the manifest
+    let names: Vec<&str> = files.keys().map(String::as_str).collect();
+    assert_eq!(names, vec!["manifest.json", "slide-1.html", "slide-2.html"]);
+    assert!(files["slide-1.html"].contains("<h1>Hello from memory</h1>"));
+    assert!(files["slide-2.html"].contains("<pre>fn main() {}</pre>"));
+    assert!(files["manifest.json"].contains("Hello from memory"));
+}
+
+/// Test rendering from memory ignores the git checkout of the current directory
+#[test]
+#[serial]
+fn test_render_from_memory_has_no_git_info() {
+    let temp_dir = TempDir::new().unwrap();
+    let original_dir = env::current_dir().unwrap();
+    let git = temp_dir.path().join(".git");
+    fs::create_dir_all(git.join("refs/heads")).unwrap();
+    fs::write(git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+    fs::write(
+        git.join("refs/heads/main"),
+        "0123456789abcdef0123456789abcdef01234567\n",
+    )
+    .unwrap();
+
+    env::set_current_dir(temp_dir.path()).unwrap();
+    let source = MemorySource::new()
+        .with_file("decks.yaml", "default:\n  - \"*.html\"\n")
+        .with_file(
+            "slides/templates/intro.html",
+            "{{ build.commit }}|{{ build.branch }}",
+        );
+    let files = Project::from_source(source).deck("default", "en").render();
+    env::set_current_dir(&original_dir).unwrap();
+
+    assert_eq!(files.unwrap()["slide-1.html"], "none|none");
+}
+
+#[test]
+fn test_render_missing_decks_file() {
+    let source = MemorySource::new().with_file("slides/templates/a.html", "a");
+
+    let err = Project::from_source(source)
+        .deck("default", "en")
+        .render()
+        .unwrap_err();
+    assert!(matches!(err, Error::DecksNotFound));
+}
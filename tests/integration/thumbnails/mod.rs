@@ -0,0 +1,194 @@
+//! Thumbnail cache integration tests
+//!
+//! Tests slide fingerprints, the thumbnail cache and browser lookup (no browser required)
+
+use serial_test::serial;
+use slides_rs::error::Error;
+use slides_rs::infrastructure::chrome::{
+    find_chrome, slide_fingerprint, write_scaled_thumbnails, ThumbnailCache, ThumbnailSize,
+    CHROME_ENV,
+};
+use std::env;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write(dir: &Path, path: &str, content: &str) {
+    let path = dir.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Output directory with two slides sharing a stylesheet
+fn output_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "slide-1.html",
+        r#"<link rel="stylesheet" href="assets/css/style.css?v=1">
+           <img src='assets/images/logo.png'>
+           <a href="slide-2.html">Next</a>
+           <a href="https://example.com/">Link</a>"#,
+    );
+    write(dir.path(), "slide-2.html", "<h1>Second</h1>");
+    write(
+        dir.path(),
+        "assets/css/style.css",
+        "body { background: url(../images/bg.png); }",
+    );
+    write(dir.path(), "assets/images/logo.png", "logo");
+    write(dir.path(), "assets/images/bg.png", "background");
+    write(dir.path(), "assets/images/unused.png", "unused");
+    dir
+}
+
+#[test]
+fn test_fingerprint_is_stable() {
+    let dir = output_dir();
+    let first = slide_fingerprint(dir.path(), 1).unwrap();
+    let second = slide_fingerprint(dir.path(), 1).unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, slide_fingerprint(dir.path(), 2).unwrap());
+}
+
+#[test]
+fn test_fingerprint_follows_referenced_assets() {
+    let dir = output_dir();
+    let mut fingerprint = slide_fingerprint(dir.path(), 1).unwrap();
+
+    for (path, content) in [
+        ("slide-1.html", "<h1>Changed</h1>"),
+        ("assets/css/style.css", "body { color: red; }"),
+    ] {
+        let dir = output_dir();
+        write(dir.path(), path, content);
+        assert_ne!(
+            slide_fingerprint(dir.path(), 1).unwrap(),
+            fingerprint,
+            "Changing {path} should change the fingerprint"
+        );
+    }
+
+    // Files referenced from the stylesheet and by the slide itself
+    for path in ["assets/images/bg.png", "assets/images/logo.png"] {
+        write(dir.path(), path, "changed");
+        let changed = slide_fingerprint(dir.path(), 1).unwrap();
+        assert_ne!(changed, fingerprint, "Changing {path} should change it");
+        fingerprint = changed;
+    }
+
+    // Unreferenced assets and linked slides
+    write(dir.path(), "assets/images/unused.png", "changed");
+    write(dir.path(), "slide-2.html", "<h1>Changed</h1>");
+    assert_eq!(slide_fingerprint(dir.path(), 1).unwrap(), fingerprint);
+}
+
+#[test]
+fn test_fingerprint_missing_slide() {
+    let dir = TempDir::new().unwrap();
+    assert!(slide_fingerprint(dir.path(), 1).is_err());
+}
+
+#[test]
+fn test_thumbnail_cache() {
+    let dir = TempDir::new().unwrap();
+    let thumbnails = dir.path();
+
+    let mut cache = ThumbnailCache::load(thumbnails);
+    assert!(!cache.is_fresh(thumbnails, 1, "abc"), "Empty cache");
+
+    cache.insert(1, "abc".to_string());
+    assert!(
+        !cache.is_fresh(thumbnails, 1, "abc"),
+        "Thumbnail file is required"
+    );
+
+    write(thumbnails, "slide-1.png", "png");
+    assert!(cache.is_fresh(thumbnails, 1, "abc"));
+    assert!(!cache.is_fresh(thumbnails, 1, "def"), "Changed slide");
+
+    cache.save(thumbnails).unwrap();
+    let cache = ThumbnailCache::load(thumbnails);
+    assert!(cache.is_fresh(thumbnails, 1, "abc"), "Cache is persisted");
+}
+
+#[test]
+fn test_thumbnail_cache_removes_leftover_slides() {
+    let dir = TempDir::new().unwrap();
+    let thumbnails = dir.path();
+    let mut cache = ThumbnailCache::default();
+
+    for number in 1..=4 {
+        write(thumbnails, &format!("slide-{number}.png"), "png");
+        write(thumbnails, &format!("small/slide-{number}.jpg"), "jpg");
+        cache.insert(number, format!("hash-{number}"));
+    }
+
+    cache.remove_after(thumbnails, 2);
+
+    assert!(thumbnails.join("slide-2.png").exists());
+    assert!(!thumbnails.join("slide-3.png").exists());
+    assert!(!thumbnails.join("slide-4.png").exists());
+    assert!(thumbnails.join("small/slide-2.jpg").exists());
+    assert!(!thumbnails.join("small/slide-3.jpg").exists());
+    assert!(cache.is_fresh(thumbnails, 2, "hash-2"));
+    assert!(!cache.is_fresh(thumbnails, 3, "hash-3"));
+}
+
+#[test]
+#[serial]
+fn test_find_chrome_configured_path() {
+    let dir = TempDir::new().unwrap();
+    let chrome = dir.path().join("chrome");
+    write(dir.path(), "chrome", "");
+
+    assert_eq!(find_chrome(Some(&chrome)).unwrap(), chrome);
+
+    let missing = dir.path().join("missing");
+    match find_chrome(Some(&missing)) {
+        Err(Error::Browser(message)) => assert!(message.contains("missing")),
+        other => panic!("Expected a browser error, got {other:?}"),
+    }
+
+    // The environment variable is used when no path is given
+    env::set_var(CHROME_ENV, &chrome);
+    let found = find_chrome(None);
+    env::remove_var(CHROME_ENV);
+    assert_eq!(found.unwrap(), chrome);
+}
+
+#[test]
+fn test_thumbnail_size_paths() {
+    assert_eq!(ThumbnailSize::Full.path(3), "thumbnails/slide-3.png");
+    assert_eq!(ThumbnailSize::Small.path(3), "thumbnails/small/slide-3.jpg");
+    assert_eq!(ThumbnailSize::Medium.file_name(3), "medium/slide-3.jpg");
+    assert_eq!("2x".parse(), Ok(ThumbnailSize::Retina));
+    assert_eq!("Small".parse(), Ok(ThumbnailSize::Small));
+    assert!("huge".parse::<ThumbnailSize>().is_err());
+}
+
+#[test]
+fn test_scaled_thumbnails() {
+    let dir = TempDir::new().unwrap();
+    let thumbnails = dir.path();
+    fs::copy(
+        "tests/fixtures/thumbnails/slide-1.png",
+        thumbnails.join("slide-1.png"),
+    )
+    .unwrap();
+
+    write_scaled_thumbnails(thumbnails, 1).expect("Variants should be written");
+
+    let full = image::open(thumbnails.join("slide-1.png")).unwrap();
+    for (size, width) in [(ThumbnailSize::Small, 480), (ThumbnailSize::Medium, 960)] {
+        let path = thumbnails.join(size.file_name(1));
+        let variant = image::open(&path).expect("Variant should be a valid image");
+        assert_eq!(variant.width(), width, "{size:?} width");
+        let expected_height = full.height() * width / full.width();
+        assert!(
+            variant.height().abs_diff(expected_height) <= 1,
+            "{size:?} should keep the aspect ratio"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Video export integration tests
+//!
+//! Tests GIF encoding and the ffmpeg invocations for (narrated) MP4 exports
+
+use image::codecs::gif::GifDecoder;
+use image::AnimationDecoder;
+use slides_rs::infrastructure::video::{self, VideoFormat, VideoFrame};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use tempfile::TempDir;
+
+const THUMBNAILS_PATH: &str = "tests/fixtures/thumbnails";
+
+fn frames(durations: &[f32]) -> Vec<VideoFrame> {
+    durations
+        .iter()
+        .enumerate()
+        .map(|(index, duration)| VideoFrame {
+            image: Path::new(THUMBNAILS_PATH).join(format!("slide-{}.png", index + 1)),
+            duration: *duration,
+            audio: None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_encode_gif() {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("presentation.gif");
+
+    video::encode_gif(&frames(&[2.0, 0.5, 3.25]), &output).expect("GIF encoding should succeed");
+
+    let decoder = GifDecoder::new(BufReader::new(File::open(&output).unwrap())).unwrap();
+    let delays: Vec<u32> = decoder
+        .into_frames()
+        .map(|frame| {
+            let (numerator, denominator) = frame.unwrap().delay().numer_denom_ms();
+            numerator / denominator
+        })
+        .collect();
+    assert_eq!(delays, vec![2000, 500, 3250]);
+}
+
+#[test]
+fn test_encode_gif_without_frames() {
+    let dir = TempDir::new().unwrap();
+    let result = video::encode_gif(&[], &dir.path().join("empty.gif"));
+    assert!(result.is_err(), "Encoding no frames should fail");
+}
+
+/// A stand-in ffmpeg that copies the concat list (the argument after `-i`)
+/// to the output file (the last argument)
+#[cfg(unix)]
+#[test]
+fn test_encode_mp4_passes_durations_to_ffmpeg() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let ffmpeg = dir.path().join("ffmpeg");
+    fs::write(
+        &ffmpeg,
+        "#!/bin/sh\n\
+         while [ \"$1\" != \"-i\" ]; do shift; done\n\
+         list=\"$2\"\n\
+         for last; do :; done\n\
+         cp \"$list\" \"$last\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let output = dir.path().join("presentation.mp4");
+    video::encode_mp4(&ffmpeg, &frames(&[2.5, 4.0]), &output, (1920, 1080))
+        .expect("Encoding should succeed");
+
+    let list = fs::read_to_string(&output).unwrap();
+    let lines: Vec<&str> = list.lines().collect();
+    assert_eq!(
+        lines.len(),
+        5,
+        "Two frames plus the repeated last one: {list}"
+    );
+    assert!(lines[0].ends_with("slide-1.png'"));
+    assert_eq!(lines[1], "duration 2.500");
+    assert!(lines[2].ends_with("slide-2.png'"));
+    assert_eq!(lines[3], "duration 4.000");
+    assert_eq!(lines[4], lines[2]);
+    assert!(
+        !dir.path().join("presentation.frames.txt").exists(),
+        "The concat list should be removed"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_encode_mp4_reports_ffmpeg_errors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let ffmpeg = dir.path().join("ffmpeg");
+    fs::write(&ffmpeg, "#!/bin/sh\necho 'Unknown encoder' >&2\nexit 1\n").unwrap();
+    fs::set_permissions(&ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let error = video::encode_mp4(
+        &ffmpeg,
+        &frames(&[1.0]),
+        &dir.path().join("out.mp4"),
+        (1920, 1080),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Unknown encoder"), "{error}");
+}
+
+/// A stand-in ffmpeg that logs its arguments and creates the output file
+#[cfg(unix)]
+#[test]
+fn test_encode_narrated_muxes_clips() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let log = dir.path().join("calls.log");
+    let ffmpeg = dir.path().join("ffmpeg");
+    fs::write(
+        &ffmpeg,
+        format!(
+            "#!/bin/sh\n\
+             echo \"$*\" >> '{}'\n\
+             for last; do :; done\n\
+             touch \"$last\"\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&ffmpeg, fs::Permissions::from_mode(0o755)).unwrap();
+    let clip = dir.path().join("intro.mp3");
+    fs::write(&clip, b"").unwrap();
+
+    let mut frames = frames(&[3.0, 2.5]);
+    frames[0].audio = Some(clip.clone());
+    let output = dir.path().join("talk.mp4");
+    video::encode_narrated(&ffmpeg, &frames, &output, (1280, 720))
+        .expect("Encoding should succeed");
+
+    let calls = fs::read_to_string(&log).unwrap();
+    let calls: Vec<&str> = calls.lines().collect();
+    assert_eq!(calls.len(), 3, "Two segments and the join: {calls:?}");
+    assert!(calls[0].contains(&format!("-i {} -shortest", clip.display())));
+    assert!(calls[1].contains("anullsrc") && calls[1].contains("-t 2.500"));
+    assert!(calls[2].contains("-c copy") && calls[2].ends_with("talk.mp4"));
+    assert!(output.exists());
+    assert!(
+        !dir.path().join("talk.segments").exists(),
+        "Segments should be removed"
+    );
+}
+
+#[test]
+fn test_encode_narrated_requires_clip_files() {
+    let dir = TempDir::new().unwrap();
+    let mut frames = frames(&[1.0]);
+    frames[0].audio = Some(dir.path().join("missing.mp3"));
+
+    let error = video::encode_narrated(
+        Path::new("ffmpeg"),
+        &frames,
+        &dir.path().join("talk.mp4"),
+        (1920, 1080),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("missing.mp3"), "{error}");
+}
+
+#[test]
+fn test_video_format_from_str() {
+    assert_eq!("MP4".parse(), Ok(VideoFormat::Mp4));
+    assert_eq!("gif".parse(), Ok(VideoFormat::Gif));
+    assert_eq!("video".parse(), Ok(VideoFormat::Narrated));
+    assert_eq!(VideoFormat::Narrated.extension(), "mp4");
+    assert!("avi".parse::<VideoFormat>().is_err());
+    assert_eq!(VideoFormat::Gif.extension(), "gif");
+}